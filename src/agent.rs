@@ -0,0 +1,51 @@
+//! `--agent ADDR`: headless collector for `--connect` clients.
+//!
+//! Daemon mode that, every tick, sends each connected client the current
+//! snapshot as one line of compact JSON, the `snapshot_v1` format used
+//! everywhere else, so a client refuses an agent too new for it instead of
+//! misreading it. A listener thread accepts clients and the sampling loop
+//! writes to them; a client that stops reading for `WRITE_TIMEOUT` is
+//! dropped rather than holding up the others. There is no authentication
+//! or encryption: listen on localhost or a private network, or tunnel it.
+
+use std::io::{self, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct AgentServer {
+    clients: Arc<Mutex<Vec<TcpStream>>>,
+}
+
+impl AgentServer {
+    /// Bind `addr` (e.g. `0.0.0.0:9596`) and start accepting clients.
+    pub fn start(addr: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let shared = Arc::clone(&clients);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok()
+                    && let Ok(mut clients) = shared.lock()
+                {
+                    clients.push(stream);
+                }
+            }
+        });
+        Ok(Self { clients })
+    }
+
+    pub fn has_clients(&self) -> bool {
+        self.clients.lock().is_ok_and(|c| !c.is_empty())
+    }
+
+    /// Send `line` to every client, dropping those that have gone away.
+    pub fn broadcast(&self, line: &str) {
+        let line = format!("{line}\n");
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain_mut(|client| client.write_all(line.as_bytes()).is_ok());
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Critical alerts and the terminal signals that announce them.
+//!
+//! A condition has to hold for `ALERT_HOLD`, and over at least two ticks,
+//! before it counts, so a one-tick spike doesn't ring anything. While any
+//! alert is active the terminal gets an OSC 9;4 error progress state
+//! (taskbar / tab badge in Windows Terminal, ConEmu, WezTerm, Ghostty),
+//! and each new alert rings the bell once, which tmux turns into a window
+//! flag even when the pane is hidden. `alert_sound` swaps the bell for a
+//! sound file, for a monitor left running on a second screen with no
+//! terminal in view.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+pub const ALERT_HOLD: Duration = Duration::from_secs(3);
+pub const CPU_CRITICAL_PCT: f32 = 95.0;
+pub const MEMORY_CRITICAL_PCT: f64 = 95.0;
+pub const DISK_CRITICAL_PCT: f64 = 95.0;
+/// Where NVMe and SATA drives start throttling, as in the Disks tab colors
+pub const DRIVE_CRITICAL_CELSIUS: u32 = 70;
+pub const GPU_CRITICAL_CELSIUS: u32 = 90;
+
+#[derive(Clone)]
+pub struct Alert {
+    /// Stable identity, e.g. `mem` or `disk:/home`, so a changing value
+    /// in the message doesn't restart the hold
+    pub key: String,
+    pub message: String,
+}
+
+/// Which conditions have held long enough to alert.
+#[derive(Default)]
+pub struct AlertTracker {
+    /// When each condition was first seen, and whether it has fired
+    since: HashMap<String, (Instant, bool)>,
+}
+
+impl AlertTracker {
+    /// Feed this tick's conditions; returns the alerts that have held for
+    /// `ALERT_HOLD`, and which of those fired just now.
+    pub fn update(&mut self, conditions: Vec<Alert>) -> (Vec<Alert>, Vec<Alert>) {
+        self.since
+            .retain(|key, _| conditions.iter().any(|c| &c.key == key));
+        let now = Instant::now();
+        let mut active = Vec::new();
+        let mut fired = Vec::new();
+        for alert in conditions {
+            let (since, was_active) = self.since.entry(alert.key.clone()).or_insert((now, false));
+            if now.duration_since(*since) < ALERT_HOLD {
+                continue;
+            }
+            if !*was_active {
+                *was_active = true;
+                fired.push(alert.clone());
+            }
+            active.push(alert);
+        }
+        (active, fired)
+    }
+}
+
+/// What a newly fired alert sounds like.
+#[derive(Clone, PartialEq)]
+pub enum AlertSound {
+    /// The terminal bell (BEL)
+    Bell,
+    /// Silent; the progress state still shows
+    Off,
+    /// Played with the platform's command-line player
+    File(String),
+}
+
+/// Writes attention sequences straight to the terminal, outside ratatui's
+/// buffer; none of them move the cursor or draw anything.
+pub struct Attention {
+    enabled: bool,
+    sound: AlertSound,
+    showing: bool,
+    /// tmux drops unknown OSCs unless wrapped in a DCS passthrough
+    /// (and `allow-passthrough on`); the bell needs no wrapping
+    tmux: bool,
+}
+
+impl Attention {
+    pub fn new(enabled: bool, sound: AlertSound) -> Self {
+        Self {
+            enabled,
+            sound,
+            showing: false,
+            tmux: std::env::var_os("TMUX").is_some(),
+        }
+    }
+
+    /// Ring for newly fired alerts and keep the progress state in step
+    /// with whether any alert is active.
+    pub fn signal(&mut self, active: &[Alert], fired: &[Alert]) -> io::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let mut out = Vec::new();
+        if !fired.is_empty() {
+            match &self.sound {
+                AlertSound::Bell => out.push(0x07),
+                AlertSound::Off => {}
+                AlertSound::File(path) => play(path),
+            }
+        }
+        let want = !active.is_empty();
+        if want != self.showing {
+            // 9;4;2 is the error state at 100%, 9;4;0 clears it
+            out.extend(self.osc(if want { "9;4;2;100" } else { "9;4;0" }));
+            self.showing = want;
+        }
+        if out.is_empty() {
+            return Ok(());
+        }
+        let mut stdout = io::stdout();
+        stdout.write_all(&out)?;
+        stdout.flush()
+    }
+
+    fn osc(&self, body: &str) -> Vec<u8> {
+        if self.tmux {
+            format!("\x1bPtmux;\x1b\x1b]{body}\x07\x1b\\").into_bytes()
+        } else {
+            format!("\x1b]{body}\x07").into_bytes()
+        }
+    }
+}
+
+/// Start the player and leave it running; a missing player or file just
+/// means no sound. A thread reaps the child so it doesn't linger as a
+/// zombie.
+fn play(path: &str) {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("afplay");
+        cmd.arg(path);
+        cmd
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-Command",
+            &format!("(New-Object Media.SoundPlayer '{}').PlaySync()", path.replace('\'', "''")),
+        ]);
+        cmd
+    } else {
+        // PulseAudio/PipeWire first, plain ALSA if that isn't there
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "paplay \"$0\" 2>/dev/null || aplay -q \"$0\"", path]);
+        cmd
+    };
+    let child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        std::thread::spawn(move || child.wait());
+    }
+}
+
+impl Drop for Attention {
+    /// Don't leave the terminal tab badged after quitting.
+    fn drop(&mut self) {
+        if self.showing {
+            let _ = self.signal(&[], &[]);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Read-only HTTP endpoint for scripts and dashboards.
+//!
+//! With `api_listen` (or `--api`) set, `GET /api/v1/snapshot` returns the
+//! same JSON as `rustmonitor snapshot`, refreshed every tick. The UI thread
+//! publishes each snapshot into a shared slot and a listener thread serves
+//! whatever is there, so a slow client never holds up drawing. There is no
+//! authentication: keep it on localhost or behind a proxy.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const SNAPSHOT_PATH: &str = "/api/v1/snapshot";
+/// A client that hasn't sent its request line by then is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct ApiServer {
+    latest: Arc<Mutex<String>>,
+}
+
+impl ApiServer {
+    /// Bind `addr` (e.g. `127.0.0.1:9595`) and start serving.
+    pub fn start(addr: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let latest = Arc::new(Mutex::new(String::new()));
+        let shared = Arc::clone(&latest);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = respond(stream, &shared);
+            }
+        });
+        Ok(Self { latest })
+    }
+
+    /// Replace the snapshot served to the next request.
+    pub fn publish(&self, json: String) {
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = json;
+        }
+    }
+}
+
+fn respond(mut stream: TcpStream, latest: &Mutex<String>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    // Ignore any query string
+    let path = target.split('?').next().unwrap_or("");
+
+    let (status, body) = match (method, path) {
+        ("GET", SNAPSHOT_PATH) => {
+            let json = latest.lock().map(|j| j.clone()).unwrap_or_default();
+            if json.is_empty() {
+                ("503 Service Unavailable", error_body("no snapshot yet"))
+            } else {
+                ("200 OK", json)
+            }
+        }
+        ("GET", _) => ("404 Not Found", error_body("not found")),
+        _ => ("405 Method Not Allowed", error_body("only GET is supported")),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn error_body(message: &str) -> String {
+    format!("{{\"error\": \"{message}\"}}")
+}
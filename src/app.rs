@@ -1,9 +1,92 @@
-use sysinfo::{Disks, Networks, Pid, Signal, System};
-use std::collections::VecDeque;
-use std::time::Instant;
+use sysinfo::{Disks, IpNetwork, Networks, Pid, ProcessStatus, ProcessesToUpdate, Signal, System, Users};
+use std::collections::{vec_deque, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+#[cfg(feature = "nvml")]
 use nvml_wrapper::Nvml;
-
-const HISTORY_LEN: usize = 60;
+#[cfg(feature = "nvml")]
+use nvml_wrapper::enum_wrappers::device::Clock;
+#[cfg(feature = "nvml")]
+use nvml_wrapper::enums::device::UsedGpuMemory;
+
+#[cfg(target_os = "linux")]
+use crate::linux_gpu;
+use crate::alerts::{self, Alert, AlertTracker};
+use crate::cli::Cli;
+use crate::clipboard;
+use crate::config::{ColorDepth, Config};
+use crate::connections::{self, Connection};
+use crate::firewall::{self, FirewallStatus};
+use crate::fsinfo::{self, FilesystemDetail};
+use crate::geoip::GeoIp;
+use crate::ifinfo::{self, InterfaceDetail, LinkInfo};
+use crate::wifi::{self, WifiInfo};
+use crate::hardware::HardwareInfo;
+use crate::hooks::{self, HookEvent, Hooks};
+use crate::keymap::{Key, Keymap};
+use crate::events::{EventLevel, EventLog};
+use crate::export;
+use crate::syslog::Syslog;
+use crate::snapshot::{self, Snapshot};
+use crate::peaks::Peaks;
+use crate::summary::SessionCounts;
+use crate::ping::{self, PingMonitor};
+use crate::metrics::{self, MetricsExporter};
+use crate::meminfo::{self, MemoryBreakdown};
+use crate::leaks::{LeakDetector, LeakSuspect, LEAK_SAMPLE};
+use crate::profiler::{self, ProfileReport};
+use crate::rdns::{self, DnsCache};
+use crate::remote::RemoteFeed;
+use crate::smart::{self, SmartHealth};
+use crate::spawn::SpawnedCommand;
+use crate::sysctl::{self, KernelParam};
+use crate::tasks::TaskManager;
+use crate::theme::{self, ThemeColors, UserTheme};
+
+/// Samples kept per graph: `history_len`, `--history` or `{` / `}`. The
+/// default covers the longest graph window at the default refresh rate
+pub const DEFAULT_HISTORY_LEN: usize = 3600;
+pub const MIN_HISTORY_LEN: usize = 10;
+pub const MAX_HISTORY_LEN: usize = 86_400;
+/// Lengths `{` / `}` step through
+const HISTORY_LENGTHS: [usize; 8] = [30, 60, 120, 300, 600, 1200, 3600, 7200];
+/// Time spans `[` / `]` zoom the graphs between
+pub const GRAPH_WINDOWS: [Duration; 3] = [
+    Duration::from_secs(60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(30 * 60),
+];
+/// Tick intervals `+` / `-` step through
+pub const REFRESH_RATES: [Duration; 7] = [
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(3),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+/// Ticks of CPU and memory kept for the selected process's sparklines
+const PROCESS_HISTORY_LEN: usize = 300;
+/// Characters `←` / `→` move the Name column by
+const NAME_SCROLL_STEP: usize = 8;
+/// Firewall queries spawn a subprocess, so only poll every ~5s
+const FIREWALL_POLL: Duration = Duration::from_secs(5);
+/// Tunables rarely change; re-read them as often as the firewall
+const KERNEL_PARAM_POLL: Duration = Duration::from_secs(5);
+/// smartctl is slow and SMART data changes slowly
+const SMART_POLL: Duration = Duration::from_secs(300);
+const SMART_TASK: &str = "Reading SMART data";
+/// How long a signalled process gets to exit before SIGKILL is offered
+const KILL_WAIT: Duration = Duration::from_secs(3);
+const KILL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long quitting waits for exports and other visible tasks to finish
+const SHUTDOWN_WAIT: Duration = Duration::from_secs(3);
+/// Used space is sampled every ~5s for the growth sparklines
+const DISK_GROWTH_SAMPLE: Duration = Duration::from_secs(5);
+/// Socket tables churn quickly; re-read every second while visible
+const CONNECTION_POLL: Duration = Duration::from_secs(1);
+/// `system_profiler` takes a second or two on macOS
+const WIFI_POLL: Duration = Duration::from_secs(2);
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum Tab {
@@ -11,11 +94,20 @@ pub enum Tab {
     Processes,
     SystemInfo,
     NetworkDetail,
+    Disks,
+    Connections,
 }
 
 impl Tab {
     pub fn all() -> &'static [Tab] {
-        &[Tab::Overview, Tab::Processes, Tab::SystemInfo, Tab::NetworkDetail]
+        &[
+            Tab::Overview,
+            Tab::Processes,
+            Tab::SystemInfo,
+            Tab::NetworkDetail,
+            Tab::Disks,
+            Tab::Connections,
+        ]
     }
 
     pub fn index(self) -> usize {
@@ -24,6 +116,8 @@ impl Tab {
             Tab::Processes => 1,
             Tab::SystemInfo => 2,
             Tab::NetworkDetail => 3,
+            Tab::Disks => 4,
+            Tab::Connections => 5,
         }
     }
 
@@ -33,6 +127,37 @@ impl Tab {
             Tab::Processes => " Processes ",
             Tab::SystemInfo => " System ",
             Tab::NetworkDetail => " Network ",
+            Tab::Disks => " Disks ",
+            Tab::Connections => " Connections ",
+        }
+    }
+}
+
+/// History graphs that can be drawn as a line chart with axes instead of
+/// a sparkline (`C`, `M` and `N`, or `line_charts`)
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Graph {
+    Cpu,
+    Memory,
+    /// RX and TX overlaid on one chart
+    Network,
+}
+
+impl Graph {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "cpu" => Some(Graph::Cpu),
+            "memory" => Some(Graph::Memory),
+            "network" => Some(Graph::Network),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Graph::Cpu => "CPU",
+            Graph::Memory => "Memory",
+            Graph::Network => "Network",
         }
     }
 }
@@ -43,15 +168,21 @@ pub enum SortBy {
     Memory,
     Name,
     Pid,
+    DiskIo,
+    /// Linux only
+    Swap,
 }
 
 impl SortBy {
     pub fn label(self) -> &'static str {
         match self {
             SortBy::Cpu => "CPU ▼",
+            // Also the MEM% order, which is the same ranking
             SortBy::Memory => "MEM ▼",
             SortBy::Name => "NAME ▼",
             SortBy::Pid => "PID ▼",
+            SortBy::DiskIo => "I/O ▼",
+            SortBy::Swap => "SWAP ▼",
         }
     }
 
@@ -60,7 +191,219 @@ impl SortBy {
             SortBy::Cpu => SortBy::Memory,
             SortBy::Memory => SortBy::Name,
             SortBy::Name => SortBy::Pid,
-            SortBy::Pid => SortBy::Cpu,
+            SortBy::Pid => SortBy::DiskIo,
+            SortBy::DiskIo if cfg!(target_os = "linux") => SortBy::Swap,
+            SortBy::DiskIo | SortBy::Swap => SortBy::Cpu,
+        }
+    }
+}
+
+/// Columns of the Processes table, shown and hidden with the column
+/// chooser or `process_columns`
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProcessColumn {
+    Pid,
+    Name,
+    Cpu,
+    Memory,
+    /// Share of total RAM
+    MemoryPercent,
+    Virtual,
+    /// Resident memory shared with other processes (Linux)
+    Shared,
+    /// Resident memory minus the shared part (Linux)
+    Private,
+    /// Swapped out (Linux)
+    Swap,
+    Runtime,
+    DiskIo,
+    /// Only drawn with the `net-accounting` feature
+    NetIo,
+    Status,
+    User,
+    Threads,
+}
+
+impl ProcessColumn {
+    pub const ALL: [ProcessColumn; 15] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::Memory,
+        ProcessColumn::MemoryPercent,
+        ProcessColumn::Virtual,
+        ProcessColumn::Shared,
+        ProcessColumn::Private,
+        ProcessColumn::Swap,
+        ProcessColumn::Runtime,
+        ProcessColumn::DiskIo,
+        ProcessColumn::NetIo,
+        ProcessColumn::Status,
+        ProcessColumn::User,
+        ProcessColumn::Threads,
+    ];
+
+    /// The columns shown before any configuration.
+    pub fn defaults() -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|c| {
+                !matches!(
+                    c,
+                    ProcessColumn::Virtual
+                        | ProcessColumn::Shared
+                        | ProcessColumn::Private
+                        | ProcessColumn::Swap
+                        | ProcessColumn::User
+                        | ProcessColumn::Threads
+                )
+            })
+            .collect()
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+
+    /// Name in `process_columns`
+    pub fn name(self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "pid",
+            ProcessColumn::Name => "name",
+            ProcessColumn::Cpu => "cpu",
+            ProcessColumn::Memory => "memory",
+            ProcessColumn::MemoryPercent => "memory_pct",
+            ProcessColumn::Virtual => "virtual",
+            ProcessColumn::Shared => "shared",
+            ProcessColumn::Private => "private",
+            ProcessColumn::Swap => "swap",
+            ProcessColumn::Runtime => "runtime",
+            ProcessColumn::DiskIo => "disk",
+            ProcessColumn::NetIo => "net",
+            ProcessColumn::Status => "status",
+            ProcessColumn::User => "user",
+            ProcessColumn::Threads => "threads",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::Name => "Name",
+            ProcessColumn::Cpu => "CPU%",
+            ProcessColumn::Memory => "Memory",
+            ProcessColumn::MemoryPercent => "MEM%",
+            ProcessColumn::Virtual => "Virtual",
+            ProcessColumn::Shared => "Shared",
+            ProcessColumn::Private => "Private",
+            ProcessColumn::Swap => "Swap",
+            ProcessColumn::Runtime => "Runtime",
+            ProcessColumn::DiskIo => "Disk R/W/s",
+            ProcessColumn::NetIo => "Net RX/TX",
+            ProcessColumn::Status => "Status",
+            ProcessColumn::User => "User",
+            ProcessColumn::Threads => "Threads",
+        }
+    }
+
+    /// Kept in large UI mode, which drops everything else for room
+    pub fn essential(self) -> bool {
+        matches!(
+            self,
+            ProcessColumn::Pid | ProcessColumn::Name | ProcessColumn::Cpu | ProcessColumn::Memory
+        )
+    }
+
+    /// Whether this build can fill the column
+    pub fn available(self) -> bool {
+        let linux_only = matches!(self, ProcessColumn::Shared | ProcessColumn::Private | ProcessColumn::Swap);
+        (self != ProcessColumn::NetIo || cfg!(feature = "net-accounting")) && (!linux_only || cfg!(target_os = "linux"))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DiskSortBy {
+    Mount,
+    Usage,
+    Read,
+    Write,
+    Busy,
+}
+
+impl DiskSortBy {
+    pub fn label(self) -> &'static str {
+        match self {
+            DiskSortBy::Mount => "MOUNT ▲",
+            DiskSortBy::Usage => "USE% ▼",
+            DiskSortBy::Read => "READ ▼",
+            DiskSortBy::Write => "WRITE ▼",
+            DiskSortBy::Busy => "BUSY ▼",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            DiskSortBy::Mount => DiskSortBy::Usage,
+            DiskSortBy::Usage => DiskSortBy::Read,
+            DiskSortBy::Read => DiskSortBy::Write,
+            DiskSortBy::Write => DiskSortBy::Busy,
+            DiskSortBy::Busy => DiskSortBy::Mount,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConnSortBy {
+    Process,
+    Port,
+    Remote,
+    State,
+}
+
+impl ConnSortBy {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConnSortBy::Process => "PROCESS ▲",
+            ConnSortBy::Port => "PORT ▲",
+            ConnSortBy::Remote => "REMOTE ▲",
+            ConnSortBy::State => "STATE ▲",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ConnSortBy::Process => ConnSortBy::Port,
+            ConnSortBy::Port => ConnSortBy::Remote,
+            ConnSortBy::Remote => ConnSortBy::State,
+            ConnSortBy::State => ConnSortBy::Process,
+        }
+    }
+}
+
+/// What the process search matches besides the name and PID.
+#[derive(Clone, Copy, PartialEq)]
+pub enum SearchScope {
+    Name,
+    CommandLine,
+    /// Reads every process's environment, so slower; other users'
+    /// processes only when running as root
+    Environment,
+}
+
+impl SearchScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchScope::Name => "Name/PID",
+            SearchScope::CommandLine => "Name + Cmdline",
+            SearchScope::Environment => "Name + Cmdline + Env",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            SearchScope::Name => SearchScope::CommandLine,
+            SearchScope::CommandLine => SearchScope::Environment,
+            SearchScope::Environment => SearchScope::Name,
         }
     }
 }
@@ -69,6 +412,7 @@ impl SortBy {
 pub enum InputMode {
     Normal,
     Search,
+    Command,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -77,15 +421,74 @@ pub enum Theme {
     Ocean,
     Forest,
     Sunset,
+    Dracula,
+    Gruvbox,
+    Nord,
+    SolarizedDark,
+    /// Catppuccin Mocha
+    Catppuccin,
+    /// For light terminal backgrounds
+    Light,
+    SolarizedLight,
+    /// Index into `App::user_themes`
+    User(usize),
 }
 
 impl Theme {
-    pub fn next(self) -> Self {
+    /// In `t` order; user themes follow.
+    const BUILT_IN: [Theme; 11] = [
+        Theme::Default,
+        Theme::Ocean,
+        Theme::Forest,
+        Theme::Sunset,
+        Theme::Dracula,
+        Theme::Gruvbox,
+        Theme::Nord,
+        Theme::SolarizedDark,
+        Theme::Catppuccin,
+        Theme::Light,
+        Theme::SolarizedLight,
+    ];
+
+    /// A built-in theme by its label, case-insensitive, with `-` or `_`
+    /// for spaces: `nord`, `solarized-dark`.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::BUILT_IN
+            .into_iter()
+            .find(|theme| same_theme_name(theme.label(), name))
+    }
+
+    /// The theme `theme = name` picks: a built-in one, else a user theme
+    /// of that name.
+    pub fn resolve(name: &str, user_themes: &[UserTheme]) -> Option<Self> {
+        Self::parse(name).or_else(|| {
+            let i = user_themes.iter().position(|t| same_theme_name(&t.name, name))?;
+            Some(Theme::User(i))
+        })
+    }
+
+    /// This theme's colors; `User` indexes into `user_themes`.
+    pub fn colors(self, user_themes: &[UserTheme]) -> ThemeColors {
         match self {
-            Theme::Default => Theme::Ocean,
-            Theme::Ocean => Theme::Forest,
-            Theme::Forest => Theme::Sunset,
-            Theme::Sunset => Theme::Default,
+            Theme::User(i) => user_themes[i].colors.clone(),
+            theme => ThemeColors::from_theme(theme),
+        }
+    }
+
+    /// The theme after this one, with `user_themes` loaded after the
+    /// built-in ones.
+    pub fn next(self, user_themes: usize) -> Self {
+        match self {
+            Theme::User(i) if i + 1 < user_themes => Theme::User(i + 1),
+            Theme::User(_) => Theme::Default,
+            built_in => {
+                let i = Self::BUILT_IN.iter().position(|&t| t == built_in).unwrap_or(0);
+                match Self::BUILT_IN.get(i + 1) {
+                    Some(&next) => next,
+                    None if user_themes > 0 => Theme::User(0),
+                    None => Theme::Default,
+                }
+            }
         }
     }
 
@@ -95,10 +498,25 @@ impl Theme {
             Theme::Ocean => "Ocean",
             Theme::Forest => "Forest",
             Theme::Sunset => "Sunset",
+            Theme::Dracula => "Dracula",
+            Theme::Gruvbox => "Gruvbox",
+            Theme::Nord => "Nord",
+            Theme::SolarizedDark => "Solarized Dark",
+            Theme::Catppuccin => "Catppuccin",
+            Theme::Light => "Light",
+            Theme::SolarizedLight => "Solarized Light",
+            Theme::User(_) => "Custom",
         }
     }
 }
 
+/// Theme names match ignoring case, with `-` and `_` standing for spaces.
+fn same_theme_name(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| s.to_lowercase().replace(['-', '_'], " ");
+    normalize(a) == normalize(b)
+}
+
+#[derive(Clone)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
@@ -106,8 +524,38 @@ pub struct ProcessInfo {
     pub memory: u64,
     pub status: String,
     pub run_time: u64,
+    /// Bytes over the process's lifetime; 0 when monitoring a remote host
     pub disk_read: u64,
     pub disk_write: u64,
+    /// Bytes per second over the last refresh
+    pub disk_read_rate: u64,
+    pub disk_write_rate: u64,
+    /// TCP bytes per second; always 0 without the `net-accounting` feature
+    pub net_rx_rate: u64,
+    pub net_tx_rate: u64,
+    /// Executable and arguments, empty when unreadable (kernel threads,
+    /// other users' processes on macOS)
+    pub cmd: String,
+    /// Owner's login name, empty when unknown
+    pub user: String,
+    /// `None` where the platform doesn't list a process's threads
+    pub threads: Option<u64>,
+    pub virtual_memory: u64,
+    /// Only read while the Shared or Private column is shown (Linux)
+    pub shared_memory: Option<u64>,
+    /// Only read while the Swap column is shown or sorted by (Linux)
+    pub swap: Option<u64>,
+}
+
+impl ProcessInfo {
+    pub fn disk_io_rate(&self) -> u64 {
+        self.disk_read_rate + self.disk_write_rate
+    }
+
+    /// Resident memory that's the process's own rather than shared
+    pub fn private_memory(&self) -> Option<u64> {
+        self.shared_memory.map(|shared| self.memory.saturating_sub(shared))
+    }
 }
 
 pub struct NetworkInterface {
@@ -118,9 +566,32 @@ pub struct NetworkInterface {
     pub packets_out: u64,
     pub errors_in: u64,
     pub errors_out: u64,
+    /// (RX, TX) packets dropped since the last refresh, like the errors;
+    /// `None` where the platform doesn't report drops
+    pub drops: Option<(u64, u64)>,
     pub mac_address: String,
+    pub addresses: Vec<IpNetwork>,
+    pub mtu: u64,
+    /// Bytes since boot
+    pub total_received: u64,
+    pub total_transmitted: u64,
+    /// Bytes since RustMonitor started
+    pub session_received: u64,
+    pub session_transmitted: u64,
+    pub link: LinkInfo,
 }
 
+impl NetworkInterface {
+    /// Known to be down; platforms without link state never are.
+    pub fn is_down(&self) -> bool {
+        matches!(
+            self.link.state.as_deref(),
+            Some("down" | "lowerlayerdown" | "notpresent")
+        )
+    }
+}
+
+#[derive(Default)]
 pub struct GpuInfo {
     pub name: String,
     pub temperature: u32,
@@ -130,6 +601,72 @@ pub struct GpuInfo {
     pub fan_speed: Option<u32>,
     pub power_usage: Option<u32>,
     pub power_limit: Option<u32>,
+    pub fan_rpm: Option<u32>,
+    pub core_clock_mhz: Option<u32>,
+    pub memory_clock_mhz: Option<u32>,
+    pub memory_busy: Option<u32>,
+    pub temp_junction: Option<u32>,
+    pub temp_memory: Option<u32>,
+    pub processes: Vec<GpuProcess>,
+}
+
+/// A process with a context on a GPU, as reported by the driver.
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,
+    /// Not available under WDDM on Windows
+    pub memory: Option<u64>,
+    pub compute: bool,
+    pub graphics: bool,
+}
+
+impl GpuProcess {
+    /// nvidia-smi style type column: C, G or C+G.
+    pub fn kind(&self) -> &'static str {
+        match (self.compute, self.graphics) {
+            (true, true) => "C+G",
+            (true, false) => "C",
+            _ => "G",
+        }
+    }
+}
+
+/// A mounted filesystem with the I/O rates of its backing device.
+pub struct DiskInfo {
+    pub device: String,
+    /// Whole-disk device holding this filesystem, for SMART lookups
+    pub parent_disk: Option<String>,
+    pub mount: String,
+    pub file_system: String,
+    pub total: u64,
+    pub used: u64,
+    pub read_per_sec: u64,
+    pub write_per_sec: u64,
+    /// Drive temperature from hwmon (Linux); SMART fills in elsewhere
+    pub temperature: Option<u32>,
+    /// Request-level stats, only where the OS exposes them (Linux)
+    pub iops: Option<f64>,
+    pub busy: Option<f64>,
+    pub queue_depth: Option<f64>,
+}
+
+impl DiskInfo {
+    pub fn usage_pct(&self) -> f64 {
+        if self.total > 0 {
+            self.used as f64 / self.total as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A CPU cluster on heterogeneous SoCs (Apple Silicon E/P clusters).
+pub struct CpuCluster {
+    pub name: String,
+    pub efficiency: bool,
+    pub utilization: u32,
+    pub freq_mhz: Option<u32>,
+    pub power_mw: Option<u32>,
 }
 
 pub struct ProcessDetail {
@@ -141,12 +678,40 @@ pub struct ProcessDetail {
     pub environ_count: usize,
     pub threads: Option<u64>,
     pub virtual_memory: u64,
+    /// Thread CPU grouped by name, busiest first (Linux)
+    pub thread_groups: Vec<ThreadGroup>,
+    /// Start time, so a reused PID isn't mistaken for the same process
+    pub start_time: u64,
+    /// Set once the process is gone; the last stats stay on screen
+    pub exited: bool,
+    /// Direct children, in the process table's order
+    pub children: Vec<ProcessInfo>,
+}
+
+/// Recent CPU and memory of one process, for the detail popup. Follows
+/// the selection, restarting whenever it lands on a different process.
+pub struct ProcessHistory {
+    pub pid: u32,
+    start_time: u64,
+    pub cpu: VecDeque<f64>,
+    /// Resident bytes
+    pub memory: VecDeque<f64>,
+}
+
+/// Threads sharing a name once pool numbering is stripped, e.g.
+/// `tokio-runtime-w`, or `worker-*` for `worker-1`, `worker-2`, ...
+pub struct ThreadGroup {
+    pub name: String,
+    pub threads: usize,
+    pub cpu: f32,
 }
 
 pub struct App {
     pub system: System,
     pub disks: Disks,
     pub networks: Networks,
+    /// Login names for the User column, read once at startup
+    users: Users,
 
     // History data
     pub cpu_history: Vec<VecDeque<f64>>,
@@ -154,6 +719,34 @@ pub struct App {
     pub mem_history: VecDeque<f64>,
     pub net_rx_history: VecDeque<f64>,
     pub net_tx_history: VecDeque<f64>,
+    /// (RX, TX) KB/s for each interface, like the totals above
+    pub interface_history: HashMap<String, (VecDeque<f64>, VecDeque<f64>)>,
+    /// Network tab graphs follow the selected interface (`i` toggles)
+    pub graph_selected_interface: bool,
+    /// (RX, TX) bytes per interface since startup; kept for interfaces
+    /// that have gone away so the session total stays complete
+    session_transfer: HashMap<String, (u64, u64)>,
+    /// Cumulative drop counters from the last refresh, to turn into deltas
+    drop_totals: HashMap<String, (u64, u64)>,
+    bandwidth_threshold: Option<f64>,
+    bandwidth_limits: HashMap<String, u64>,
+    bandwidth_warning: bool,
+    hooks: Hooks,
+    /// StatsD / Graphite / InfluxDB output, when `metrics_output` is set
+    metrics: Option<MetricsExporter>,
+    /// Alert copies for log pipelines, when `syslog` is on
+    syslog: Option<Syslog>,
+    /// Interfaces at or above `bandwidth_threshold` of their capacity
+    pub saturated_interfaces: Vec<String>,
+    /// Wireless interfaces, refreshed while the Network tab is open
+    pub wifi: Vec<WifiInfo>,
+    /// Signal quality % per wireless interface
+    pub wifi_signal_history: HashMap<String, VecDeque<f64>>,
+    /// Latency to the gateway or `ping_target`; `None` when turned off
+    pub ping: Option<PingMonitor>,
+    ping_interval: Duration,
+    pub disk_read_history: VecDeque<f64>,
+    pub disk_write_history: VecDeque<f64>,
 
     // Current stat
     pub processes: Vec<ProcessInfo>,
@@ -162,10 +755,49 @@ pub struct App {
     pub used_memory: u64,
     pub total_swap: u64,
     pub used_swap: u64,
+    /// Buffers, cache and commit charge; `None` off Linux and remotely
+    pub memory_breakdown: Option<MemoryBreakdown>,
     pub cpu_count: usize,
     pub global_cpu: f32,
+    pub cpu_clusters: Vec<CpuCluster>,
+    pub cpu_power_mw: Option<u32>,
+    pub ane_power_mw: Option<u32>,
+    pub package_power_mw: Option<u32>,
     pub net_rx: u64,
     pub net_tx: u64,
+    pub disk_read: u64,
+    pub disk_write: u64,
+    pub disk_devices: Vec<DiskInfo>,
+    /// Used bytes per mount and when they were read, one sample every
+    /// `DISK_GROWTH_SAMPLE`
+    pub disk_used_history: HashMap<String, VecDeque<(Instant, u64)>>,
+    pub connections: Vec<Connection>,
+    /// SMART health keyed by whole-disk device
+    pub smart: HashMap<String, SmartHealth>,
+    smart_polled_at: Option<Instant>,
+    /// When networks, disks and processes were last refreshed; their
+    /// counters are bytes since then. `None` before the first refresh,
+    /// whose counters run from boot
+    rate_sample_time: Option<Instant>,
+    disk_sample_time: Instant,
+    #[cfg(target_os = "linux")]
+    diskstats: crate::diskstats::DiskStatsSampler,
+    pub firewall: FirewallStatus,
+    pub tasks: TaskManager,
+    pub kernel_param_names: Vec<String>,
+    pub kernel_params: Vec<KernelParam>,
+    leaks: LeakDetector,
+    #[cfg(feature = "net-accounting")]
+    net_accounting: crate::netacct::NetAccounting,
+    /// Leak threshold, bytes per minute
+    leak_slope: f64,
+    profile_secs: u64,
+    pub leak_suspects: Vec<LeakSuspect>,
+    alert_tracker: AlertTracker,
+    /// Critical conditions that have held for `ALERT_HOLD`
+    pub alerts: Vec<Alert>,
+    /// Alerts that became active on the last tick
+    pub fired_alerts: Vec<Alert>,
 
     // System info
     pub hostname: String,
@@ -176,36 +808,147 @@ pub struct App {
     pub cpu_arch: String,
     pub boot_time: u64,
     pub start_time: Instant,
+    pub hardware: HardwareInfo,
+    pub show_identifiers: bool,
 
     // UI state
+    pub no_color: bool,
+    /// Palette the theme colors are mapped to
+    pub color_depth: ColorDepth,
+    pub ascii_only: bool,
     pub active_tab: Tab,
     pub sort_by: SortBy,
+    /// Enabled Processes table columns, in display order
+    pub process_columns: Vec<ProcessColumn>,
+    /// Cursor row while the column chooser is open
+    pub column_chooser: Option<usize>,
+    /// Selected row of the filtered process list
     pub process_scroll: usize,
+    /// First row in view; moves only when the selection reaches an edge
+    pub process_offset: usize,
+    /// Rows the current tab's list showed in the last frame, which half a
+    /// page (`half_page_down` / `half_page_up`) is half of
+    pub list_rows: usize,
+    /// Characters cut from the start of the Name column (`←` / `→`)
+    pub name_scroll: usize,
+    /// Name column shows the full command line (`c` toggles)
+    pub show_cmdline: bool,
     pub network_scroll: usize,
+    pub disk_scroll: usize,
+    pub disk_sort: DiskSortBy,
+    /// Hide pseudo filesystems and repeated mounts of the same device
+    pub filter_disks: bool,
+    hidden_filesystems: Vec<String>,
+    pub gpu_process_scroll: usize,
     pub input_mode: InputMode,
+    pub keymap: Keymap,
+    /// First key of a sequence like `g g`, waiting for the second
+    pub key_prefix: Option<Key>,
     pub search_query: String,
+    /// Tab while searching widens the process search
+    pub search_scope: SearchScope,
     pub filtered_processes: Vec<usize>,
+    pub connection_scroll: usize,
+    pub connection_sort: ConnSortBy,
+    /// Search on the Connections tab, kept apart from the process filter
+    pub connection_query: String,
+    pub filtered_connections: Vec<usize>,
+    /// Resolve remote addresses to host names (`n` toggles)
+    pub reverse_dns: bool,
+    pub dns: DnsCache,
+    /// Country / ASN databases, once loaded
+    pub geoip: Option<GeoIp>,
     pub theme: Theme,
+    /// Themes from the `themes` config directory, after the built-in ones
+    pub user_themes: Vec<UserTheme>,
+    /// Taller rows, bigger gauges and fewer columns (`L` toggles)
+    pub large_ui: bool,
+    /// Network rates in Mbit/s rather than MB/s
+    pub rate_bits: bool,
     pub show_help: bool,
+    /// Lines scrolled off the top of the help popup
+    pub help_scroll: usize,
     pub kill_confirm: Option<u32>,
+    /// PIDs, start times and names waiting for confirmation to kill every
+    /// process the search matches
+    pub kill_all_confirm: Option<Vec<(u32, u64, String)>>,
+    /// The pending confirmation is to escalate to SIGKILL
+    pub kill_force: bool,
     pub status_message: Option<(String, Instant)>,
+    /// Alerts, kills, command results and errors, for the `E` popup
+    pub events: EventLog,
+    pub show_events: bool,
+    /// Events skipped from the top of the popup
+    pub events_scroll: usize,
     pub tick_count: u64,
+    /// Time between refreshes, `refresh_rate` at startup and `+` / `-`
+    pub tick_rate: Duration,
+    /// Samples kept in every graph history
+    pub history_len: usize,
+    /// Samples recorded so far, up to `history_len`; the rest of each
+    /// history is the zero padding it started with
+    pub history_filled: usize,
+    /// How far back the graphs reach, one of `GRAPH_WINDOWS`
+    pub graph_window: Duration,
+    /// Graphs drawn as line charts rather than sparklines
+    pub line_charts: HashSet<Graph>,
     pub show_process_detail: bool,
     pub process_detail: Option<ProcessDetail>,
+    pub process_history: Option<ProcessHistory>,
+    /// Child highlighted in the detail popup, by PID so it survives refreshes
+    pub detail_child: Option<u32>,
+    pub command_input: String,
+    pub spawned: Vec<SpawnedCommand>,
+    /// The last process launched with `R`, kept at the top of the table
+    /// until it exits
+    pub pinned_pid: Option<u32>,
+    /// The prompt was opened with `R`, so what it runs gets pinned
+    run_pins: bool,
+    /// The last `:run` process; while the search is exactly its PID, only
+    /// that process matches rather than every PID containing the digits
+    run_pid: Option<u32>,
+    pub show_command_output: bool,
+    pub profile_report: Option<ProfileReport>,
+    pub disk_detail: Option<FilesystemDetail>,
+    pub interface_detail: Option<InterfaceDetail>,
+    pub peaks: Peaks,
+    /// Like `peaks` but never reset, for the exit summary
+    pub session_peaks: Peaks,
+    pub session: SessionCounts,
+    #[cfg(feature = "nvml")]
     pub nvml: Option<Nvml>,
+    /// Which NVML library was loaded, or why none was
+    pub nvml_status: String,
+    /// Poll `nvidia-smi` instead; cleared the first time it fails
+    pub nvidia_smi_fallback: bool,
     pub gpus: Vec<GpuInfo>,
     pub gpu_util_history: Vec<VecDeque<f64>>,
+    /// `--remote`: snapshots from another host replace local sampling
+    pub remote: Option<RemoteFeed>,
     #[cfg(target_os = "macos")]
     pub apple_gpu_sampler: Option<crate::macos_gpu::AppleGpuSampler>,
+    #[cfg(target_os = "linux")]
+    pub intel_gpu_sampler: linux_gpu::IntelGpuSampler,
+    #[cfg(target_os = "windows")]
+    pub windows_gpu_sampler: Option<crate::windows_gpu::WindowsGpuSampler>,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(config: Config, cli: &Cli) -> Self {
         let mut system = System::new_all();
         system.refresh_all();
         let disks = Disks::new_with_refreshed_list();
         let networks = Networks::new_with_refreshed_list();
         let cpu_count = system.cpus().len();
+        let history_len = cli.history_len.unwrap_or(config.history_len);
+        let (user_themes, mut theme_errors) = theme::load_user_themes();
+        let theme = match &config.theme {
+            None => Theme::Default,
+            Some(name) => Theme::resolve(name, &user_themes).unwrap_or_else(|| {
+                theme_errors.push(format!("no theme named {name}"));
+                Theme::Default
+            }),
+        };
 
         let cpu_brand = system
             .cpus()
@@ -213,6 +956,11 @@ impl App {
             .map(|c| c.brand().to_string())
             .unwrap_or_else(|| "Unknown".into());
 
+        #[cfg(feature = "nvml")]
+        let (nvml, nvml_status) = crate::nvml::load(config.nvml_path.as_deref());
+        #[cfg(not(feature = "nvml"))]
+        let nvml_status = "not built in (using nvidia-smi)".to_string();
+
         let mut app = Self {
             hostname: System::host_name().unwrap_or_else(|| "Unknown".into()),
             os_name: System::name().unwrap_or_else(|| "Unknown".into()),
@@ -222,57 +970,320 @@ impl App {
             cpu_arch: std::env::consts::ARCH.to_string(),
             boot_time: System::boot_time(),
             start_time: Instant::now(),
+            hardware: HardwareInfo::default(),
+            show_identifiers: cli.show_identifiers,
 
             system,
             disks,
             networks,
-            cpu_history: vec![VecDeque::from(vec![0.0; HISTORY_LEN]); cpu_count],
-            global_cpu_history: VecDeque::from(vec![0.0; HISTORY_LEN]),
-            mem_history: VecDeque::from(vec![0.0; HISTORY_LEN]),
-            net_rx_history: VecDeque::from(vec![0.0; HISTORY_LEN]),
-            net_tx_history: VecDeque::from(vec![0.0; HISTORY_LEN]),
+            users: Users::new_with_refreshed_list(),
+            cpu_history: vec![empty_history(history_len); cpu_count],
+            global_cpu_history: empty_history(history_len),
+            mem_history: empty_history(history_len),
+            net_rx_history: empty_history(history_len),
+            net_tx_history: empty_history(history_len),
+            interface_history: HashMap::new(),
+            session_transfer: HashMap::new(),
+            drop_totals: HashMap::new(),
+            bandwidth_threshold: config.bandwidth_threshold,
+            bandwidth_limits: config.bandwidth_limits.clone(),
+            bandwidth_warning: config.bandwidth_warning,
+            hooks: config.hooks.clone(),
+            metrics: config.metrics_output.clone().map(|target| {
+                MetricsExporter::new(
+                    target,
+                    config.metrics_token.clone(),
+                    config.metrics_prefix.clone(),
+                    config.metrics_interval,
+                )
+            }),
+            syslog: config.syslog.then(Syslog::open),
+            saturated_interfaces: Vec::new(),
+            graph_selected_interface: false,
+            wifi: Vec::new(),
+            wifi_signal_history: HashMap::new(),
+            ping: config
+                .ping_enabled
+                .then(|| PingMonitor::new(config.ping_target.clone())),
+            ping_interval: Duration::from_secs(config.ping_interval),
+            disk_read_history: empty_history(history_len),
+            disk_write_history: empty_history(history_len),
             processes: Vec::new(),
             network_interfaces: Vec::new(),
             total_memory: 0,
             used_memory: 0,
             total_swap: 0,
             used_swap: 0,
+            memory_breakdown: None,
             cpu_count,
             global_cpu: 0.0,
+            cpu_clusters: Vec::new(),
+            cpu_power_mw: None,
+            ane_power_mw: None,
+            package_power_mw: None,
             net_rx: 0,
             net_tx: 0,
-
+            disk_read: 0,
+            disk_write: 0,
+            disk_devices: Vec::new(),
+            disk_used_history: HashMap::new(),
+            connections: Vec::new(),
+            smart: HashMap::new(),
+            smart_polled_at: None,
+            rate_sample_time: None,
+            disk_sample_time: Instant::now(),
+            #[cfg(target_os = "linux")]
+            diskstats: crate::diskstats::DiskStatsSampler::default(),
+            firewall: firewall::query(),
+            tasks: TaskManager::default(),
+            kernel_params: sysctl::read(&config.kernel_params),
+            kernel_param_names: config.kernel_params.clone(),
+            leaks: LeakDetector::default(),
+            #[cfg(feature = "net-accounting")]
+            net_accounting: Default::default(),
+            leak_slope: config.leak_slope * 1024.0 * 1024.0,
+            profile_secs: config.profile_secs,
+            leak_suspects: Vec::new(),
+            alert_tracker: AlertTracker::default(),
+            alerts: Vec::new(),
+            fired_alerts: Vec::new(),
+
+            no_color: config.no_color(),
+            color_depth: config.color_depth(),
+            ascii_only: config.ascii_only(),
             active_tab: Tab::Overview,
             sort_by: SortBy::Cpu,
+            process_columns: config.process_columns.clone(),
+            column_chooser: None,
             process_scroll: 0,
+            process_offset: 0,
+            list_rows: 10,
+            name_scroll: 0,
+            show_cmdline: config.show_cmdline,
             network_scroll: 0,
+            disk_scroll: 0,
+            disk_sort: DiskSortBy::Mount,
+            filter_disks: config.disk_filter,
+            hidden_filesystems: config.hidden_filesystems.clone(),
+            gpu_process_scroll: 0,
             input_mode: InputMode::Normal,
+            key_prefix: None,
+            keymap: Keymap::new(config.keymap, &config.key_bindings),
             search_query: String::new(),
+            search_scope: SearchScope::Name,
             filtered_processes: Vec::new(),
-            theme: Theme::Default,
+            connection_scroll: 0,
+            connection_sort: ConnSortBy::Process,
+            connection_query: String::new(),
+            filtered_connections: Vec::new(),
+            reverse_dns: config.reverse_dns,
+            large_ui: config.large_ui,
+            rate_bits: config.rate_bits,
+            geoip: None,
+            dns: DnsCache::default(),
+            theme,
+            user_themes,
             show_help: false,
+            help_scroll: 0,
             kill_confirm: None,
+            kill_all_confirm: None,
+            kill_force: false,
             status_message: None,
+            events: EventLog::default(),
+            show_events: false,
+            events_scroll: 0,
             tick_count: 0,
+            tick_rate: config.refresh_rate,
+            history_len,
+            history_filled: 0,
+            graph_window: GRAPH_WINDOWS[0],
+            line_charts: config.line_charts,
             show_process_detail: false,
             process_detail: None,
-            nvml: Nvml::init().ok(),
+            process_history: None,
+            detail_child: None,
+            command_input: String::new(),
+            spawned: Vec::new(),
+            pinned_pid: None,
+            run_pins: false,
+            run_pid: None,
+            show_command_output: false,
+            profile_report: None,
+            disk_detail: None,
+            interface_detail: None,
+            peaks: Peaks::default(),
+            session_peaks: Peaks::default(),
+            session: SessionCounts::default(),
+            #[cfg(feature = "nvml")]
+            nvml,
+            nvml_status,
+            nvidia_smi_fallback: false,
             gpus: Vec::new(),
             gpu_util_history: Vec::new(),
+            remote: None,
             #[cfg(target_os = "macos")]
             apple_gpu_sampler: crate::macos_gpu::AppleGpuSampler::new(),
+            #[cfg(target_os = "linux")]
+            intel_gpu_sampler: linux_gpu::IntelGpuSampler::default(),
+            #[cfg(target_os = "windows")]
+            windows_gpu_sampler: None,
         };
+        #[cfg(feature = "nvml")]
+        {
+            app.nvidia_smi_fallback = app.nvml.is_none();
+        }
+        #[cfg(not(feature = "nvml"))]
+        {
+            app.nvidia_smi_fallback = true;
+        }
+        // dmidecode and system_profiler can take seconds; fill the System tab in later
+        if cli.remote.is_none() && cli.connect.is_none() {
+            app.tasks.spawn("Reading hardware inventory", || {
+                let hardware = HardwareInfo::collect();
+                Box::new(move |app: &mut App| app.hardware = hardware)
+            });
+        }
+        if !config.geoip.is_empty() {
+            let paths = config.geoip.clone();
+            app.tasks.spawn("Loading GeoIP databases", move || {
+                let (geoip, errors) = GeoIp::load(&paths);
+                Box::new(move |app: &mut App| {
+                    app.geoip = geoip;
+                    if !errors.is_empty() {
+                        app.log_event(EventLevel::Error, format!("GeoIP: {}", errors.join("; ")));
+                    }
+                    app.update_filtered_connections();
+                })
+            });
+        }
+        if !theme_errors.is_empty() {
+            app.log_event(EventLevel::Warning, format!("Themes: {}", theme_errors.join("; ")));
+        }
+        #[cfg(feature = "nvml")]
+        if app.nvml.is_some() {
+            app.events.push(EventLevel::Info, format!("NVML: {}", app.nvml_status));
+        } else if crate::nvml::is_failure(&app.nvml_status) {
+            app.log_event(EventLevel::Error, format!("NVML: {}", app.nvml_status));
+        }
         app.update_stats();
         app
     }
 
+    /// Switch to showing `feed`'s host. What was read from this machine
+    /// at startup and has no remote counterpart is cleared rather than
+    /// left looking like the remote's.
+    pub fn attach_remote(&mut self, feed: RemoteFeed) {
+        self.hostname = feed.host.clone();
+        self.remote = Some(feed);
+        self.firewall = FirewallStatus::unavailable("remote");
+        self.kernel_param_names.clear();
+        self.kernel_params.clear();
+        self.ping = None;
+        self.gpus.clear();
+        self.gpu_util_history.clear();
+        self.processes.clear();
+        self.network_interfaces.clear();
+        self.disk_devices.clear();
+        self.update_filtered();
+    }
+
     pub fn tick(&mut self) {
+        if self.remote.is_some() {
+            self.tick_remote();
+            return;
+        }
         self.system.refresh_all();
         self.networks.refresh(true);
         self.disks.refresh(true);
         self.update_stats();
+        if self.show_process_detail {
+            self.refresh_detail();
+        }
+        self.record_process_history();
         self.tick_count += 1;
+        if self.every(FIREWALL_POLL) && !self.tasks.is_running("firewall") {
+            self.tasks.spawn_quiet("firewall", || {
+                let next = firewall::query();
+                Box::new(move |app: &mut App| app.firewall.update(next))
+            });
+        }
+        #[cfg(feature = "net-accounting")]
+        if !self.tasks.is_running("net accounting") {
+            self.tasks.spawn_quiet("net accounting", || {
+                let counters = crate::netacct::query();
+                Box::new(move |app: &mut App| app.net_accounting.update(counters))
+            });
+        }
+        if self.every(KERNEL_PARAM_POLL) {
+            self.kernel_params = sysctl::read(&self.kernel_param_names);
+        }
+        if self.every(DISK_GROWTH_SAMPLE) {
+            self.sample_disk_growth();
+        }
+        if self.every(LEAK_SAMPLE) {
+            self.sample_leaks();
+        }
+        self.poll_spawned();
+        if self.active_tab == Tab::Disks {
+            self.poll_smart();
+        }
+        self.record_tick();
+        // The Network tab's listening ports panel shares the socket list
+        if matches!(self.active_tab, Tab::Connections | Tab::NetworkDetail)
+            && self.every(CONNECTION_POLL)
+            && !self.tasks.is_running("connections")
+        {
+            // Walking every /proc/<pid>/fd is too slow for the UI thread
+            self.tasks.spawn_quiet("connections", || {
+                let list = connections::query();
+                Box::new(move |app: &mut App| app.set_connections(list))
+            });
+        }
+
+        if self.active_tab == Tab::NetworkDetail
+            && self.every(WIFI_POLL)
+            && !self.tasks.is_running("wifi")
+        {
+            self.tasks.spawn_quiet("wifi", || {
+                let list = wifi::query();
+                Box::new(move |app: &mut App| app.set_wifi(list))
+            });
+        }
+
+        if let Some(monitor) = &self.ping
+            && self.every(self.ping_interval)
+            && !self.tasks.is_running("ping")
+        {
+            let target = monitor.target.clone();
+            self.tasks.spawn_quiet("ping", move || {
+                let is_gateway = target.is_none();
+                let target = target.or_else(ping::default_gateway);
+                let rtt = target.as_deref().and_then(ping::ping);
+                Box::new(move |app: &mut App| {
+                    let Some(monitor) = &mut app.ping else {
+                        return;
+                    };
+                    if target.is_none() {
+                        return;
+                    }
+                    if monitor.target.is_none() {
+                        monitor.target = target;
+                        monitor.is_gateway = is_gateway;
+                    }
+                    monitor.record(rtt);
+                })
+            });
+        }
+    }
 
+    /// Alerts, peaks and status expiry, for local and remote ticks alike.
+    fn record_tick(&mut self) {
+        self.update_alerts();
+        let gpu_temp = self.gpus.iter().map(|g| g.temperature).max();
+        for peaks in [&mut self.peaks, &mut self.session_peaks] {
+            peaks.record(self.global_cpu as f64, self.used_memory, self.net_rx, self.net_tx, gpu_temp);
+        }
+        self.push_metrics();
         if let Some((_, time)) = &self.status_message
             && time.elapsed().as_secs() >= 3
         {
@@ -280,76 +1291,818 @@ impl App {
         }
     }
 
-    fn update_stats(&mut self) {
-        self.global_cpu = self.system.global_cpu_usage();
-        self.global_cpu_history.pop_front();
-        self.global_cpu_history.push_back(self.global_cpu as f64);
+    /// Every `metrics_interval`, send a sample to the configured collector.
+    fn push_metrics(&mut self) {
+        if self.tasks.is_running("metrics") || !self.metrics.as_mut().is_some_and(MetricsExporter::due) {
+            return;
+        }
+        let Some(exporter) = &self.metrics else {
+            return;
+        };
+        let payload = exporter.payload(self);
+        let target = exporter.target.clone();
+        let token = exporter.token.clone();
+        self.tasks.spawn_quiet("metrics", move || {
+            let result = metrics::send(&target, token.as_deref(), &payload);
+            Box::new(move |app: &mut App| {
+                let Some(exporter) = &mut app.metrics else {
+                    return;
+                };
+                let label = exporter.target.label();
+                match result {
+                    Err(e) if !exporter.failing => {
+                        exporter.failing = true;
+                        app.log_event(EventLevel::Error, format!("Metrics to {label}: {e}"));
+                    }
+                    Ok(()) if exporter.failing => {
+                        exporter.failing = false;
+                        app.log_event(EventLevel::Info, format!("Metrics to {label} delivered again"));
+                    }
+                    _ => {}
+                }
+            })
+        });
+    }
 
-        for (i, cpu) in self.system.cpus().iter().enumerate() {
-            if i < self.cpu_history.len() {
-                self.cpu_history[i].pop_front();
-                self.cpu_history[i].push_back(cpu.cpu_usage() as f64);
+    /// `--remote`: apply the snapshots that arrived since the last tick in
+    /// place of a local refresh. Sockets, Wi-Fi, latency, the firewall,
+    /// SMART and leak tracking only know about this machine and stay idle.
+    fn tick_remote(&mut self) {
+        let Some(remote) = &mut self.remote else {
+            return;
+        };
+        let host = remote.host.clone();
+        let was_connected = remote.connected;
+        let received = remote.drain();
+        if !was_connected && remote.connected {
+            self.log_event(EventLevel::Info, format!("Remote {host}: reconnected"));
+        }
+        for received in received {
+            match received {
+                Ok(snapshot) => self.apply_snapshot(snapshot),
+                Err(e) => self.log_event(EventLevel::Error, format!("Remote {host}: {e}")),
             }
         }
+        self.tick_count += 1;
+        if self.every(DISK_GROWTH_SAMPLE) {
+            self.sample_disk_growth();
+        }
+        self.record_tick();
+    }
 
-        self.total_memory = self.system.total_memory();
-        self.used_memory = self.system.used_memory();
-        self.total_swap = self.system.total_swap();
-        self.used_swap = self.system.used_swap();
-        let mem_pct = if self.total_memory > 0 {
-            (self.used_memory as f64 / self.total_memory as f64) * 100.0
+    /// Whether this tick is one of those roughly `period` apart at the
+    /// current refresh rate; every tick once the rate is slower than that.
+    fn every(&self, period: Duration) -> bool {
+        let ticks = (period.as_millis() / self.tick_rate.as_millis().max(1)).max(1);
+        self.tick_count.is_multiple_of(ticks as u64)
+    }
+
+    /// `+` / `-`: step to the next shorter or longer tick interval.
+    pub fn change_refresh_rate(&mut self, faster: bool) {
+        let next = if faster {
+            REFRESH_RATES.iter().rev().find(|&&r| r < self.tick_rate)
         } else {
-            0.0
+            REFRESH_RATES.iter().find(|&&r| r > self.tick_rate)
         };
-        self.mem_history.pop_front();
-        self.mem_history.push_back(mem_pct);
-
-        let (mut rx, mut tx) = (0u64, 0u64);
-        self.network_interfaces.clear();
-        for (name, data) in self.networks.iter() {
-            rx += data.received();
-            tx += data.transmitted();
-            self.network_interfaces.push(NetworkInterface {
-                name: name.to_string(),
-                received: data.received(),
-                transmitted: data.transmitted(),
-                packets_in: data.packets_received(),
-                packets_out: data.packets_transmitted(),
-                errors_in: data.errors_on_received(),
-                errors_out: data.errors_on_transmitted(),
-                mac_address: data.mac_address().to_string(),
-            });
+        if let Some(&rate) = next {
+            self.tick_rate = rate;
         }
-        self.net_rx = rx;
-        self.net_tx = tx;
-        self.net_rx_history.pop_front();
-        self.net_rx_history.push_back(rx as f64 / 1024.0);
-        self.net_tx_history.pop_front();
-        self.net_tx_history.push_back(tx as f64 / 1024.0);
-
-        self.processes = self
-            .system
-            .processes()
-            .iter()
-            .map(|(pid, proc_)| ProcessInfo {
-                pid: pid.as_u32(),
-                name: proc_.name().to_string_lossy().to_string(),
-                cpu: proc_.cpu_usage(),
-                memory: proc_.memory(),
-                status: format!("{:?}", proc_.status()),
-                run_time: proc_.run_time(),
-                disk_read: proc_.disk_usage().read_bytes,
-                disk_write: proc_.disk_usage().written_bytes,
-            })
-            .collect();
+        self.set_status(format!("Refresh every {}", format_interval(self.tick_rate)));
+    }
 
-        self.sort_processes();
-        self.update_filtered();
-        self.update_gpu();
+    /// `[` / `]`: zoom the graphs in to a shorter or out to a longer window.
+    pub fn zoom_graphs(&mut self, out: bool) {
+        let next = if out {
+            GRAPH_WINDOWS.iter().find(|&&w| w > self.graph_window)
+        } else {
+            GRAPH_WINDOWS.iter().rev().find(|&&w| w < self.graph_window)
+        };
+        if let Some(&window) = next {
+            self.graph_window = window;
+        }
+        let mut status = format!("Graphs: last {}", format_interval(self.graph_window));
+        if self.window_len() > self.history_len {
+            status.push_str(&format!(
+                " (history holds {}, see history_len)",
+                format_interval(self.graph_span())
+            ));
+        }
+        self.set_status(status);
     }
 
-    fn update_gpu(&mut self) {
-        // Try NVML first (NVIDIA GPUs on all platforms)
+    /// `{` / `}`: step to the next shorter or longer history.
+    pub fn change_history_len(&mut self, longer: bool) {
+        let next = if longer {
+            HISTORY_LENGTHS.iter().find(|&&l| l > self.history_len)
+        } else {
+            HISTORY_LENGTHS.iter().rev().find(|&&l| l < self.history_len)
+        };
+        if let Some(&len) = next {
+            self.set_history_len(len);
+        }
+        self.set_status(format!(
+            "History: {} samples ({})",
+            self.history_len,
+            format_interval(self.tick_rate * self.history_len as u32)
+        ));
+    }
+
+    /// Resize every graph history, keeping the newest samples; a longer
+    /// history is padded with zeros at the old end, as at startup.
+    fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        self.history_filled = self.history_filled.min(len);
+        let series = self
+            .cpu_history
+            .iter_mut()
+            .chain(&mut self.gpu_util_history)
+            .chain(self.interface_history.values_mut().flat_map(|(rx, tx)| [rx, tx]))
+            .chain(self.wifi_signal_history.values_mut())
+            .chain([
+                &mut self.global_cpu_history,
+                &mut self.mem_history,
+                &mut self.net_rx_history,
+                &mut self.net_tx_history,
+                &mut self.disk_read_history,
+                &mut self.disk_write_history,
+            ]);
+        for history in series {
+            while history.len() > len {
+                history.pop_front();
+            }
+            while history.len() < len {
+                history.push_front(0.0);
+            }
+        }
+        for samples in self.disk_used_history.values_mut() {
+            let excess = samples.len().saturating_sub(len);
+            samples.drain(..excess);
+        }
+    }
+
+    /// Samples in the graph window at the current refresh rate.
+    fn window_len(&self) -> usize {
+        (self.graph_window.as_millis() / self.tick_rate.as_millis().max(1)) as usize
+    }
+
+    /// Time the graphs cover: the window, or less if the history is shorter.
+    pub fn graph_span(&self) -> Duration {
+        self.tick_rate * self.window_len().min(self.history_len) as u32
+    }
+
+    /// The samples of `history` inside the graph window, oldest first.
+    pub fn windowed<'a>(&self, history: &'a VecDeque<f64>) -> vec_deque::Iter<'a, f64> {
+        history.range(history.len().saturating_sub(self.window_len())..)
+    }
+
+    /// Like `windowed`, without the zero padding a history starts with, for
+    /// statistics.
+    pub fn sampled<'a>(&self, history: &'a VecDeque<f64>) -> vec_deque::Iter<'a, f64> {
+        let count = self.window_len().min(self.history_filled);
+        history.range(history.len().saturating_sub(count)..)
+    }
+
+    /// The graph window of `history` in at most `points` values, each the
+    /// highest sample it covers so a short spike stays visible when a long
+    /// window is squeezed into a few columns.
+    pub fn downsample(&self, history: &VecDeque<f64>, points: usize) -> Vec<f64> {
+        let window: Vec<f64> = self.windowed(history).copied().collect();
+        let len = window.len();
+        if len <= points || points == 0 {
+            return window;
+        }
+        (0..points)
+            .map(|p| {
+                window[p * len / points..(p + 1) * len / points]
+                    .iter()
+                    .copied()
+                    .fold(0.0, f64::max)
+            })
+            .collect()
+    }
+
+    /// Load one remote sample into the same fields and histories a local
+    /// refresh fills.
+    fn apply_snapshot(&mut self, s: Snapshot) {
+        if let Some(remote) = &mut self.remote {
+            remote.uptime = s.uptime;
+            remote.load_average = s.load_average;
+        }
+        self.hostname = s.hostname;
+        self.os_name = s.os;
+        self.os_version.clear();
+        self.kernel_version = s.kernel;
+        self.cpu_arch = s.arch;
+        self.cpu_brand = s.cpu_brand;
+        self.boot_time = s.timestamp.saturating_sub(s.uptime);
+
+        self.global_cpu = s.cpu_usage;
+        self.global_cpu_history.pop_front();
+        self.global_cpu_history.push_back(self.global_cpu as f64);
+        if self.cpu_history.len() != s.cpu_per_core.len() {
+            self.cpu_count = s.cpu_per_core.len();
+            self.cpu_history = vec![empty_history(self.history_len); self.cpu_count];
+        }
+        for (history, usage) in self.cpu_history.iter_mut().zip(&s.cpu_per_core) {
+            history.pop_front();
+            history.push_back(*usage as f64);
+        }
+
+        self.total_memory = s.memory_total;
+        self.used_memory = s.memory_used;
+        self.total_swap = s.swap_total;
+        self.used_swap = s.swap_used;
+        let mem_pct = if self.total_memory > 0 {
+            (self.used_memory as f64 / self.total_memory as f64) * 100.0
+        } else {
+            0.0
+        };
+        self.mem_history.pop_front();
+        self.mem_history.push_back(mem_pct);
+        self.history_filled = (self.history_filled + 1).min(self.history_len);
+
+        let previous = std::mem::take(&mut self.network_interfaces);
+        self.network_interfaces = s
+            .network
+            .into_iter()
+            .map(|i| {
+                // The remote rates are per second, so count from the totals
+                let (rx, tx) = previous
+                    .iter()
+                    .find(|p| p.name == i.name)
+                    .map(|p| {
+                        (
+                            i.total_received.saturating_sub(p.total_received),
+                            i.total_transmitted.saturating_sub(p.total_transmitted),
+                        )
+                    })
+                    .unwrap_or_default();
+                let session = self.session_transfer.entry(i.name.clone()).or_default();
+                session.0 += rx;
+                session.1 += tx;
+                NetworkInterface {
+                    name: i.name,
+                    received: i.rx_rate,
+                    transmitted: i.tx_rate,
+                    packets_in: 0,
+                    packets_out: 0,
+                    errors_in: 0,
+                    errors_out: 0,
+                    drops: None,
+                    mac_address: String::new(),
+                    addresses: Vec::new(),
+                    mtu: i.mtu,
+                    total_received: i.total_received,
+                    total_transmitted: i.total_transmitted,
+                    session_received: session.0,
+                    session_transmitted: session.1,
+                    link: LinkInfo::default(),
+                }
+            })
+            .collect();
+        self.net_rx = self.network_interfaces.iter().map(|i| i.received).sum();
+        self.net_tx = self.network_interfaces.iter().map(|i| i.transmitted).sum();
+        self.net_rx_history.pop_front();
+        self.net_rx_history.push_back(self.net_rx as f64 / 1024.0);
+        self.net_tx_history.pop_front();
+        self.net_tx_history.push_back(self.net_tx as f64 / 1024.0);
+        self.sample_interfaces();
+        self.update_saturation();
+
+        self.disk_read = s.disk_read_rate;
+        self.disk_write = s.disk_write_rate;
+        self.disk_read_history.pop_front();
+        self.disk_read_history.push_back(self.disk_read as f64 / 1024.0);
+        self.disk_write_history.pop_front();
+        self.disk_write_history.push_back(self.disk_write as f64 / 1024.0);
+        self.disk_devices = s
+            .disks
+            .into_iter()
+            .map(|d| DiskInfo {
+                device: d.device,
+                parent_disk: None,
+                mount: d.mount,
+                file_system: d.file_system,
+                total: d.total,
+                used: d.used,
+                read_per_sec: d.read_rate,
+                write_per_sec: d.write_rate,
+                temperature: None,
+                iops: None,
+                busy: None,
+                queue_depth: None,
+            })
+            .collect();
+        self.sort_disks();
+        self.disk_scroll = self.disk_scroll.min(self.disk_devices.len().saturating_sub(1));
+
+        self.processes = s
+            .processes
+            .into_iter()
+            .map(|p| ProcessInfo {
+                pid: p.pid,
+                name: p.name,
+                cpu: p.cpu,
+                memory: p.memory,
+                status: p.status,
+                run_time: p.run_time,
+                disk_read: 0,
+                disk_write: 0,
+                disk_read_rate: p.disk_read_rate,
+                disk_write_rate: p.disk_write_rate,
+                net_rx_rate: 0,
+                net_tx_rate: 0,
+                cmd: String::new(),
+                user: String::new(),
+                threads: None,
+                virtual_memory: 0,
+                shared_memory: None,
+                swap: None,
+            })
+            .collect();
+        self.sort_processes();
+        self.update_filtered();
+
+        self.gpus = s
+            .gpus
+            .into_iter()
+            .map(|g| GpuInfo {
+                name: g.name,
+                temperature: g.temperature,
+                utilization: g.utilization,
+                memory_used: g.memory_used,
+                memory_total: g.memory_total,
+                fan_speed: None,
+                power_usage: None,
+                power_limit: None,
+                fan_rpm: None,
+                core_clock_mhz: None,
+                memory_clock_mhz: None,
+                memory_busy: None,
+                temp_junction: None,
+                temp_memory: None,
+                processes: Vec::new(),
+            })
+            .collect();
+        self.gpu_util_history
+            .resize_with(self.gpus.len(), || empty_history(self.history_len));
+        for (history, gpu) in self.gpu_util_history.iter_mut().zip(&self.gpus) {
+            history.pop_front();
+            history.push_back(gpu.utilization as f64);
+        }
+    }
+
+    /// Actions that read or signal processes on this machine can't reach a
+    /// `--remote` host; say so instead of acting on a local PID.
+    fn refuse_remote(&mut self) -> bool {
+        if self.remote.is_some() {
+            self.set_status("Not available when monitoring a remote host".into());
+        }
+        self.remote.is_some()
+    }
+
+    fn update_stats(&mut self) {
+        self.global_cpu = self.system.global_cpu_usage();
+        self.global_cpu_history.pop_front();
+        self.global_cpu_history.push_back(self.global_cpu as f64);
+
+        for (i, cpu) in self.system.cpus().iter().enumerate() {
+            if i < self.cpu_history.len() {
+                self.cpu_history[i].pop_front();
+                self.cpu_history[i].push_back(cpu.cpu_usage() as f64);
+            }
+        }
+
+        self.total_memory = self.system.total_memory();
+        self.used_memory = self.system.used_memory();
+        self.total_swap = self.system.total_swap();
+        self.used_swap = self.system.used_swap();
+        self.memory_breakdown = if cfg!(target_os = "linux") { meminfo::read() } else { None };
+        let mem_pct = if self.total_memory > 0 {
+            (self.used_memory as f64 / self.total_memory as f64) * 100.0
+        } else {
+            0.0
+        };
+        self.mem_history.pop_front();
+        self.mem_history.push_back(mem_pct);
+        self.history_filled = (self.history_filled + 1).min(self.history_len);
+
+        // Networks, disks and processes count bytes since the previous
+        // refresh
+        let secs = self.rate_sample_time.map_or(0.0, |at| at.elapsed().as_secs_f64());
+        self.rate_sample_time = Some(Instant::now());
+        let per_sec = |bytes: u64| if secs > 0.0 { (bytes as f64 / secs) as u64 } else { 0 };
+
+        let (mut rx, mut tx) = (0u64, 0u64);
+        self.network_interfaces.clear();
+        for (name, data) in self.networks.iter() {
+            rx += per_sec(data.received());
+            tx += per_sec(data.transmitted());
+            // Summed per refresh rather than diffed against the boot
+            // counters, so an interface that resets them keeps its tally
+            let session = self.session_transfer.entry(name.to_string()).or_default();
+            session.0 += data.received();
+            session.1 += data.transmitted();
+            let drops = ifinfo::read_drops(name).map(|(rx, tx)| {
+                let (prev_rx, prev_tx) = self
+                    .drop_totals
+                    .insert(name.to_string(), (rx, tx))
+                    .unwrap_or((rx, tx));
+                (rx.saturating_sub(prev_rx), tx.saturating_sub(prev_tx))
+            });
+            self.network_interfaces.push(NetworkInterface {
+                name: name.to_string(),
+                received: per_sec(data.received()),
+                transmitted: per_sec(data.transmitted()),
+                packets_in: data.packets_received(),
+                packets_out: data.packets_transmitted(),
+                errors_in: data.errors_on_received(),
+                errors_out: data.errors_on_transmitted(),
+                drops,
+                mac_address: data.mac_address().to_string(),
+                addresses: data.ip_networks().to_vec(),
+                mtu: data.mtu(),
+                total_received: data.total_received(),
+                total_transmitted: data.total_transmitted(),
+                session_received: session.0,
+                session_transmitted: session.1,
+                link: LinkInfo::read(name),
+            });
+        }
+        self.net_rx = rx;
+        self.net_tx = tx;
+        self.net_rx_history.pop_front();
+        self.net_rx_history.push_back(rx as f64 / 1024.0);
+        self.net_tx_history.pop_front();
+        self.net_tx_history.push_back(tx as f64 / 1024.0);
+        self.sample_interfaces();
+        self.update_saturation();
+
+        // Counted once per device even when it is mounted in several places
+        let (mut read, mut written) = (0u64, 0u64);
+        let mut seen = Vec::new();
+        for disk in self.disks.iter() {
+            if seen.contains(&disk.name()) {
+                continue;
+            }
+            seen.push(disk.name());
+            let usage = disk.usage();
+            read += usage.read_bytes;
+            written += usage.written_bytes;
+        }
+        self.disk_read = per_sec(read);
+        self.disk_write = per_sec(written);
+        self.disk_read_history.pop_front();
+        self.disk_read_history.push_back(self.disk_read as f64 / 1024.0);
+        self.disk_write_history.pop_front();
+        self.disk_write_history.push_back(self.disk_write as f64 / 1024.0);
+        self.update_disk_devices();
+
+        // One file read per process, so only when it's on screen
+        let read_shared = self
+            .visible_process_columns()
+            .iter()
+            .any(|c| matches!(c, ProcessColumn::Shared | ProcessColumn::Private));
+        let read_swap = self.sort_by == SortBy::Swap || self.visible_process_columns().contains(&ProcessColumn::Swap);
+        self.processes = self
+            .system
+            .processes()
+            .iter()
+            .map(|(pid, proc_)| {
+                let usage = proc_.disk_usage();
+                let (net_rx_rate, net_tx_rate) = self.process_net_rate(pid.as_u32());
+                ProcessInfo {
+                    pid: pid.as_u32(),
+                    name: proc_.name().to_string_lossy().to_string(),
+                    cpu: proc_.cpu_usage(),
+                    memory: proc_.memory(),
+                    status: format!("{:?}", proc_.status()),
+                    run_time: proc_.run_time(),
+                    disk_read: usage.total_read_bytes,
+                    disk_write: usage.total_written_bytes,
+                    disk_read_rate: per_sec(usage.read_bytes),
+                    disk_write_rate: per_sec(usage.written_bytes),
+                    net_rx_rate,
+                    net_tx_rate,
+                    cmd: proc_
+                        .cmd()
+                        .iter()
+                        .map(|s| s.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    user: proc_
+                        .user_id()
+                        .and_then(|uid| self.users.get_user_by_id(uid))
+                        .map(|u| u.name().to_string())
+                        .unwrap_or_default(),
+                    threads: proc_.tasks().map(|t| t.len() as u64),
+                    virtual_memory: proc_.virtual_memory(),
+                    shared_memory: if read_shared { shared_memory(pid.as_u32()) } else { None },
+                    swap: if read_swap { swap(pid.as_u32()) } else { None },
+                }
+            })
+            .collect();
+
+        self.sort_processes();
+        self.update_filtered();
+        self.update_gpu();
+    }
+
+    #[cfg(feature = "net-accounting")]
+    fn process_net_rate(&self, pid: u32) -> (u64, u64) {
+        self.net_accounting.rate(pid)
+    }
+
+    #[cfg(not(feature = "net-accounting"))]
+    fn process_net_rate(&self, _pid: u32) -> (u64, u64) {
+        (0, 0)
+    }
+
+    fn sample_interfaces(&mut self) {
+        let history = &mut self.interface_history;
+        history.retain(|name, _| self.network_interfaces.iter().any(|i| &i.name == name));
+        for iface in &self.network_interfaces {
+            let (rx, tx) = history.entry(iface.name.clone()).or_insert_with(|| {
+                (
+                    empty_history(self.history_len),
+                    empty_history(self.history_len),
+                )
+            });
+            rx.pop_front();
+            rx.push_back(iface.received as f64 / 1024.0);
+            tx.pop_front();
+            tx.push_back(iface.transmitted as f64 / 1024.0);
+        }
+    }
+
+    /// Share of the interface's capacity in use, in %, for the busier
+    /// direction. A configured `bandwidth_limits` entry wins over the
+    /// negotiated link speed.
+    pub fn interface_utilization(&self, iface: &NetworkInterface) -> Option<f64> {
+        let mbps = self
+            .bandwidth_limits
+            .get(&iface.name)
+            .copied()
+            .or(iface.link.speed_mbps.map(u64::from))
+            .filter(|&m| m > 0)?;
+        let bits = iface.received.max(iface.transmitted) as f64 * 8.0;
+        Some(bits / (mbps as f64 * 1e6) * 100.0)
+    }
+
+    fn update_saturation(&mut self) {
+        let Some(threshold) = self.bandwidth_threshold else {
+            return;
+        };
+        let saturated: Vec<(String, f64)> = self
+            .network_interfaces
+            .iter()
+            .filter_map(|iface| {
+                let pct = self.interface_utilization(iface)?;
+                (pct >= threshold).then(|| (iface.name.clone(), pct))
+            })
+            .collect();
+        // Only interfaces that just crossed, not every tick they stay busy
+        let crossed: Vec<&(String, f64)> = saturated
+            .iter()
+            .filter(|(name, _)| !self.saturated_interfaces.contains(name))
+            .collect();
+        if self.bandwidth_warning
+            && let Some((name, pct)) = crossed.first()
+        {
+            self.log_event(EventLevel::Warning, format!("⚠ {name} at {pct:.0}% of its capacity"));
+        }
+        for (name, pct) in crossed {
+            self.run_hook(
+                HookEvent::Bandwidth,
+                vec![("interface", name.clone()), ("percent", format!("{pct:.0}"))],
+            );
+        }
+        self.saturated_interfaces = saturated.into_iter().map(|(name, _)| name).collect();
+    }
+
+    /// (RX, TX) bytes over all interfaces since RustMonitor started.
+    pub fn session_transfer(&self) -> (u64, u64) {
+        self.session_transfer
+            .values()
+            .fold((0, 0), |(rx, tx), (r, t)| (rx + r, tx + t))
+    }
+
+    /// (RX, TX) bytes over the current interfaces since boot.
+    pub fn boot_transfer(&self) -> (u64, u64) {
+        self.network_interfaces.iter().fold((0, 0), |(rx, tx), i| {
+            (rx + i.total_received, tx + i.total_transmitted)
+        })
+    }
+
+    /// The interface the Network tab graphs follow, when that's switched on.
+    pub fn graphed_interface(&self) -> Option<&NetworkInterface> {
+        if !self.graph_selected_interface {
+            return None;
+        }
+        self.network_interfaces.get(self.network_scroll)
+    }
+
+    pub fn toggle_interface_graph(&mut self) {
+        self.graph_selected_interface = !self.graph_selected_interface;
+        self.set_status(match self.graphed_interface() {
+            Some(iface) => format!("Graphs: {}", iface.name),
+            None => "Graphs: all interfaces".into(),
+        });
+    }
+
+    fn set_wifi(&mut self, list: Vec<WifiInfo>) {
+        let history = &mut self.wifi_signal_history;
+        history.retain(|name, _| list.iter().any(|w| &w.interface == name));
+        for wifi in &list {
+            let samples = history
+                .entry(wifi.interface.clone())
+                .or_insert_with(|| empty_history(self.history_len));
+            samples.pop_front();
+            samples.push_back(wifi.quality().unwrap_or(0) as f64);
+        }
+        self.wifi = list;
+    }
+
+    fn sample_disk_growth(&mut self) {
+        let history = &mut self.disk_used_history;
+        history.retain(|mount, _| self.disk_devices.iter().any(|d| &d.mount == mount));
+        for disk in &self.disk_devices {
+            let samples = history.entry(disk.mount.clone()).or_default();
+            if samples.len() >= self.history_len {
+                samples.pop_front();
+            }
+            samples.push_back((Instant::now(), disk.used));
+        }
+    }
+
+    fn sample_leaks(&mut self) {
+        // Threads share their process's memory; count each process once
+        let processes = self
+            .system
+            .processes()
+            .values()
+            .filter(|p| p.thread_kind().is_none())
+            .map(|p| (p.pid().as_u32(), p.name().to_string_lossy().to_string(), p.memory()));
+        self.leaks.sample(processes);
+        self.leak_suspects = self.leaks.suspects(self.leak_slope);
+    }
+
+    fn update_disk_devices(&mut self) {
+        let secs = self.disk_sample_time.elapsed().as_secs_f64();
+        self.disk_sample_time = Instant::now();
+        #[cfg(target_os = "linux")]
+        let device_io = self.diskstats.sample();
+
+        self.disk_devices = self
+            .disks
+            .iter()
+            .map(|disk| {
+                let total = disk.total_space();
+                let device = disk.name().to_string_lossy().to_string();
+                let mut info = DiskInfo {
+                    parent_disk: smart::whole_disk(&device),
+                    device,
+                    mount: disk.mount_point().to_string_lossy().to_string(),
+                    file_system: disk.file_system().to_string_lossy().to_string(),
+                    total,
+                    used: total.saturating_sub(disk.available_space()),
+                    read_per_sec: 0,
+                    write_per_sec: 0,
+                    temperature: None,
+                    iops: None,
+                    busy: None,
+                    queue_depth: None,
+                };
+                // sysinfo's usage is bytes since the previous refresh
+                if secs > 0.0 {
+                    let usage = disk.usage();
+                    info.read_per_sec = (usage.read_bytes as f64 / secs) as u64;
+                    info.write_per_sec = (usage.written_bytes as f64 / secs) as u64;
+                }
+                #[cfg(target_os = "linux")]
+                if let Some(io) = info.device.rsplit('/').next().and_then(|n| device_io.get(n)) {
+                    info.read_per_sec = io.read_per_sec;
+                    info.write_per_sec = io.write_per_sec;
+                    info.iops = Some(io.iops);
+                    info.busy = Some(io.busy);
+                    info.queue_depth = Some(io.queue_depth);
+                }
+                #[cfg(target_os = "linux")]
+                {
+                    info.temperature = info.parent_disk.as_deref().and_then(smart::hwmon_temperature);
+                }
+                info
+            })
+            .collect();
+        if self.filter_disks {
+            self.disk_devices
+                .retain(|d| !fsinfo::is_pseudo(&d.device, &d.file_system, &self.hidden_filesystems));
+            // Bind mounts and btrfs subvolumes repeat a device; keep its shortest mount
+            self.disk_devices.sort_by_key(|d| d.mount.len());
+            let mut seen = HashSet::new();
+            self.disk_devices
+                .retain(|d| !d.device.starts_with("/dev/") || seen.insert(d.device.clone()));
+        }
+        self.sort_disks();
+        self.disk_scroll = self.disk_scroll.min(self.disk_devices.len().saturating_sub(1));
+    }
+
+    fn update_alerts(&mut self) {
+        let conditions = self.critical_conditions();
+        let (active, fired) = self.alert_tracker.update(conditions);
+        for alert in &fired {
+            self.log_event(EventLevel::Warning, format!("⚠ {}", alert.message));
+            if let Some(syslog) = &self.syslog {
+                syslog.alert(&alert.key, &self.hostname, &alert.message);
+            }
+            self.run_hook(
+                HookEvent::Alert,
+                vec![("key", alert.key.clone()), ("message", alert.message.clone())],
+            );
+        }
+        self.session.alerts_fired += fired.len();
+        self.alerts = active;
+        self.fired_alerts = fired;
+    }
+
+    /// Conditions bad enough to interrupt someone who isn't looking.
+    fn critical_conditions(&self) -> Vec<Alert> {
+        let mut conditions = Vec::new();
+        let mut alert = |key: String, message: String| conditions.push(Alert { key, message });
+
+        if self.global_cpu >= alerts::CPU_CRITICAL_PCT {
+            alert("cpu".into(), format!("CPU at {:.0}%", self.global_cpu));
+        }
+        if self.total_memory > 0 {
+            let pct = self.used_memory as f64 / self.total_memory as f64 * 100.0;
+            if pct >= alerts::MEMORY_CRITICAL_PCT {
+                alert("mem".into(), format!("Memory at {pct:.0}%"));
+            }
+        }
+        for disk in &self.disk_devices {
+            // Read-only images such as squashfs are always full
+            if fsinfo::is_pseudo(&disk.device, &disk.file_system, &self.hidden_filesystems) {
+                continue;
+            }
+            if disk.usage_pct() >= alerts::DISK_CRITICAL_PCT {
+                alert(
+                    format!("disk:{}", disk.mount),
+                    format!("{} is {:.0}% full", disk.mount, disk.usage_pct()),
+                );
+            }
+            if let Some(temp) = self.disk_temperature(disk)
+                && temp >= alerts::DRIVE_CRITICAL_CELSIUS
+            {
+                let drive = disk.parent_disk.as_deref().unwrap_or(&disk.device);
+                alert(format!("temp:{drive}"), format!("{drive} at {temp}°C"));
+            }
+            if let Some(parent) = &disk.parent_disk
+                && self.smart.get(parent).is_some_and(SmartHealth::is_failing)
+            {
+                alert(format!("smart:{parent}"), format!("{parent} is failing SMART"));
+            }
+        }
+        for (i, gpu) in self.gpus.iter().enumerate() {
+            if gpu.temperature >= alerts::GPU_CRITICAL_CELSIUS {
+                alert(format!("gpu:{i}"), format!("{} at {}°C", gpu.name, gpu.temperature));
+            }
+        }
+        // Several mounts of one drive report the same temperature and health
+        let mut seen = HashSet::new();
+        conditions.retain(|a| seen.insert(a.key.clone()));
+        conditions
+    }
+
+    pub fn smart_for(&self, disk: &DiskInfo) -> Option<&SmartHealth> {
+        disk.parent_disk.as_ref().and_then(|d| self.smart.get(d))
+    }
+
+    pub fn disk_temperature(&self, disk: &DiskInfo) -> Option<u32> {
+        disk.temperature
+            .or_else(|| self.smart_for(disk).and_then(|s| s.temperature))
+    }
+
+    fn sort_disks(&mut self) {
+        match self.disk_sort {
+            DiskSortBy::Mount => self.disk_devices.sort_by(|a, b| a.mount.cmp(&b.mount)),
+            DiskSortBy::Usage => self
+                .disk_devices
+                .sort_by(|a, b| b.usage_pct().total_cmp(&a.usage_pct())),
+            DiskSortBy::Read => self
+                .disk_devices
+                .sort_by_key(|d| std::cmp::Reverse(d.read_per_sec)),
+            DiskSortBy::Write => self
+                .disk_devices
+                .sort_by_key(|d| std::cmp::Reverse(d.write_per_sec)),
+            DiskSortBy::Busy => self.disk_devices.sort_by(|a, b| {
+                b.busy.unwrap_or(0.0).total_cmp(&a.busy.unwrap_or(0.0))
+            }),
+        }
+    }
+
+    /// Poll NVIDIA GPUs through NVML; false when it isn't loaded or finds none.
+    #[cfg(feature = "nvml")]
+    fn update_nvml_gpus(&mut self) -> bool {
         if let Some(nvml) = &self.nvml
             && let Ok(count) = nvml.device_count()
         {
@@ -374,6 +2127,9 @@ impl App {
                     let fan_speed = device.fan_speed(0).ok();
                     let power_usage = device.power_usage().ok();
                     let power_limit = device.enforced_power_limit().ok();
+                    let core_clock_mhz = device.clock_info(Clock::Graphics).ok();
+                    let memory_clock_mhz = device.clock_info(Clock::Memory).ok();
+                    let processes = nvml_processes(&device, &self.system);
 
                     self.gpus.push(GpuInfo {
                         name,
@@ -384,18 +2140,56 @@ impl App {
                         fan_speed,
                         power_usage,
                         power_limit,
+                        core_clock_mhz,
+                        memory_clock_mhz,
+                        processes,
+                        ..Default::default()
                     });
 
                     while self.gpu_util_history.len() <= i as usize {
                         self.gpu_util_history
-                            .push(VecDeque::from(vec![0.0; HISTORY_LEN]));
+                            .push(empty_history(self.history_len));
                     }
                     self.gpu_util_history[i as usize].pop_front();
                     self.gpu_util_history[i as usize].push_back(utilization as f64);
                 }
                 if !self.gpus.is_empty() {
+                    let count = self.gpu_process_count();
+                    self.gpu_process_scroll = self.gpu_process_scroll.min(count.saturating_sub(1));
+                    return true;
+                }
+        }
+        false
+    }
+
+    #[cfg(not(feature = "nvml"))]
+    fn update_nvml_gpus(&mut self) -> bool {
+        false
+    }
+
+    fn update_gpu(&mut self) {
+        // Try NVML first (NVIDIA GPUs on all platforms)
+        if self.update_nvml_gpus() {
+            return;
+        }
+
+        if self.nvidia_smi_fallback {
+            match crate::nvidia_smi::query().filter(|gpus| !gpus.is_empty()) {
+                Some(gpus) => {
+                    for (i, gpu) in gpus.iter().enumerate() {
+                        while self.gpu_util_history.len() <= i {
+                            self.gpu_util_history
+                                .push(empty_history(self.history_len));
+                        }
+                        self.gpu_util_history[i].pop_front();
+                        self.gpu_util_history[i].push_back(gpu.utilization as f64);
+                    }
+                    self.gpus = gpus;
                     return;
                 }
+                // Not installed or no NVIDIA GPU; don't spawn it every tick
+                None => self.nvidia_smi_fallback = false,
+            }
         }
 
         // Fallback: platform-specific GPU detection
@@ -411,7 +2205,40 @@ impl App {
         {
             self.detect_linux_gpu();
         }
-        // Windows without NVML: no fallback (AMD/Intel don't expose easy APIs)
+        #[cfg(target_os = "windows")]
+        {
+            self.detect_windows_gpu();
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn detect_windows_gpu(&mut self) {
+        // Opened lazily so NVIDIA systems with working NVML never query PDH
+        if self.windows_gpu_sampler.is_none() {
+            self.windows_gpu_sampler = crate::windows_gpu::WindowsGpuSampler::new();
+        }
+        let Some(sampler) = &mut self.windows_gpu_sampler else {
+            return;
+        };
+        let readings = sampler.readings();
+
+        self.gpus.clear();
+        for (idx, reading) in readings.into_iter().enumerate() {
+            self.gpus.push(GpuInfo {
+                name: reading.name,
+                utilization: reading.utilization,
+                memory_used: reading.memory_used,
+                memory_total: reading.memory_total,
+                ..Default::default()
+            });
+
+            while self.gpu_util_history.len() <= idx {
+                self.gpu_util_history
+                    .push(empty_history(self.history_len));
+            }
+            self.gpu_util_history[idx].pop_front();
+            self.gpu_util_history[idx].push_back(reading.utilization as f64);
+        }
     }
 
     #[cfg(target_os = "macos")]
@@ -429,6 +2256,11 @@ impl App {
                 // Convert power from milliwatts to the same unit as NVML (milliwatts)
                 let power_usage = metrics.power_mw;
 
+                self.cpu_clusters = metrics.cpu_clusters;
+                self.cpu_power_mw = metrics.cpu_power_mw;
+                self.ane_power_mw = metrics.ane_power_mw;
+                self.package_power_mw = metrics.package_power_mw;
+
                 self.gpus.clear();
                 self.gpus.push(GpuInfo {
                     name: gpu_name,
@@ -439,11 +2271,13 @@ impl App {
                     fan_speed: None,
                     power_usage,
                     power_limit: None,
+                    core_clock_mhz: metrics.freq_mhz,
+                    ..Default::default()
                 });
 
                 if self.gpu_util_history.is_empty() {
                     self.gpu_util_history
-                        .push(VecDeque::from(vec![0.0; HISTORY_LEN]));
+                        .push(empty_history(self.history_len));
                 }
                 self.gpu_util_history[0].pop_front();
                 self.gpu_util_history[0].push_back(metrics.utilization as f64);
@@ -456,13 +2290,7 @@ impl App {
         if !self.gpus.iter().any(|g| g.name == name) {
             self.gpus.push(GpuInfo {
                 name,
-                temperature: 0,
-                utilization: 0,
-                memory_used: 0,
-                memory_total: 0,
-                fan_speed: None,
-                power_usage: None,
-                power_limit: None,
+                ..Default::default()
             });
         }
     }
@@ -491,9 +2319,9 @@ impl App {
                     .filter_map(|l| {
                         let slot = l.split_whitespace().next()?;
                         // Line format: "01:00.0 VGA compatible controller: AMD ... [Radeon ...]"
-                        let name = l.splitn(2, ": ").nth(1)?;
+                        let (_, name) = l.split_once(": ")?;
                         // Take the part after the second ": " (vendor: product)
-                        let product = name.splitn(2, ": ").nth(1).unwrap_or(name);
+                        let product = name.split_once(": ").map_or(name, |(_, p)| p);
                         Some((slot.to_string(), product.to_string()))
                     })
                     .collect::<Vec<_>>()
@@ -505,6 +2333,9 @@ impl App {
             Err(_) => return,
         };
 
+        // Rebuild the list every tick so readings don't freeze after the first sample
+        self.gpus.clear();
+
         for entry in entries.flatten() {
             let path = entry.path();
             let name_str = path
@@ -547,78 +2378,59 @@ impl App {
                 })
                 .unwrap_or_else(|| format!("GPU ({name_str})"));
 
-            // Utilization (AMD: gpu_busy_percent, Intel i915: similar)
-            let utilization = fs::read_to_string(device_path.join("gpu_busy_percent"))
-                .ok()
-                .and_then(|s| s.trim().parse::<u32>().ok())
-                .unwrap_or(0);
+            // Utilization: AMD reports gpu_busy_percent directly, Intel needs RC6 sampling
+            let driver = linux_gpu::driver_name(&device_path).unwrap_or_default();
+            let is_intel = linux_gpu::is_intel_driver(&driver);
+            let utilization = if is_intel {
+                self.intel_gpu_sampler.utilization(&path, &device_path)
+            } else {
+                linux_gpu::read_value::<u32>(&device_path.join("gpu_busy_percent"))
+            }
+            .unwrap_or(0);
 
             // VRAM (AMD only)
-            let mem_used = fs::read_to_string(device_path.join("mem_info_vram_used"))
-                .ok()
-                .and_then(|s| s.trim().parse::<u64>().ok())
-                .unwrap_or(0);
-            let mem_total = fs::read_to_string(device_path.join("mem_info_vram_total"))
-                .ok()
-                .and_then(|s| s.trim().parse::<u64>().ok())
-                .unwrap_or(0);
-
-            // Temperature: scan hwmon subdirectories for temp1_input
-            let hwmon_dir = device_path.join("hwmon");
-            let temperature = if hwmon_dir.is_dir() {
-                fs::read_dir(&hwmon_dir)
-                    .ok()
-                    .and_then(|entries| {
-                        for e in entries.flatten() {
-                            let temp_path = e.path().join("temp1_input");
-                            if let Ok(val) = fs::read_to_string(&temp_path) {
-                                if let Ok(t) = val.trim().parse::<u32>() {
-                                    return Some(t / 1000); // millidegrees → degrees
-                                }
-                            }
-                        }
-                        None
-                    })
-                    .unwrap_or(0)
-            } else {
-                0
-            };
+            let mem_used =
+                linux_gpu::read_value::<u64>(&device_path.join("mem_info_vram_used")).unwrap_or(0);
+            let mem_total =
+                linux_gpu::read_value::<u64>(&device_path.join("mem_info_vram_total")).unwrap_or(0);
 
-            // Power usage (AMD: power1_average in hwmon, microwatts)
-            let power_usage = if hwmon_dir.is_dir() {
-                fs::read_dir(&hwmon_dir)
-                    .ok()
-                    .and_then(|entries| {
-                        for e in entries.flatten() {
-                            let power_path = e.path().join("power1_average");
-                            if let Ok(val) = fs::read_to_string(&power_path) {
-                                if let Ok(uw) = val.trim().parse::<u64>() {
-                                    return Some((uw / 1000) as u32); // microwatts → milliwatts
-                                }
-                            }
-                        }
-                        None
-                    })
+            // Memory controller load, the closest thing amdgpu has to VRAM bandwidth
+            let memory_busy = linux_gpu::read_value::<u32>(&device_path.join("mem_busy_percent"));
+
+            // Current DPM levels for shader and memory clocks (AMD only)
+            let core_clock_mhz = if is_intel {
+                linux_gpu::intel_act_freq_mhz(&path, &device_path)
             } else {
-                None
+                linux_gpu::dpm_clock_mhz(&device_path.join("pp_dpm_sclk"))
             };
+            let memory_clock_mhz = linux_gpu::dpm_clock_mhz(&device_path.join("pp_dpm_mclk"));
+
+            // Temperatures, fan and power from hwmon
+            let hwmon = linux_gpu::read_hwmon(&device_path);
 
             if !self.gpus.iter().any(|g| g.name == gpu_name) {
                 self.gpus.push(GpuInfo {
                     name: gpu_name,
-                    temperature,
+                    temperature: hwmon.temperature.unwrap_or(0),
                     utilization,
                     memory_used: mem_used,
                     memory_total: mem_total,
                     fan_speed: None,
-                    power_usage,
-                    power_limit: None,
+                    power_usage: hwmon.power_mw,
+                    power_limit: hwmon.power_cap_mw,
+                    fan_rpm: hwmon.fan_rpm,
+                    core_clock_mhz,
+                    memory_clock_mhz,
+                    memory_busy,
+                    temp_junction: hwmon.temp_junction,
+                    temp_memory: hwmon.temp_memory,
+                    processes: Vec::new(),
                 });
 
                 let idx = self.gpus.len() - 1;
                 while self.gpu_util_history.len() <= idx {
                     self.gpu_util_history
-                        .push(VecDeque::from(vec![0.0; HISTORY_LEN]));
+                        .push(empty_history(self.history_len));
                 }
                 self.gpu_util_history[idx].pop_front();
                 self.gpu_util_history[idx].push_back(utilization as f64);
@@ -631,32 +2443,182 @@ impl App {
             SortBy::Cpu => self.processes.sort_by(|a, b| {
                 b.cpu.partial_cmp(&a.cpu).unwrap_or(std::cmp::Ordering::Equal)
             }),
-            SortBy::Memory => self.processes.sort_by(|a, b| b.memory.cmp(&a.memory)),
+            SortBy::Memory => self
+                .processes
+                .sort_by_key(|p| std::cmp::Reverse(p.memory)),
             SortBy::Name => self.processes.sort_by(|a, b| {
                 a.name.to_lowercase().cmp(&b.name.to_lowercase())
             }),
-            SortBy::Pid => self.processes.sort_by(|a, b| a.pid.cmp(&b.pid)),
+            SortBy::Pid => self.processes.sort_by_key(|p| p.pid),
+            SortBy::DiskIo => self
+                .processes
+                .sort_by_key(|p| std::cmp::Reverse(p.disk_io_rate())),
+            SortBy::Swap => self
+                .processes
+                .sort_by_key(|p| std::cmp::Reverse(p.swap.unwrap_or(0))),
+        }
+        // The pinned process leads whatever the sort
+        if let Some(pos) = self.pinned_pid.and_then(|pid| self.processes.iter().position(|p| p.pid == pid)) {
+            let pinned = self.processes.remove(pos);
+            self.processes.insert(0, pinned);
         }
     }
 
     fn update_filtered(&mut self) {
         if self.search_query.is_empty() {
             self.filtered_processes = (0..self.processes.len()).collect();
+        } else if let Some(pid) = self.run_pid.filter(|pid| self.search_query == pid.to_string()) {
+            self.filtered_processes = self
+                .processes
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.pid == pid)
+                .map(|(i, _)| i)
+                .collect();
         } else {
             let query = self.search_query.to_lowercase();
+            let scope = self.search_scope;
+            // The local process table says nothing about a remote host's
+            let system = self.remote.is_none().then_some(&self.system);
+            let matches = |s: &std::ffi::OsStr| s.to_string_lossy().to_lowercase().contains(&query);
             self.filtered_processes = self
                 .processes
                 .iter()
                 .enumerate()
                 .filter(|(_, p)| {
-                    p.name.to_lowercase().contains(&query)
-                        || p.pid.to_string().contains(&query)
+                    if p.name.to_lowercase().contains(&query) || p.pid.to_string().contains(&query) {
+                        return true;
+                    }
+                    if scope == SearchScope::Name {
+                        return false;
+                    }
+                    // Joined so a query can span arguments, e.g. "--port 80"
+                    if p.cmd.to_lowercase().contains(&query) {
+                        return true;
+                    }
+                    scope == SearchScope::Environment
+                        && system
+                            .and_then(|system| system.process(Pid::from_u32(p.pid)))
+                            .is_some_and(|process| process.environ().iter().any(|var| matches(var)))
                 })
                 .map(|(i, _)| i)
                 .collect();
         }
     }
 
+    /// Take a fresh socket list, naming owners from the process table.
+    fn set_connections(&mut self, mut list: Vec<Connection>) {
+        for conn in &mut list {
+            if let Some(process) = conn.pid.and_then(|pid| self.system.process(Pid::from_u32(pid))) {
+                conn.process = process.name().to_string_lossy().to_string();
+            }
+        }
+        self.connections = list;
+        self.sort_connections();
+        self.update_filtered_connections();
+        self.connection_scroll = self
+            .connection_scroll
+            .min(self.filtered_connections.len().saturating_sub(1));
+        self.resolve_remote_names();
+    }
+
+    /// Look up the next batch of uncached remote addresses in the background.
+    fn resolve_remote_names(&mut self) {
+        if !self.reverse_dns || self.tasks.is_running("reverse dns") {
+            return;
+        }
+        let ips = self
+            .dns
+            .unresolved(self.connections.iter().filter_map(|c| c.remote.map(|r| r.ip())));
+        if ips.is_empty() {
+            return;
+        }
+        self.tasks.spawn_quiet("reverse dns", move || {
+            let results: Vec<_> = ips.into_iter().map(|ip| (ip, rdns::lookup(ip))).collect();
+            Box::new(move |app: &mut App| {
+                app.dns.insert(results);
+                // Names are searchable too
+                app.update_filtered_connections();
+            })
+        });
+    }
+
+    /// `host:port` when reverse DNS is on and found a name, else the address.
+    pub fn remote_label(&self, conn: &Connection) -> String {
+        match conn.remote {
+            Some(remote) if self.reverse_dns => match self.dns.name(&remote.ip()) {
+                Some(name) => format!("{name}:{}", remote.port()),
+                None => connections::format_addr(Some(&remote)),
+            },
+            _ => connections::format_addr(conn.remote.as_ref()),
+        }
+    }
+
+    /// Country and ASN of the remote address, when a GeoIP database is
+    /// loaded and knows it.
+    pub fn origin_label(&self, conn: &Connection) -> Option<String> {
+        let origin = self.geoip.as_ref()?.lookup(conn.remote?.ip())?;
+        Some(origin.label())
+    }
+
+    pub fn toggle_reverse_dns(&mut self) {
+        self.reverse_dns = !self.reverse_dns;
+        self.update_filtered_connections();
+        self.resolve_remote_names();
+        self.set_status(if self.reverse_dns {
+            "Resolving remote host names".into()
+        } else {
+            "Showing numeric addresses".into()
+        });
+    }
+
+    /// Listening sockets, exposed on every interface first, then by port.
+    pub fn listening_ports(&self) -> Vec<&Connection> {
+        let mut ports: Vec<&Connection> =
+            self.connections.iter().filter(|c| c.is_listening()).collect();
+        ports.sort_by_key(|c| (!c.is_exposed(), c.local.port(), c.protocol as u8, c.local.is_ipv6()));
+        ports
+    }
+
+    fn sort_connections(&mut self) {
+        match self.connection_sort {
+            ConnSortBy::Process => self.connections.sort_by(|a, b| {
+                // Sockets without a known owner go last
+                (a.process.is_empty(), a.process.to_lowercase(), a.local.port())
+                    .cmp(&(b.process.is_empty(), b.process.to_lowercase(), b.local.port()))
+            }),
+            ConnSortBy::Port => self.connections.sort_by_key(|c| (c.local.port(), c.protocol as u8)),
+            ConnSortBy::Remote => self
+                .connections
+                .sort_by_key(|c| (c.remote.is_none(), c.remote.map(|r| (r.ip(), r.port())))),
+            ConnSortBy::State => self
+                .connections
+                .sort_by(|a, b| (&a.state, a.local.port()).cmp(&(&b.state, b.local.port()))),
+        }
+    }
+
+    fn update_filtered_connections(&mut self) {
+        let query = self.connection_query.to_lowercase();
+        self.filtered_connections = self
+            .connections
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| {
+                query.is_empty()
+                    || c.process.to_lowercase().contains(&query)
+                    || c.pid.is_some_and(|pid| pid.to_string().contains(&query))
+                    || connections::format_addr(Some(&c.local)).contains(&query)
+                    || self.remote_label(c).to_lowercase().contains(&query)
+                    || self
+                        .origin_label(c)
+                        .is_some_and(|o| o.to_lowercase().contains(&query))
+                    || c.state.to_lowercase().contains(&query)
+                    || c.proto_label().contains(&query)
+            })
+            .map(|(i, _)| i)
+            .collect();
+    }
+
     pub fn next_tab(&mut self) {
         let tabs = Tab::all();
         let idx = self.active_tab.index();
@@ -683,6 +2645,24 @@ impl App {
                     self.network_scroll += 1;
                 }
             }
+            Tab::Disks => {
+                let max = self.disk_devices.len().saturating_sub(1);
+                if self.disk_scroll < max {
+                    self.disk_scroll += 1;
+                }
+            }
+            Tab::Connections => {
+                let max = self.filtered_connections.len().saturating_sub(1);
+                if self.connection_scroll < max {
+                    self.connection_scroll += 1;
+                }
+            }
+            Tab::Overview => {
+                let max = self.gpu_process_count().saturating_sub(1);
+                if self.gpu_process_scroll < max {
+                    self.gpu_process_scroll += 1;
+                }
+            }
             _ => {}
         }
     }
@@ -695,6 +2675,15 @@ impl App {
             Tab::NetworkDetail => {
                 self.network_scroll = self.network_scroll.saturating_sub(1);
             }
+            Tab::Disks => {
+                self.disk_scroll = self.disk_scroll.saturating_sub(1);
+            }
+            Tab::Connections => {
+                self.connection_scroll = self.connection_scroll.saturating_sub(1);
+            }
+            Tab::Overview => {
+                self.gpu_process_scroll = self.gpu_process_scroll.saturating_sub(1);
+            }
             _ => {}
         }
     }
@@ -711,9 +2700,79 @@ impl App {
         }
     }
 
+    /// What the Name column shows: the command line when `c` is on and
+    /// one could be read, otherwise the name.
+    pub fn process_label<'a>(&self, p: &'a ProcessInfo) -> &'a str {
+        if self.show_cmdline && !p.cmd.is_empty() { &p.cmd } else { &p.name }
+    }
+
+    pub fn toggle_cmdline(&mut self) {
+        self.show_cmdline = !self.show_cmdline;
+        self.name_scroll = 0;
+        self.set_status(format!(
+            "Name column: {}",
+            if self.show_cmdline { "command line" } else { "process name" }
+        ));
+    }
+
+    /// Shift the Name column by `NAME_SCROLL_STEP` characters, up to the
+    /// point where the longest listed name still shows its last one.
+    pub fn scroll_names(&mut self, right: bool) {
+        let longest = self
+            .filtered_processes
+            .iter()
+            .filter_map(|&idx| self.processes.get(idx))
+            .map(|p| self.process_label(p).chars().count())
+            .max()
+            .unwrap_or(0);
+        self.name_scroll = if right {
+            (self.name_scroll + NAME_SCROLL_STEP).min(longest.saturating_sub(1))
+        } else {
+            self.name_scroll.saturating_sub(NAME_SCROLL_STEP)
+        };
+    }
+
+    pub fn half_page_down(&mut self) {
+        for _ in 0..(self.list_rows / 2).max(1) {
+            self.scroll_down();
+        }
+    }
+
+    pub fn half_page_up(&mut self) {
+        for _ in 0..(self.list_rows / 2).max(1) {
+            self.scroll_up();
+        }
+    }
+
+    /// Step to the next or previous row matching the search, wrapping at
+    /// either end of the filtered list.
+    pub fn next_match(&mut self, forward: bool) {
+        let (query, len, scroll) = match self.active_tab {
+            Tab::Processes => (&self.search_query, self.filtered_processes.len(), &mut self.process_scroll),
+            Tab::Connections => (
+                &self.connection_query,
+                self.filtered_connections.len(),
+                &mut self.connection_scroll,
+            ),
+            _ => return,
+        };
+        if query.is_empty() {
+            self.set_status("No search to step through".into());
+            return;
+        }
+        if len == 0 {
+            self.set_status("No matches".into());
+            return;
+        }
+        *scroll = if forward { (*scroll + 1) % len } else { (*scroll + len - 1) % len };
+    }
+
     pub fn scroll_to_top(&mut self) {
         self.process_scroll = 0;
         self.network_scroll = 0;
+        self.gpu_process_scroll = 0;
+        self.disk_scroll = 0;
+        self.connection_scroll = 0;
     }
 
     pub fn scroll_to_bottom(&mut self) {
@@ -724,51 +2783,528 @@ impl App {
             Tab::NetworkDetail => {
                 self.network_scroll = self.network_interfaces.len().saturating_sub(1);
             }
+            Tab::Disks => {
+                self.disk_scroll = self.disk_devices.len().saturating_sub(1);
+            }
+            Tab::Connections => {
+                self.connection_scroll = self.filtered_connections.len().saturating_sub(1);
+            }
+            Tab::Overview => {
+                self.gpu_process_scroll = self.gpu_process_count().saturating_sub(1);
+            }
             _ => {}
         }
     }
 
     pub fn toggle_sort(&mut self) {
+        if self.active_tab == Tab::Disks {
+            self.disk_sort = self.disk_sort.next();
+            self.sort_disks();
+            return;
+        }
+        if self.active_tab == Tab::Connections {
+            self.connection_sort = self.connection_sort.next();
+            self.sort_connections();
+            self.update_filtered_connections();
+            return;
+        }
         self.sort_by = self.sort_by.next();
+        // Swap is only read for its column; fill it in rather than sort
+        // on blanks until the next tick
+        if self.sort_by == SortBy::Swap && self.remote.is_none() {
+            for p in &mut self.processes {
+                if p.swap.is_none() {
+                    p.swap = swap(p.pid);
+                }
+            }
+        }
         self.sort_processes();
         self.update_filtered();
     }
 
+    pub fn toggle_disk_filter(&mut self) {
+        // Applied on the next tick; refreshing now would skew the I/O rates
+        self.filter_disks = !self.filter_disks;
+        self.set_status(if self.filter_disks {
+            "Hiding pseudo and duplicate filesystems".into()
+        } else {
+            "Showing all filesystems".into()
+        });
+    }
+
     pub fn toggle_theme(&mut self) {
-        self.theme = self.theme.next();
-        self.set_status(format!("Theme: {}", self.theme.label()));
+        self.theme = self.theme.next(self.user_themes.len());
+        self.set_status(format!("Theme: {}", self.theme_name()));
+    }
+
+    pub fn theme_name(&self) -> &str {
+        match self.theme {
+            Theme::User(i) => &self.user_themes[i].name,
+            theme => theme.label(),
+        }
+    }
+
+    pub fn theme_colors(&self) -> ThemeColors {
+        self.theme.colors(&self.user_themes)
+    }
+
+    /// The enabled columns this build can fill; large UI mode keeps only
+    /// the essential ones.
+    pub fn visible_process_columns(&self) -> Vec<ProcessColumn> {
+        self.process_columns
+            .iter()
+            .copied()
+            .filter(|c| c.available() && (!self.large_ui || c.essential()))
+            .collect()
+    }
+
+    /// Rows of the column chooser: the shown columns in display order,
+    /// then the hidden ones this build can fill.
+    pub fn chooser_columns(&self) -> Vec<ProcessColumn> {
+        let hidden = ProcessColumn::ALL
+            .into_iter()
+            .filter(|c| c.available() && !self.process_columns.contains(c));
+        self.process_columns
+            .iter()
+            .copied()
+            .filter(|c| c.available())
+            .chain(hidden)
+            .collect()
+    }
+
+    pub fn toggle_column_chooser(&mut self) {
+        self.column_chooser = match self.column_chooser {
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+
+    pub fn column_chooser_move(&mut self, down: bool) {
+        let last = self.chooser_columns().len().saturating_sub(1);
+        if let Some(row) = &mut self.column_chooser {
+            *row = if down { (*row + 1).min(last) } else { row.saturating_sub(1) };
+        }
+    }
+
+    fn chosen_column(&self) -> Option<ProcessColumn> {
+        self.column_chooser
+            .and_then(|row| self.chooser_columns().get(row).copied())
+    }
+
+    /// Show or hide the column under the chooser's cursor; a column shown
+    /// again goes last. Name always stays, so a row can still be told
+    /// apart.
+    pub fn toggle_chosen_column(&mut self) {
+        let Some(column) = self.chosen_column() else {
+            return;
+        };
+        if column == ProcessColumn::Name {
+            self.set_status("The Name column can't be hidden".into());
+        } else if let Some(i) = self.process_columns.iter().position(|&c| c == column) {
+            self.process_columns.remove(i);
+        } else {
+            self.process_columns.push(column);
+        }
+        // Keep the cursor on the column as it moves between the groups
+        self.column_chooser = self.chooser_columns().iter().position(|&c| c == column);
+    }
+
+    /// Move the shown column under the cursor one place left (up the
+    /// list) or right, taking the cursor with it.
+    pub fn move_chosen_column(&mut self, later: bool) {
+        let Some(column) = self.chosen_column() else {
+            return;
+        };
+        let Some(i) = self.process_columns.iter().position(|&c| c == column) else {
+            return;
+        };
+        let j = if later { i + 1 } else { i.wrapping_sub(1) };
+        if j < self.process_columns.len() {
+            self.process_columns.swap(i, j);
+            self.column_chooser = self.chooser_columns().iter().position(|&c| c == column);
+        }
+    }
+
+    pub fn toggle_large_ui(&mut self) {
+        self.large_ui = !self.large_ui;
+        self.set_status(format!(
+            "Large UI: {}",
+            if self.large_ui { "on" } else { "off" }
+        ));
+    }
+
+    pub fn toggle_line_chart(&mut self, graph: Graph) {
+        let chart = self.line_charts.insert(graph);
+        if !chart {
+            self.line_charts.remove(&graph);
+        }
+        self.set_status(format!(
+            "{} graph: {}",
+            graph.label(),
+            if chart { "line chart" } else { "sparkline" }
+        ));
+    }
+
+    pub fn toggle_rate_units(&mut self) {
+        self.rate_bits = !self.rate_bits;
+        self.set_status(format!(
+            "Network rates in {}",
+            if self.rate_bits { "bits/s" } else { "bytes/s" }
+        ));
+    }
+
+    /// A network rate in the chosen units, e.g. `1.2 MB/s` or `9.8 Mbit/s`.
+    pub fn format_rate(&self, bytes_per_sec: u64) -> String {
+        if self.rate_bits {
+            format!("{}/s", format_bits(bytes_per_sec))
+        } else {
+            format!("{}/s", format_bytes(bytes_per_sec))
+        }
     }
 
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    /// Past the end is fine: drawing clamps to the last page.
+    pub fn scroll_help(&mut self, lines: isize) {
+        self.help_scroll = self.help_scroll.saturating_add_signed(lines);
     }
 
     pub fn enter_search(&mut self) {
         self.input_mode = InputMode::Search;
-        self.search_query.clear();
+        if self.active_tab == Tab::Connections {
+            self.connection_query.clear();
+        } else {
+            self.search_query.clear();
+        }
     }
 
     pub fn exit_search(&mut self) {
         self.input_mode = InputMode::Normal;
-        self.search_query.clear();
-        self.update_filtered();
+        if self.active_tab == Tab::Connections {
+            self.connection_query.clear();
+            self.update_filtered_connections();
+        } else {
+            self.search_query.clear();
+            self.update_filtered();
+        }
     }
 
     pub fn search_push(&mut self, c: char) {
-        self.search_query.push(c);
+        self.search_push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    fn search_push_str(&mut self, text: &str) {
+        if self.active_tab == Tab::Connections {
+            self.connection_query.push_str(text);
+            self.connection_scroll = 0;
+            self.update_filtered_connections();
+            return;
+        }
+        self.search_query.push_str(text);
         self.process_scroll = 0;
         self.update_filtered();
     }
 
+    /// Whether a popup is drawn over the tab and takes the keys.
+    pub fn popup_open(&self) -> bool {
+        self.show_help
+            || self.column_chooser.is_some()
+            || self.kill_confirm.is_some()
+            || self.kill_all_confirm.is_some()
+            || self.show_process_detail
+            || self.profile_report.is_some()
+            || self.disk_detail.is_some()
+            || self.interface_detail.is_some()
+            || self.show_command_output
+            || self.show_events
+    }
+
+    /// Bracketed paste goes into an open search or command line as one
+    /// piece rather than as keystrokes; anywhere else it's dropped, so a
+    /// stray paste can't trigger key actions.
+    pub fn paste(&mut self, text: &str) {
+        if self.input_mode == InputMode::Normal || self.popup_open() {
+            return;
+        }
+        // First line only, without the newline a terminal copy tends to end in
+        let text: String = text
+            .lines()
+            .next()
+            .unwrap_or("")
+            .trim()
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect();
+        if text.is_empty() {
+            return;
+        }
+        match self.input_mode {
+            InputMode::Command => self.command_input.push_str(&text),
+            InputMode::Search => self.search_push_str(&text),
+            InputMode::Normal => {}
+        }
+    }
+
     pub fn search_pop(&mut self) {
+        if self.active_tab == Tab::Connections {
+            self.connection_query.pop();
+            self.update_filtered_connections();
+            return;
+        }
         self.search_query.pop();
         self.update_filtered();
     }
 
-    pub fn request_kill(&mut self) {
+    pub fn cycle_search_scope(&mut self) {
         if self.active_tab != Tab::Processes {
             return;
         }
+        self.search_scope = self.search_scope.next();
+        if self.search_scope == SearchScope::Environment && self.remote.is_some() {
+            self.set_status("Environment search only works on the local machine".into());
+            self.search_scope = self.search_scope.next();
+        }
+        self.process_scroll = 0;
+        self.update_filtered();
+    }
+
+    pub fn enter_command(&mut self) {
+        if self.refuse_remote() {
+            return;
+        }
+        self.input_mode = InputMode::Command;
+        self.command_input.clear();
+    }
+
+    /// The `:` prompt with `run -d ` typed, for launching a detached
+    /// command that's then pinned to the top of the table.
+    pub fn enter_run(&mut self) {
+        self.enter_command();
+        if self.input_mode == InputMode::Command {
+            self.command_input.push_str("run -d ");
+            self.run_pins = true;
+        }
+    }
+
+    pub fn exit_command(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.command_input.clear();
+        self.run_pins = false;
+    }
+
+    pub fn command_push(&mut self, c: char) {
+        self.command_input.push(c);
+    }
+
+    pub fn command_pop(&mut self) {
+        self.command_input.pop();
+    }
+
+    /// Execute the `:` prompt. Supported: `run <cmd>`, `run -d <cmd>`
+    /// (detached) and `export-history [file]`.
+    pub fn execute_command(&mut self) {
+        let input = std::mem::take(&mut self.command_input);
+        self.input_mode = InputMode::Normal;
+        let pin = std::mem::take(&mut self.run_pins);
+
+        let input = input.trim();
+        let (verb, rest) = input.split_once(' ').unwrap_or((input, ""));
+        match verb {
+            "run" => {
+                let rest = rest.trim();
+                match rest.strip_prefix("-d ") {
+                    Some(cmd) => self.run_command(cmd.trim(), true, pin),
+                    None => self.run_command(rest, false, pin),
+                }
+            }
+            "export-history" => {
+                let path = Some(rest.trim()).filter(|p| !p.is_empty());
+                self.export_history(path.map(str::to_string));
+            }
+            "" => {}
+            _ => self.set_status(format!("Unknown command: {verb}")),
+        }
+    }
+
+    /// Launch `command` and show it in the process table: pinned to the
+    /// top and selected for `R`, otherwise by filtering the table to it.
+    pub fn run_command(&mut self, command: &str, detached: bool, pin: bool) {
+        match SpawnedCommand::spawn(command, detached) {
+            Ok(spawned) => {
+                let pid = spawned.pid;
+                self.spawned.push(spawned);
+                // Either way the process shows up with the next refresh
+                self.active_tab = Tab::Processes;
+                if pin {
+                    self.pinned_pid = Some(pid);
+                    self.search_query.clear();
+                    self.sort_processes();
+                } else {
+                    self.run_pid = Some(pid);
+                    self.search_query = pid.to_string();
+                }
+                self.process_scroll = 0;
+                self.update_filtered();
+                let hint = if detached { "" } else { " — press o for output" };
+                self.log_event(EventLevel::Info, format!("Started PID {pid}{hint}"));
+            }
+            Err(e) => self.log_event(EventLevel::Error, format!("Failed to run: {e}")),
+        }
+    }
+
+    /// Query SMART for every physical disk behind a mount, at most every
+    /// `SMART_POLL`.
+    fn poll_smart(&mut self) {
+        let due = self.smart_polled_at.is_none_or(|at| at.elapsed() >= SMART_POLL);
+        if !due || self.tasks.is_running(SMART_TASK) {
+            return;
+        }
+        let mut devices: Vec<String> = self
+            .disk_devices
+            .iter()
+            .filter_map(|d| d.parent_disk.clone())
+            .collect();
+        devices.sort();
+        devices.dedup();
+        self.smart_polled_at = Some(Instant::now());
+        if devices.is_empty() {
+            return;
+        }
+        self.tasks.spawn(SMART_TASK, move || {
+            let results = smart::query_all(&devices);
+            Box::new(move |app: &mut App| app.smart.extend(results))
+        });
+    }
+
+    /// Apply the results of background tasks that have finished.
+    pub fn poll_tasks(&mut self) {
+        for completion in self.tasks.take_finished() {
+            completion(self);
+        }
+    }
+
+    /// Give exports and other tasks with a spinner up to `SHUTDOWN_WAIT` to
+    /// land before quitting, so their results aren't lost. Returns the
+    /// labels of any still running.
+    pub fn shutdown(&mut self) -> Vec<String> {
+        let deadline = Instant::now() + SHUTDOWN_WAIT;
+        while self.tasks.has_visible() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+            self.poll_tasks();
+        }
+        self.tasks.visible().map(|(label, _)| label.to_string()).collect()
+    }
+
+    fn poll_spawned(&mut self) {
+        let mut finished = Vec::new();
+        for cmd in &mut self.spawned {
+            if cmd.exit_status.is_none() && cmd.poll() {
+                finished.push((cmd.pid, cmd.exit_status));
+            }
+        }
+        for (pid, status) in finished {
+            if self.pinned_pid == Some(pid) {
+                self.pinned_pid = None;
+            }
+            let code = status
+                .and_then(|s| s.code())
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "signal".into());
+            self.log_event(EventLevel::Info, format!("PID {pid} exited ({code})"));
+        }
+    }
+
+    /// The most recent attached command, whose output the popup shows.
+    pub fn last_attached_command(&self) -> Option<&SpawnedCommand> {
+        self.spawned.iter().rev().find(|c| !c.detached)
+    }
+
+    pub fn toggle_command_output(&mut self) {
+        if self.last_attached_command().is_some() {
+            self.show_command_output = !self.show_command_output;
+        } else {
+            self.set_status("No command output — use :run <cmd>".into());
+        }
+    }
+
+    /// Sample the selected process with perf in the background and show
+    /// the hottest functions when done.
+    pub fn profile_selected(&mut self) {
+        if self.active_tab != Tab::Processes || self.refuse_remote() {
+            return;
+        }
+        if self.tasks.is_running("Profiling") {
+            self.set_status("A profile is already running".into());
+            return;
+        }
+        let Some((pid, name)) = self.selected_process().map(|p| (p.pid, p.name.clone())) else {
+            return;
+        };
+        let secs = self.profile_secs;
+        self.set_status(format!("Profiling {name} (PID {pid}) for {secs}s"));
+        self.tasks.spawn("Profiling", move || {
+            let result = profiler::profile(pid, name, secs);
+            Box::new(move |app: &mut App| match result {
+                Ok(report) => app.profile_report = Some(report),
+                Err(e) => app.log_event(EventLevel::Error, e),
+            })
+        });
+    }
+
+    /// Write the filtered, sorted process table to a timestamped CSV in
+    /// the working directory.
+    pub fn export_processes(&mut self) {
+        let csv = export::processes_csv(
+            self.filtered_processes
+                .iter()
+                .filter_map(|&idx| self.processes.get(idx)),
+        );
+        let count = self.filtered_processes.len();
+        let path = export::timestamped_name("processes", "csv");
+        self.tasks.spawn("Exporting processes", move || {
+            let result = std::fs::write(&path, csv);
+            Box::new(move |app: &mut App| match result {
+                Ok(()) => app.log_event(EventLevel::Info, format!("Exported {count} processes to {path}")),
+                Err(e) => app.log_event(EventLevel::Error, format!("Export to {path} failed: {e}")),
+            })
+        });
+    }
+
+    /// The graph histories as CSV, to `path` or a timestamped file in the
+    /// working directory.
+    pub fn export_history(&mut self, path: Option<String>) {
+        let csv = export::history_csv(self);
+        let rows = csv.lines().count() - 1;
+        let path = path.unwrap_or_else(|| export::timestamped_name("history", "csv"));
+        self.tasks.spawn("Exporting history", move || {
+            let result = std::fs::write(&path, csv);
+            Box::new(move |app: &mut App| match result {
+                Ok(()) => app.log_event(EventLevel::Info, format!("Exported {rows} samples to {path}")),
+                Err(e) => app.log_event(EventLevel::Error, format!("Export to {path} failed: {e}")),
+            })
+        });
+    }
+
+    /// `S`: everything on screen and in the graphs, as pretty JSON.
+    pub fn save_snapshot(&mut self) {
+        let json = snapshot::full_state(self).to_pretty();
+        let path = export::timestamped_name("snapshot", "json");
+        self.tasks.spawn("Saving snapshot", move || {
+            let result = std::fs::write(&path, json);
+            Box::new(move |app: &mut App| match result {
+                Ok(()) => app.log_event(EventLevel::Info, format!("Saved snapshot to {path}")),
+                Err(e) => app.log_event(EventLevel::Error, format!("Snapshot to {path} failed: {e}")),
+            })
+        });
+    }
+
+    pub fn request_kill(&mut self) {
+        if self.active_tab != Tab::Processes || self.refuse_remote() {
+            return;
+        }
         if let Some(&idx) = self.filtered_processes.get(self.process_scroll)
             && let Some(proc) = self.processes.get(idx)
         {
@@ -777,33 +3313,228 @@ impl App {
     }
 
     pub fn confirm_kill(&mut self) {
+        let force = std::mem::take(&mut self.kill_force);
         if let Some(pid) = self.kill_confirm.take() {
             let sysinfo_pid = Pid::from_u32(pid);
             if let Some(process) = self.system.process(sysinfo_pid) {
-                if process.kill_with(Signal::Term).unwrap_or(false) {
-                    self.set_status(format!("Sent SIGTERM to PID {pid}"));
+                let name = process.name().to_string_lossy().to_string();
+                let start_time = process.start_time();
+                let signal = if !force && process.kill_with(Signal::Term).unwrap_or(false) {
+                    self.log_event(EventLevel::Info, format!("Sent SIGTERM to {name} (PID {pid})"));
+                    "TERM"
                 } else if process.kill() {
-                    self.set_status(format!("Killed PID {pid}"));
+                    self.log_event(EventLevel::Info, format!("Killed {name} (PID {pid})"));
+                    "KILL"
                 } else {
-                    self.set_status(format!("Failed to kill PID {pid}"));
-                }
+                    self.log_event(EventLevel::Error, format!("Failed to kill {name} (PID {pid})"));
+                    return;
+                };
+                self.run_hook(
+                    HookEvent::ProcessKill,
+                    vec![("pid", pid.to_string()), ("name", name.clone()), ("signal", signal.to_string())],
+                );
+                self.watch_exit(pid, name, start_time, force);
             } else {
                 self.set_status(format!("Process {pid} not found"));
             }
         }
     }
 
+    /// Ask to SIGTERM every process the search currently matches, except
+    /// this one.
+    pub fn request_kill_all(&mut self) {
+        if self.active_tab != Tab::Processes || self.refuse_remote() {
+            return;
+        }
+        if self.search_query.is_empty() {
+            self.set_status("Filter the list with / first".into());
+            return;
+        }
+        let own = std::process::id();
+        let targets: Vec<_> = self
+            .filtered_processes
+            .iter()
+            .filter_map(|&idx| self.processes.get(idx))
+            .filter(|p| p.pid != own)
+            .filter_map(|p| {
+                let start_time = self.system.process(Pid::from_u32(p.pid))?.start_time();
+                Some((p.pid, start_time, p.name.clone()))
+            })
+            .collect();
+        if targets.is_empty() {
+            self.set_status("No matching processes".into());
+        } else {
+            self.kill_all_confirm = Some(targets);
+        }
+    }
+
+    pub fn confirm_kill_all(&mut self) {
+        let Some(targets) = self.kill_all_confirm.take() else {
+            return;
+        };
+        let mut signalled = Vec::new();
+        let mut failed = 0;
+        for (pid, start_time, name) in targets {
+            // A PID reused since the prompt is someone else's process
+            match self.system.process(Pid::from_u32(pid)).filter(|p| p.start_time() == start_time) {
+                Some(process) if process.kill_with(Signal::Term).unwrap_or(false) => {
+                    signalled.push((pid, start_time));
+                    self.run_hook(
+                        HookEvent::ProcessKill,
+                        vec![("pid", pid.to_string()), ("name", name), ("signal", "TERM".to_string())],
+                    );
+                }
+                // Already gone counts as done
+                None => {}
+                Some(_) => failed += 1,
+            }
+        }
+        let query = self.search_query.clone();
+        let mut msg = format!("Sent SIGTERM to {} processes matching \"{query}\"", signalled.len());
+        if failed > 0 {
+            msg.push_str(&format!(", {failed} refused"));
+        }
+        self.log_event(
+            if failed > 0 { EventLevel::Warning } else { EventLevel::Info },
+            msg,
+        );
+        if signalled.is_empty() {
+            return;
+        }
+        let count = signalled.len();
+        self.tasks.spawn(format!("Waiting for {count} processes to exit"), move || {
+            let running = wait_for_all_exit(&signalled);
+            Box::new(move |app: &mut App| {
+                app.session.processes_killed += count - running.len();
+                app.processes
+                    .retain(|p| running.contains(&p.pid) || !signalled.iter().any(|&(pid, _)| pid == p.pid));
+                app.update_filtered();
+                app.process_scroll = app
+                    .process_scroll
+                    .min(app.filtered_processes.len().saturating_sub(1));
+                if running.is_empty() {
+                    app.log_event(EventLevel::Info, format!("All {count} processes exited"));
+                } else {
+                    app.log_event(
+                        EventLevel::Warning,
+                        format!("{} of {count} processes still running after {}s", running.len(), KILL_WAIT.as_secs()),
+                    );
+                }
+            })
+        });
+    }
+
     pub fn cancel_kill(&mut self) {
         self.kill_confirm = None;
+        self.kill_force = false;
+    }
+
+    /// Poll a signalled process every 50ms instead of waiting for the next
+    /// tick, so the status says when it actually exits.
+    fn watch_exit(&mut self, pid: u32, name: String, start_time: u64, forced: bool) {
+        self.tasks.spawn(format!("Waiting for PID {pid} to exit"), move || {
+            let started = Instant::now();
+            let exited = wait_for_exit(pid, start_time);
+            let elapsed = started.elapsed();
+            Box::new(move |app: &mut App| app.finish_kill(pid, name, exited, elapsed, forced))
+        });
+    }
+
+    fn finish_kill(&mut self, pid: u32, name: String, exited: bool, elapsed: Duration, forced: bool) {
+        if exited {
+            self.log_event(
+                EventLevel::Info,
+                format!("{name} (PID {pid}) exited after {:.1}s", elapsed.as_secs_f64()),
+            );
+            self.session.processes_killed += 1;
+            // Drop it now rather than leaving it listed until the next tick
+            self.processes.retain(|p| p.pid != pid);
+            self.update_filtered();
+            self.process_scroll = self
+                .process_scroll
+                .min(self.filtered_processes.len().saturating_sub(1));
+        } else if forced {
+            self.log_event(
+                EventLevel::Error,
+                format!("{name} (PID {pid}) survived SIGKILL; it may be stuck in uninterruptible I/O"),
+            );
+        } else {
+            // Ask before escalating; SIGKILL gives it no chance to clean up
+            self.log_event(
+                EventLevel::Warning,
+                format!("{name} (PID {pid}) still running after {}s", KILL_WAIT.as_secs()),
+            );
+            self.kill_confirm = Some(pid);
+            self.kill_force = true;
+        }
     }
 
     pub fn set_status(&mut self, msg: String) {
         self.status_message = Some((msg, Instant::now()));
     }
 
+    /// Show in the status bar and keep in the event history; `set_status`
+    /// alone is for acknowledging key presses.
+    pub fn log_event(&mut self, level: EventLevel, msg: String) {
+        self.events.push(level, msg.clone());
+        self.set_status(msg);
+    }
+
+    /// Run the configured command for `event`, if any, in the background;
+    /// only a failure is worth an entry in the event history.
+    fn run_hook(&mut self, event: HookEvent, vars: Vec<(&'static str, String)>) {
+        let Some(template) = self.hooks.command(event).map(str::to_string) else {
+            return;
+        };
+        self.tasks.spawn_quiet(event.key(), move || {
+            let result = hooks::run(event, &template, &vars);
+            Box::new(move |app: &mut App| {
+                if let Err(e) = result {
+                    app.log_event(EventLevel::Error, format!("{} hook failed: {e}", event.key()));
+                }
+            })
+        });
+    }
+
+    pub fn toggle_events(&mut self) {
+        self.show_events = !self.show_events;
+        self.events_scroll = 0;
+    }
+
+    pub fn events_scroll_down(&mut self) {
+        self.events_scroll = (self.events_scroll + 1).min(self.events.len().saturating_sub(1));
+    }
+
+    pub fn events_scroll_up(&mut self) {
+        self.events_scroll = self.events_scroll.saturating_sub(1);
+    }
+
+    /// Seconds since boot of the monitored host.
+    pub fn uptime(&self) -> u64 {
+        match &self.remote {
+            Some(remote) => remote.uptime,
+            None => System::uptime(),
+        }
+    }
+
+    /// 1, 5 and 15 minute load of the monitored host.
+    pub fn load_average(&self) -> [f64; 3] {
+        match &self.remote {
+            Some(remote) => remote.load_average,
+            None => {
+                let load = System::load_average();
+                [load.one, load.five, load.fifteen]
+            }
+        }
+    }
+
     pub fn uptime_str(&self) -> String {
-        let sys_uptime = System::uptime();
-        format_duration(sys_uptime)
+        format_duration(self.uptime())
+    }
+
+    pub fn reset_peaks(&mut self) {
+        self.peaks = Peaks::default();
+        self.set_status("Session peaks reset".into());
     }
 
     pub fn monitor_uptime_str(&self) -> String {
@@ -812,65 +3543,309 @@ impl App {
 
     pub fn boot_time_str(&self) -> String {
         let secs_since_epoch = self.boot_time;
-        let uptime = System::uptime();
+        let uptime = self.uptime();
         format!("{}s ago (uptime: {})", secs_since_epoch, format_duration(uptime))
     }
 
+    fn gpu_process_count(&self) -> usize {
+        self.gpus.iter().map(|g| g.processes.len()).sum()
+    }
+
+    /// The GPU process highlighted on the Overview tab, counting across all GPUs.
+    pub fn selected_gpu_process(&self) -> Option<&GpuProcess> {
+        self.gpus
+            .iter()
+            .flat_map(|g| &g.processes)
+            .nth(self.gpu_process_scroll)
+    }
+
+    pub fn jump_to_gpu_process(&mut self) {
+        if let Some(pid) = self.selected_gpu_process().map(|p| p.pid)
+            && !self.select_pid(pid)
+        {
+            self.set_status(format!("PID {pid} is not in the process list"));
+        }
+    }
+
+    /// Switch to the Processes tab with `pid` selected. Clears any search
+    /// filter that would hide it; returns false if the PID isn't listed.
+    pub fn select_pid(&mut self, pid: u32) -> bool {
+        let Some(pos) = self.processes.iter().position(|p| p.pid == pid) else {
+            return false;
+        };
+        self.search_query.clear();
+        self.update_filtered();
+        self.active_tab = Tab::Processes;
+        self.process_scroll = pos;
+        true
+    }
+
+    /// Selects the selected process's parent, clearing the search if it
+    /// hides it. With the detail popup open, the popup moves up to the
+    /// parent too, so repeated presses walk up the ancestry.
+    pub fn select_parent(&mut self) {
+        if self.refuse_remote() {
+            return;
+        }
+        let (pid, parent) = match &self.process_detail {
+            Some(detail) if self.show_process_detail => (detail.base.pid, detail.parent_pid),
+            _ => match self.selected_process() {
+                Some(p) => (
+                    p.pid,
+                    self.system.process(Pid::from_u32(p.pid)).and_then(|p| p.parent()).map(|pp| pp.as_u32()),
+                ),
+                None => return,
+            },
+        };
+        let Some(parent) = parent else {
+            self.set_status(format!("PID {pid} has no parent"));
+            return;
+        };
+        if !self.select_pid(parent) {
+            self.set_status(format!("Parent PID {parent} is not in the process list"));
+            return;
+        }
+        if self.show_process_detail {
+            // Highlight where we came from, so Enter walks back down
+            self.detail_child = Some(pid);
+            self.show_detail();
+        }
+    }
+
+    /// Copies the selected process's PID, or its full command line (the
+    /// name when that's unreadable), to the clipboard.
+    pub fn copy_selected(&mut self, command: bool) {
+        let Some(p) = self.selected_process() else {
+            return;
+        };
+        let (text, what) = if !command {
+            (p.pid.to_string(), format!("PID {}", p.pid))
+        } else if p.cmd.is_empty() {
+            (p.name.clone(), format!("name of PID {}", p.pid))
+        } else {
+            (p.cmd.clone(), format!("command line of PID {}", p.pid))
+        };
+        match clipboard::copy(&text) {
+            Ok(via) => self.set_status(format!("Copied {what} (via {via})")),
+            Err(e) => self.set_status(format!("Couldn't copy {what}: {e}")),
+        }
+    }
+
     pub fn selected_process(&self) -> Option<&ProcessInfo> {
         self.filtered_processes
             .get(self.process_scroll)
             .and_then(|&idx| self.processes.get(idx))
     }
 
+    pub fn show_disk_detail(&mut self) {
+        if self.refuse_remote() {
+            return;
+        }
+        let Some(info) = self.disk_devices.get(self.disk_scroll) else {
+            return;
+        };
+        self.disk_detail = self
+            .disks
+            .iter()
+            .find(|d| d.mount_point().to_string_lossy() == info.mount)
+            .map(FilesystemDetail::collect);
+    }
+
+    pub fn show_interface_detail(&mut self) {
+        if self.refuse_remote() {
+            return;
+        }
+        self.interface_detail = self
+            .network_interfaces
+            .get(self.network_scroll)
+            .map(InterfaceDetail::collect);
+    }
+
     pub fn show_detail(&mut self) {
-        if self.active_tab != Tab::Processes {
+        if self.active_tab != Tab::Processes || self.refuse_remote() {
             return;
         }
         if let Some(&idx) = self.filtered_processes.get(self.process_scroll)
             && let Some(p) = self.processes.get(idx)
         {
-            let pid = Pid::from_u32(p.pid);
-            let base = ProcessInfo {
-                pid: p.pid,
-                name: p.name.clone(),
-                cpu: p.cpu,
-                memory: p.memory,
-                status: p.status.clone(),
-                run_time: p.run_time,
-                disk_read: p.disk_read,
-                disk_write: p.disk_write,
-            };
-            let detail = if let Some(proc_) = self.system.process(pid) {
-                ProcessDetail {
-                    base,
-                    parent_pid: proc_.parent().map(|pp| pp.as_u32()),
-                    cmd: proc_.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect::<Vec<_>>().join(" "),
-                    exe: proc_.exe().map(|e| e.to_string_lossy().to_string()).unwrap_or_default(),
-                    root: proc_.root().map(|r| r.to_string_lossy().to_string()).unwrap_or_default(),
-                    environ_count: proc_.environ().len(),
-                    threads: proc_.tasks().map(|t| t.len() as u64),
-                    virtual_memory: proc_.virtual_memory(),
+            self.process_detail = Some(self.read_detail(p.clone()));
+            self.show_process_detail = true;
+        }
+    }
+
+    fn read_detail(&self, mut base: ProcessInfo) -> ProcessDetail {
+        let pid = Pid::from_u32(base.pid);
+        if base.shared_memory.is_none() {
+            base.shared_memory = shared_memory(base.pid);
+        }
+        if base.swap.is_none() {
+            base.swap = swap(base.pid);
+        }
+        if let Some(proc_) = self.system.process(pid) {
+            ProcessDetail {
+                base,
+                parent_pid: proc_.parent().map(|pp| pp.as_u32()),
+                cmd: proc_.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect::<Vec<_>>().join(" "),
+                exe: proc_.exe().map(|e| e.to_string_lossy().to_string()).unwrap_or_default(),
+                root: proc_.root().map(|r| r.to_string_lossy().to_string()).unwrap_or_default(),
+                environ_count: proc_.environ().len(),
+                threads: proc_.tasks().map(|t| t.len() as u64),
+                virtual_memory: proc_.virtual_memory(),
+                thread_groups: self.thread_groups(pid),
+                start_time: proc_.start_time(),
+                exited: false,
+                children: self.child_processes(pid),
+            }
+        } else {
+            ProcessDetail {
+                base,
+                parent_pid: None,
+                cmd: String::new(),
+                exe: String::new(),
+                root: String::new(),
+                environ_count: 0,
+                threads: None,
+                virtual_memory: 0,
+                thread_groups: Vec::new(),
+                start_time: 0,
+                exited: false,
+                children: Vec::new(),
+            }
+        }
+    }
+
+    /// Re-reads the open detail popup's process so its stats stay live.
+    /// Once the process is gone the popup keeps its last numbers.
+    fn refresh_detail(&mut self) {
+        let Some(detail) = self.process_detail.as_ref().filter(|d| !d.exited) else {
+            return;
+        };
+        let pid = detail.base.pid;
+        let start_time = detail.start_time;
+        let alive = self.system.process(Pid::from_u32(pid)).is_some_and(|p| p.start_time() == start_time);
+        let current = self.processes.iter().find(|p| p.pid == pid).cloned();
+        match current {
+            Some(base) if alive => self.process_detail = Some(self.read_detail(base)),
+            _ => {
+                if let Some(detail) = self.process_detail.as_mut() {
+                    detail.exited = true;
+                    detail.base.cpu = 0.0;
                 }
-            } else {
-                ProcessDetail {
-                    base,
-                    parent_pid: None,
-                    cmd: String::new(),
-                    exe: String::new(),
-                    root: String::new(),
-                    environ_count: 0,
-                    threads: None,
-                    virtual_memory: 0,
+            }
+        }
+    }
+
+    /// Samples the process in the detail popup, or the selected one while
+    /// the popup is closed, so opening it already has some history.
+    fn record_process_history(&mut self) {
+        let pid = match &self.process_detail {
+            Some(detail) if self.show_process_detail => {
+                if detail.exited {
+                    return;
                 }
+                detail.base.pid
+            }
+            _ => match self.selected_process() {
+                Some(p) => p.pid,
+                None => return,
+            },
+        };
+        let Some(process) = self.system.process(Pid::from_u32(pid)) else {
+            return;
+        };
+        let start_time = process.start_time();
+        let (cpu, memory) = (process.cpu_usage() as f64, process.memory() as f64);
+        let history = match &mut self.process_history {
+            Some(h) if h.pid == pid && h.start_time == start_time => h,
+            slot => slot.insert(ProcessHistory {
+                pid,
+                start_time,
+                cpu: VecDeque::with_capacity(PROCESS_HISTORY_LEN),
+                memory: VecDeque::with_capacity(PROCESS_HISTORY_LEN),
+            }),
+        };
+        for (samples, value) in [(&mut history.cpu, cpu), (&mut history.memory, memory)] {
+            if samples.len() == PROCESS_HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back(value);
+        }
+    }
+
+    /// Per-thread CPU of `pid` summed by thread name (`/proc/<pid>/task/*/comm`).
+    fn thread_groups(&self, pid: Pid) -> Vec<ThreadGroup> {
+        let Some(tasks) = self.system.process(pid).and_then(|p| p.tasks()) else {
+            return Vec::new();
+        };
+        let mut groups: Vec<ThreadGroup> = Vec::new();
+        for thread in tasks.iter().filter_map(|tid| self.system.process(*tid)) {
+            let comm = thread.name().to_string_lossy();
+            let stem = comm.trim_end_matches(|c: char| c.is_ascii_digit());
+            let name = if stem.is_empty() || stem.len() == comm.len() {
+                comm.to_string()
+            } else {
+                format!("{stem}*")
             };
-            self.process_detail = Some(detail);
-            self.show_process_detail = true;
+            match groups.iter_mut().find(|g| g.name == name) {
+                Some(group) => {
+                    group.threads += 1;
+                    group.cpu += thread.cpu_usage();
+                }
+                None => groups.push(ThreadGroup {
+                    name,
+                    threads: 1,
+                    cpu: thread.cpu_usage(),
+                }),
+            }
         }
+        groups.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(b.threads.cmp(&a.threads)));
+        groups
     }
 
     pub fn close_detail(&mut self) {
         self.show_process_detail = false;
         self.process_detail = None;
+        self.detail_child = None;
+    }
+
+    fn child_processes(&self, pid: Pid) -> Vec<ProcessInfo> {
+        self.processes
+            .iter()
+            .filter(|p| self.system.process(Pid::from_u32(p.pid)).and_then(|c| c.parent()) == Some(pid))
+            .cloned()
+            .collect()
+    }
+
+    /// Moves the detail popup's child highlight by `delta`, starting from
+    /// the first child when none is highlighted yet.
+    pub fn detail_child_move(&mut self, delta: isize) {
+        let Some(detail) = &self.process_detail else {
+            return;
+        };
+        if detail.children.is_empty() {
+            return;
+        }
+        let current = self
+            .detail_child
+            .and_then(|pid| detail.children.iter().position(|c| c.pid == pid));
+        let next = match current {
+            Some(i) => i.saturating_add_signed(delta).min(detail.children.len() - 1),
+            None => 0,
+        };
+        self.detail_child = Some(detail.children[next].pid);
+    }
+
+    /// Closes the detail popup with the highlighted child selected in the
+    /// process table. Returns false, leaving the popup open, without one.
+    pub fn jump_to_child(&mut self) -> bool {
+        let Some(pid) = self.detail_child else {
+            return false;
+        };
+        self.close_detail();
+        if !self.select_pid(pid) {
+            self.set_status(format!("PID {pid} has exited"));
+        }
+        true
     }
 
     pub fn has_gpu(&self) -> bool {
@@ -878,6 +3853,10 @@ impl App {
     }
 }
 
+/// Neural Engine power at full load, used to turn ANE power into a usage
+/// percentage (the same 8 W reference asitop uses).
+pub const ANE_MAX_POWER_MW: u32 = 8000;
+
 pub fn format_duration(secs: u64) -> String {
     let days = secs / 86400;
     let hours = (secs % 86400) / 3600;
@@ -891,6 +3870,99 @@ pub fn format_duration(secs: u64) -> String {
     }
 }
 
+/// Intervals and graph spans: `250ms`, `1s`, `2.5s`, `10m`, `1h 30m`.
+pub fn format_interval(interval: Duration) -> String {
+    let ms = interval.as_millis();
+    let secs = interval.as_secs();
+    if ms < 1000 {
+        format!("{ms}ms")
+    } else if secs < 60 && !ms.is_multiple_of(1000) {
+        format!("{:.1}s", interval.as_secs_f64())
+    } else if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        match secs % 60 {
+            0 => format!("{}m", secs / 60),
+            s => format!("{}m {s}s", secs / 60),
+        }
+    } else {
+        match (secs % 3600) / 60 {
+            0 => format!("{}h", secs / 3600),
+            m => format!("{}h {m}m", secs / 3600),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn shared_memory(pid: u32) -> Option<u64> {
+    crate::procmem::shared_bytes(pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn shared_memory(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn swap(pid: u32) -> Option<u64> {
+    crate::procmem::swap_bytes(pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn swap(_pid: u32) -> Option<u64> {
+    None
+}
+
+fn empty_history(len: usize) -> VecDeque<f64> {
+    VecDeque::from(vec![0.0; len])
+}
+
+/// Whether `pid` exits (or becomes a zombie) within `KILL_WAIT`. A new
+/// process reusing the PID has a different start time and counts as exited.
+fn wait_for_exit(pid: u32, start_time: u64) -> bool {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    let started = Instant::now();
+    loop {
+        system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+        match system.process(pid) {
+            None => return true,
+            Some(p) if p.start_time() != start_time || p.status() == ProcessStatus::Zombie => {
+                return true;
+            }
+            Some(_) => {}
+        }
+        if started.elapsed() >= KILL_WAIT {
+            return false;
+        }
+        std::thread::sleep(KILL_POLL_INTERVAL);
+    }
+}
+
+/// Like `wait_for_exit` for several processes at once; the PIDs still
+/// running when `KILL_WAIT` is up.
+fn wait_for_all_exit(targets: &[(u32, u64)]) -> Vec<u32> {
+    let pids: Vec<Pid> = targets.iter().map(|&(pid, _)| Pid::from_u32(pid)).collect();
+    let mut system = System::new();
+    let started = Instant::now();
+    loop {
+        system.refresh_processes(ProcessesToUpdate::Some(&pids), true);
+        let running: Vec<u32> = targets
+            .iter()
+            .filter(|&&(pid, start_time)| {
+                system.process(Pid::from_u32(pid)).is_some_and(|p| {
+                    p.start_time() == start_time && p.status() != ProcessStatus::Zombie
+                })
+            })
+            .map(|&(pid, _)| pid)
+            .collect();
+        if running.is_empty() || started.elapsed() >= KILL_WAIT {
+            return running;
+        }
+        std::thread::sleep(KILL_POLL_INTERVAL);
+    }
+}
+
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -909,3 +3981,63 @@ pub fn format_bytes(bytes: u64) -> String {
         format!("{bytes} B")
     }
 }
+
+/// Bytes as bits with decimal prefixes, the way link speeds and ISP plans
+/// are quoted (1 Mbit = 1,000,000 bits).
+pub fn format_bits(bytes: u64) -> String {
+    const KBIT: f64 = 1e3;
+    const MBIT: f64 = 1e6;
+    const GBIT: f64 = 1e9;
+    const TBIT: f64 = 1e12;
+
+    let bits = bytes as f64 * 8.0;
+    if bits >= TBIT {
+        format!("{:.1} Tbit", bits / TBIT)
+    } else if bits >= GBIT {
+        format!("{:.1} Gbit", bits / GBIT)
+    } else if bits >= MBIT {
+        format!("{:.1} Mbit", bits / MBIT)
+    } else if bits >= KBIT {
+        format!("{:.1} kbit", bits / KBIT)
+    } else {
+        format!("{bits} bit")
+    }
+}
+
+/// Processes with compute or graphics contexts on an NVIDIA GPU, merged by
+/// PID and sorted by GPU memory.
+#[cfg(feature = "nvml")]
+fn nvml_processes(device: &nvml_wrapper::Device, system: &System) -> Vec<GpuProcess> {
+    let mut processes: Vec<GpuProcess> = Vec::new();
+    let compute = device.running_compute_processes().unwrap_or_default();
+    let graphics = device.running_graphics_processes().unwrap_or_default();
+    for (info, is_compute) in compute
+        .into_iter()
+        .map(|p| (p, true))
+        .chain(graphics.into_iter().map(|p| (p, false)))
+    {
+        let memory = match info.used_gpu_memory {
+            UsedGpuMemory::Used(bytes) => Some(bytes),
+            UsedGpuMemory::Unavailable => None,
+        };
+        if let Some(existing) = processes.iter_mut().find(|p| p.pid == info.pid) {
+            existing.compute |= is_compute;
+            existing.graphics |= !is_compute;
+            existing.memory = existing.memory.max(memory);
+            continue;
+        }
+        let name = system
+            .process(Pid::from_u32(info.pid))
+            .map(|p| p.name().to_string_lossy().to_string())
+            .unwrap_or_else(|| "?".into());
+        processes.push(GpuProcess {
+            pid: info.pid,
+            name,
+            memory,
+            compute: is_compute,
+            graphics: !is_compute,
+        });
+    }
+    processes.sort_by_key(|p| std::cmp::Reverse(p.memory));
+    processes
+}
@@ -0,0 +1,232 @@
+//! Command-line arguments.
+
+use std::env;
+
+use crate::app::{MAX_HISTORY_LEN, MIN_HISTORY_LEN};
+
+const USAGE: &str = "\
+Usage: rustmonitor [OPTIONS]
+       rustmonitor snapshot [FILE]
+       rustmonitor diff <A.json> <B.json>
+
+Commands:
+  snapshot [FILE]      Capture the current state as JSON (to stdout without FILE)
+  diff <A> <B>         Print system and process differences between two snapshots
+
+Options:
+  --daemon             Run without the TUI, logging samples (to stdout without --log)
+  --log <FILE>         Append daemon samples to FILE
+  --log-format <FMT>   jsonl, csv or snapshot (default: csv for *.csv files, jsonl otherwise)
+  --interval <SECS>    Seconds between daemon samples (default: 5)
+  --remote <HOST>      Monitor HOST ([user@]host) over ssh; needs rustmonitor installed there
+  --remote-helper      Stream snapshots to stdout for --remote (run by ssh, not by hand)
+  --agent <ADDR>       Run headless, streaming snapshots to --connect clients on ADDR
+  --connect <ADDR>     Monitor the machine running --agent at ADDR (host:port)
+  --api <ADDR>         Serve the current snapshot as JSON at http://ADDR/api/v1/snapshot
+  --history <N>        Samples kept per graph (default: 3600)
+  --show-identifiers   Show serial numbers and asset tags on the System tab
+  --summary            Print a session summary (duration, peaks, alerts) on quit
+  --tmux-status        Print a one-line CPU/MEM/NET summary for tmux and exit
+  -h, --help           Print this help
+  -V, --version        Print version";
+
+/// Daemon log line format.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    /// One JSON object per line
+    JsonLines,
+    Csv,
+    /// A full compact snapshot per line, as read by `--remote`
+    Snapshot,
+}
+
+impl LogFormat {
+    /// CSV for `.csv` files, JSON Lines for anything else.
+    pub fn for_path(path: Option<&str>) -> Self {
+        match path {
+            Some(p) if p.to_ascii_lowercase().ends_with(".csv") => LogFormat::Csv,
+            _ => LogFormat::JsonLines,
+        }
+    }
+}
+
+pub struct Cli {
+    pub show_identifiers: bool,
+    pub exit_summary: bool,
+    /// Overrides `api_listen`
+    pub api_listen: Option<String>,
+    /// Headless: log samples instead of drawing
+    pub daemon: bool,
+    pub log_path: Option<String>,
+    /// Inferred from `log_path` when not given
+    pub log_format: Option<LogFormat>,
+    /// Seconds between daemon samples; 0 samples every tick
+    pub log_interval: u64,
+    /// `[user@]host` to monitor over ssh
+    pub remote: Option<String>,
+    /// Daemon mode streaming snapshots for a `--remote` on the other end
+    pub remote_helper: bool,
+    /// Daemon mode serving snapshots to `--connect` clients on this address
+    pub agent_listen: Option<String>,
+    /// `host:port` of an agent to monitor
+    pub connect: Option<String>,
+    /// Overrides `history_len`
+    pub history_len: Option<usize>,
+}
+
+impl Default for Cli {
+    fn default() -> Self {
+        Self {
+            show_identifiers: false,
+            exit_summary: false,
+            api_listen: None,
+            daemon: false,
+            log_path: None,
+            log_format: None,
+            log_interval: 5,
+            remote: None,
+            remote_helper: false,
+            agent_listen: None,
+            connect: None,
+            history_len: None,
+        }
+    }
+}
+
+/// What `main` should do after parsing the arguments.
+pub enum Action {
+    Run(Cli),
+    /// Print to stdout and exit successfully (help, version)
+    Exit(String),
+    /// Print to stderr and exit with a usage error
+    Error(String),
+    /// Write a JSON snapshot to the file, or stdout
+    Snapshot(Option<String>),
+    /// Compare two snapshot files
+    Diff(String, String),
+    /// Print a one-line summary for a tmux status bar
+    TmuxStatus,
+}
+
+impl Cli {
+    pub fn parse() -> Action {
+        Self::parse_from(env::args().skip(1))
+    }
+
+    pub fn parse_from<I: IntoIterator<Item = String>>(args: I) -> Action {
+        let mut args = args.into_iter().peekable();
+        match args.peek().map(String::as_str) {
+            Some("snapshot") => {
+                args.next();
+                let path = args.next();
+                if let Some(extra) = args.next() {
+                    return Action::Error(format!("Unexpected argument: {extra}\n\n{USAGE}"));
+                }
+                return Action::Snapshot(path);
+            }
+            Some("diff") => {
+                args.next();
+                return match (args.next(), args.next(), args.next()) {
+                    (Some(a), Some(b), None) => Action::Diff(a, b),
+                    _ => Action::Error(format!("diff takes exactly two snapshot files\n\n{USAGE}")),
+                };
+            }
+            _ => {}
+        }
+
+        let mut cli = Cli::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--api" => match args.next() {
+                    Some(addr) => cli.api_listen = Some(addr),
+                    None => return Action::Error(format!("--api needs an address\n\n{USAGE}")),
+                },
+                "--daemon" => cli.daemon = true,
+                "--log" => match args.next() {
+                    Some(path) => cli.log_path = Some(path),
+                    None => return Action::Error(format!("--log needs a file\n\n{USAGE}")),
+                },
+                "--log-format" => {
+                    cli.log_format = match args.next().as_deref() {
+                        Some("jsonl" | "json") => Some(LogFormat::JsonLines),
+                        Some("csv") => Some(LogFormat::Csv),
+                        Some("snapshot") => Some(LogFormat::Snapshot),
+                        _ => {
+                            return Action::Error(format!(
+                                "--log-format takes jsonl, csv or snapshot\n\n{USAGE}"
+                            ));
+                        }
+                    }
+                }
+                "--interval" => match args.next().and_then(|s| s.parse::<u64>().ok()) {
+                    Some(secs) if secs > 0 => cli.log_interval = secs,
+                    _ => {
+                        return Action::Error(format!(
+                            "--interval takes a positive number of seconds\n\n{USAGE}"
+                        ));
+                    }
+                },
+                "--remote" => match args.next() {
+                    Some(host) => cli.remote = Some(host),
+                    None => return Action::Error(format!("--remote needs a host\n\n{USAGE}")),
+                },
+                "--remote-helper" => {
+                    cli.remote_helper = true;
+                    cli.daemon = true;
+                    cli.log_format = Some(LogFormat::Snapshot);
+                    cli.log_interval = 0;
+                }
+                "--agent" => match args.next() {
+                    Some(addr) => {
+                        cli.agent_listen = Some(addr);
+                        cli.daemon = true;
+                    }
+                    None => return Action::Error(format!("--agent needs an address\n\n{USAGE}")),
+                },
+                "--connect" => match args.next() {
+                    Some(addr) => cli.connect = Some(addr),
+                    None => return Action::Error(format!("--connect needs an address\n\n{USAGE}")),
+                },
+                "--history" => match args.next().and_then(|s| s.parse::<usize>().ok()) {
+                    Some(len) if (MIN_HISTORY_LEN..=MAX_HISTORY_LEN).contains(&len) => {
+                        cli.history_len = Some(len)
+                    }
+                    _ => {
+                        return Action::Error(format!(
+                            "--history takes {MIN_HISTORY_LEN} to {MAX_HISTORY_LEN} samples\n\n{USAGE}"
+                        ));
+                    }
+                },
+                "--show-identifiers" => cli.show_identifiers = true,
+                "--summary" => cli.exit_summary = true,
+                "--tmux-status" => return Action::TmuxStatus,
+                "-h" | "--help" => return Action::Exit(USAGE.to_string()),
+                "-V" | "--version" => return Action::Exit(version()),
+                other => return Action::Error(format!("Unknown argument: {other}\n\n{USAGE}")),
+            }
+        }
+        if cli.log_path.is_some() && !cli.daemon {
+            return Action::Error(format!("--log only applies with --daemon\n\n{USAGE}"));
+        }
+        if cli.remote.is_some() && cli.connect.is_some() {
+            return Action::Error(format!("--remote and --connect are alternatives\n\n{USAGE}"));
+        }
+        if (cli.remote.is_some() || cli.connect.is_some()) && cli.daemon {
+            let flag = if cli.remote.is_some() { "--remote" } else { "--connect" };
+            return Action::Error(format!("{flag} can't be combined with --daemon or --agent\n\n{USAGE}"));
+        }
+        Action::Run(cli)
+    }
+}
+
+/// Version plus the optional backends compiled in, so a portable build can
+/// be told apart from a regular one.
+fn version() -> String {
+    let nvml = if cfg!(feature = "nvml") { "+nvml" } else { "-nvml" };
+    let net = if cfg!(feature = "net-accounting") {
+        "+net-accounting"
+    } else {
+        "-net-accounting"
+    };
+    format!("rustmonitor {} ({nvml} {net})", env!("CARGO_PKG_VERSION"))
+}
@@ -0,0 +1,85 @@
+//! Copying text to the system clipboard without a clipboard library.
+//!
+//! Locally the platform's clipboard command takes the text (`pbcopy`,
+//! `wl-copy`, `xclip`, `xsel`, `clip.exe`). Over SSH, or when none of
+//! those is installed, an OSC 52 sequence asks the terminal to set its
+//! clipboard instead, which works wherever the terminal allows it
+//! (most do; tmux needs `set-clipboard on` or passthrough).
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Copies `text`, returning how it got there for the status bar.
+pub fn copy(text: &str) -> io::Result<&'static str> {
+    let remote = ["SSH_CONNECTION", "SSH_TTY"].iter().any(|v| std::env::var_os(v).is_some());
+    if !remote {
+        for (program, args) in commands() {
+            if pipe_to(program, args, text) {
+                return Ok(program);
+            }
+        }
+    }
+    osc52(text)?;
+    Ok("terminal")
+}
+
+fn commands() -> &'static [(&'static str, &'static [&'static str])] {
+    if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(windows) {
+        &[("clip.exe", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    }
+}
+
+/// Whether `program` ran and took `text` on stdin.
+fn pipe_to(program: &str, args: &[&str], text: &str) -> bool {
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return false;
+    };
+    let written = child.stdin.take().is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    // wl-copy and xclip fork to keep serving the selection, so this
+    // returns once the text is handed over
+    child.wait().is_ok_and(|status| status.success()) && written
+}
+
+/// Writes the OSC 52 set-clipboard sequence straight to the terminal,
+/// wrapped in a DCS passthrough under tmux like the alert sequences.
+fn osc52(text: &str) -> io::Result<()> {
+    let body = format!("52;c;{}", base64(text.as_bytes()));
+    let sequence = if std::env::var_os("TMUX").is_some() {
+        format!("\x1bPtmux;\x1b\x1b]{body}\x07\x1b\\")
+    } else {
+        format!("\x1b]{body}\x07")
+    };
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
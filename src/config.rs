@@ -0,0 +1,526 @@
+//! User configuration loaded from `rustmonitor/config` in the platform
+//! config directory (`$XDG_CONFIG_HOME`, `~/.config` or `%APPDATA%`).
+//!
+//! The format is deliberately minimal — one `key = value` per line, lines
+//! starting with `#` are comments — so it can be parsed without extra
+//! dependencies. Unknown keys and invalid values are ignored.
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::alerts::AlertSound;
+use crate::app::{self, Graph, ProcessColumn, REFRESH_RATES};
+use crate::fsinfo;
+use crate::hooks::Hooks;
+use crate::keymap::Profile;
+use crate::metrics::MetricsTarget;
+use crate::profiler;
+use crate::sysctl;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    /// Colors unless `NO_COLOR` is set or the terminal is `dumb`
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Charset {
+    /// Unicode unless the terminal is known to lack block/braille glyphs
+    Auto,
+    Unicode,
+    Ascii,
+}
+
+/// How many colors the terminal can show; themes are written in 24-bit
+/// RGB and mapped down to the nearest palette entries.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColorDepth {
+    /// Guessed from `COLORTERM`, `TERM` and `TERM_PROGRAM`
+    Auto,
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+pub struct Config {
+    /// Theme at startup, built-in or from a theme file, by name
+    pub theme: Option<String>,
+    pub color: ColorMode,
+    pub charset: Charset,
+    pub color_depth: ColorDepth,
+    /// sysctl keys shown on the System tab; empty hides the section
+    pub kernel_params: Vec<String>,
+    /// RSS growth, in MB per minute, at which a steadily growing process
+    /// is reported as a possible leak
+    pub leak_slope: f64,
+    /// How long `p` profiles the selected process, in seconds
+    pub profile_secs: u64,
+    /// Hide pseudo filesystems and duplicate mounts at startup (`f` toggles)
+    pub disk_filter: bool,
+    /// Filesystem types the disk filter hides
+    pub hidden_filesystems: Vec<String>,
+    /// Load NVML from this path instead of searching the usual locations
+    pub nvml_path: Option<String>,
+    /// Ring the bell and set the terminal's error progress state on
+    /// critical alerts
+    pub attention: bool,
+    /// Bell, silence, or a sound file for newly fired critical alerts
+    pub alert_sound: AlertSound,
+    /// Show host names for remote addresses on the Connections tab
+    pub reverse_dns: bool,
+    /// Start in large UI mode: taller rows, bigger gauges, fewer columns
+    pub large_ui: bool,
+    /// MaxMind DB files for the Connections tab's Origin column
+    pub geoip: Vec<String>,
+    /// Touch this file every tick so a supervisor can detect hangs
+    pub watchdog_file: Option<String>,
+    /// Serve the JSON snapshot over HTTP on this address
+    pub api_listen: Option<String>,
+    /// Host for the Network tab's latency chart; `None` pings the default
+    /// gateway
+    pub ping_target: Option<String>,
+    /// `ping_target = off` turns the latency monitor off
+    pub ping_enabled: bool,
+    /// Seconds between pings
+    pub ping_interval: u64,
+    /// Print a session summary to stdout on quit
+    pub exit_summary: bool,
+    /// Show network rates in bits per second (`b` toggles)
+    pub rate_bits: bool,
+    /// Flag interfaces using at least this % of their capacity; `None`
+    /// turns the check off
+    pub bandwidth_threshold: Option<f64>,
+    /// Capacity in Mbit/s by interface, for links that don't report a
+    /// speed (Wi-Fi, tunnels) or sit behind a slower ISP plan
+    pub bandwidth_limits: HashMap<String, u64>,
+    /// Also warn in the status bar when an interface crosses the threshold
+    pub bandwidth_warning: bool,
+    /// Shell commands run on alerts, bandwidth warnings and kills
+    pub hooks: Hooks,
+    /// rustmonitor on the far side of `--remote`, if not on its PATH
+    pub remote_command: String,
+    /// StatsD, Graphite or InfluxDB endpoint to push samples to
+    pub metrics_output: Option<MetricsTarget>,
+    /// InfluxDB API token
+    pub metrics_token: Option<String>,
+    /// Metric name prefix; `{host}` is replaced with the hostname
+    pub metrics_prefix: String,
+    /// Seconds between pushes
+    pub metrics_interval: u64,
+    /// Time between refreshes
+    pub refresh_rate: Duration,
+    /// Samples kept per graph
+    pub history_len: usize,
+    /// Graphs drawn as line charts with axes rather than sparklines
+    pub line_charts: HashSet<Graph>,
+    /// Start with full command lines in the Name column (`c` toggles)
+    pub show_cmdline: bool,
+    /// Processes table columns shown at startup, in order
+    pub process_columns: Vec<ProcessColumn>,
+    /// Copy fired alerts to syslog / journald / the Windows event log
+    pub syslog: bool,
+    /// Save the graph histories on quit and load them at startup
+    pub persist_history: bool,
+    /// Key bindings the `key.<action>` lines start from
+    pub keymap: Profile,
+    /// `key.<action>` lines, action name and keys
+    pub key_bindings: Vec<(String, String)>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: None,
+            color: ColorMode::Auto,
+            charset: Charset::Auto,
+            color_depth: ColorDepth::Auto,
+            kernel_params: sysctl::default_names(),
+            leak_slope: 1.0,
+            profile_secs: profiler::DEFAULT_PROFILE_SECS,
+            disk_filter: true,
+            hidden_filesystems: fsinfo::DEFAULT_HIDDEN_FILESYSTEMS
+                .iter()
+                .map(|fs| fs.to_string())
+                .collect(),
+            nvml_path: None,
+            attention: true,
+            alert_sound: AlertSound::Bell,
+            reverse_dns: true,
+            large_ui: false,
+            geoip: Vec::new(),
+            watchdog_file: None,
+            api_listen: None,
+            ping_target: None,
+            ping_enabled: true,
+            ping_interval: 2,
+            exit_summary: false,
+            rate_bits: false,
+            bandwidth_threshold: None,
+            bandwidth_limits: HashMap::new(),
+            bandwidth_warning: false,
+            hooks: Hooks::default(),
+            remote_command: "rustmonitor".to_string(),
+            metrics_output: None,
+            metrics_token: None,
+            metrics_prefix: "rustmonitor.{host}".to_string(),
+            metrics_interval: 10,
+            syslog: false,
+            refresh_rate: Duration::from_millis(500),
+            history_len: app::DEFAULT_HISTORY_LEN,
+            line_charts: HashSet::new(),
+            show_cmdline: false,
+            process_columns: ProcessColumn::defaults(),
+            persist_history: false,
+            keymap: Profile::Default,
+            key_bindings: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let mut config = Self::default();
+        if let Some(text) = config_path().and_then(|p| fs::read_to_string(p).ok()) {
+            for (key, value) in parse_pairs(&text) {
+                config.apply(&key, &value);
+            }
+        }
+        config
+    }
+
+    fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            "theme" => self.theme = Some(value.to_string()),
+            "color" => {
+                self.color = match value {
+                    "always" => ColorMode::Always,
+                    "never" => ColorMode::Never,
+                    "auto" => ColorMode::Auto,
+                    _ => self.color,
+                }
+            }
+            "charset" => {
+                self.charset = match value {
+                    "unicode" => Charset::Unicode,
+                    "ascii" => Charset::Ascii,
+                    "auto" => Charset::Auto,
+                    _ => self.charset,
+                }
+            }
+            "color_depth" => {
+                self.color_depth = match value {
+                    "truecolor" | "24bit" => ColorDepth::TrueColor,
+                    "256" => ColorDepth::Ansi256,
+                    "16" => ColorDepth::Ansi16,
+                    "auto" => ColorDepth::Auto,
+                    _ => self.color_depth,
+                }
+            }
+            "kernel_params" => self.kernel_params = parse_list(value),
+            "disk_filter" => {
+                if let Some(b) = parse_bool(value) {
+                    self.disk_filter = b;
+                }
+            }
+            "attention" => {
+                if let Some(b) = parse_bool(value) {
+                    self.attention = b;
+                }
+            }
+            "alert_sound" => {
+                self.alert_sound = match value {
+                    "bell" | "" => AlertSound::Bell,
+                    "off" | "none" => AlertSound::Off,
+                    path => AlertSound::File(path.to_string()),
+                }
+            }
+            "reverse_dns" => {
+                if let Some(b) = parse_bool(value) {
+                    self.reverse_dns = b;
+                }
+            }
+            "large_ui" => {
+                if let Some(b) = parse_bool(value) {
+                    self.large_ui = b;
+                }
+            }
+            "exit_summary" => {
+                if let Some(b) = parse_bool(value) {
+                    self.exit_summary = b;
+                }
+            }
+            "refresh_rate" => {
+                let rates = REFRESH_RATES;
+                // `500`, `500ms`, `2s` or `0.5s`
+                let ms = match value.strip_suffix("ms") {
+                    Some(ms) => ms.trim().parse::<u64>().ok(),
+                    None => match value.strip_suffix('s') {
+                        Some(secs) => secs.trim().parse::<f64>().ok().map(|s| (s * 1000.0).round() as u64),
+                        None => value.parse::<u64>().ok(),
+                    },
+                };
+                if let Some(ms) = ms
+                    && (rates[0]..=rates[rates.len() - 1]).contains(&Duration::from_millis(ms))
+                {
+                    self.refresh_rate = Duration::from_millis(ms);
+                }
+            }
+            "history_len" => {
+                if let Ok(len) = value.parse::<usize>()
+                    && (app::MIN_HISTORY_LEN..=app::MAX_HISTORY_LEN).contains(&len)
+                {
+                    self.history_len = len;
+                }
+            }
+            "line_charts" => {
+                self.line_charts = parse_list(value)
+                    .iter()
+                    .filter_map(|name| Graph::parse(name))
+                    .collect()
+            }
+            "show_cmdline" => {
+                if let Some(b) = parse_bool(value) {
+                    self.show_cmdline = b;
+                }
+            }
+            "process_columns" => {
+                let mut columns: Vec<_> = parse_list(value)
+                    .iter()
+                    .filter_map(|name| ProcessColumn::parse(name))
+                    .collect();
+                let mut seen = HashSet::new();
+                columns.retain(|&c| seen.insert(c.name()));
+                if !columns.contains(&ProcessColumn::Name) {
+                    columns.insert(columns.len().min(1), ProcessColumn::Name);
+                }
+                self.process_columns = columns;
+            }
+            "persist_history" => {
+                if let Some(b) = parse_bool(value) {
+                    self.persist_history = b;
+                }
+            }
+            "syslog" => {
+                if let Some(b) = parse_bool(value) {
+                    self.syslog = b;
+                }
+            }
+            "network_units" => {
+                self.rate_bits = match value {
+                    "bits" => true,
+                    "bytes" => false,
+                    _ => self.rate_bits,
+                }
+            }
+            "bandwidth_threshold" => match value {
+                "off" | "false" => self.bandwidth_threshold = None,
+                _ => {
+                    if let Ok(pct) = value.trim_end_matches('%').parse::<f64>()
+                        && pct > 0.0
+                    {
+                        self.bandwidth_threshold = Some(pct);
+                    }
+                }
+            },
+            "bandwidth_limits" => {
+                self.bandwidth_limits = parse_list(value)
+                    .iter()
+                    .filter_map(|entry| {
+                        let (name, rate) = entry.split_once(':')?;
+                        Some((name.trim().to_string(), parse_mbps(rate.trim())?))
+                    })
+                    .collect()
+            }
+            "bandwidth_warning" => {
+                if let Some(b) = parse_bool(value) {
+                    self.bandwidth_warning = b;
+                }
+            }
+            "hidden_filesystems" => self.hidden_filesystems = parse_list(value),
+            "geoip" => self.geoip = parse_list(value),
+            "ping_target" => match value {
+                "off" | "false" => self.ping_enabled = false,
+                "" | "gateway" => self.ping_target = None,
+                host => self.ping_target = Some(host.to_string()),
+            },
+            "ping_interval" => {
+                if let Ok(secs) = value.parse::<u64>()
+                    && secs > 0
+                {
+                    self.ping_interval = secs;
+                }
+            }
+            "metrics_output" => match value {
+                "" | "off" => self.metrics_output = None,
+                _ => {
+                    if let Some(target) = MetricsTarget::parse(value) {
+                        self.metrics_output = Some(target);
+                    }
+                }
+            },
+            "metrics_token" => self.metrics_token = Some(value.to_string()).filter(|t| !t.is_empty()),
+            "metrics_prefix" if !value.is_empty() => self.metrics_prefix = value.to_string(),
+            "metrics_interval" => {
+                if let Ok(secs) = value.parse::<u64>()
+                    && secs > 0
+                {
+                    self.metrics_interval = secs;
+                }
+            }
+            "watchdog_file" => {
+                self.watchdog_file = Some(value.to_string()).filter(|p| !p.is_empty())
+            }
+            "api_listen" => self.api_listen = Some(value.to_string()).filter(|a| !a.is_empty()),
+            "on_alert" => self.hooks.on_alert = Some(value.to_string()),
+            "on_bandwidth" => self.hooks.on_bandwidth = Some(value.to_string()),
+            "on_process_kill" => self.hooks.on_process_kill = Some(value.to_string()),
+            "remote_command" if !value.is_empty() => self.remote_command = value.to_string(),
+            "nvml_path" => self.nvml_path = Some(value.to_string()).filter(|p| !p.is_empty()),
+            "leak_slope" => {
+                if let Ok(slope) = value.parse::<f64>()
+                    && slope > 0.0
+                {
+                    self.leak_slope = slope;
+                }
+            }
+            "profile_secs" => {
+                if let Ok(secs) = value.trim_end_matches('s').parse::<u64>()
+                    && (1..=profiler::MAX_PROFILE_SECS).contains(&secs)
+                {
+                    self.profile_secs = secs;
+                }
+            }
+            "keymap" => self.keymap = Profile::parse(value).unwrap_or(self.keymap),
+            _ if key.starts_with("key.") => {
+                self.key_bindings.push((key[4..].to_string(), value.to_string()))
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether output should be rendered without any ANSI colors.
+    pub fn no_color(&self) -> bool {
+        match self.color {
+            ColorMode::Always => false,
+            ColorMode::Never => true,
+            ColorMode::Auto => {
+                env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+                    || env::var("TERM").is_ok_and(|t| t == "dumb")
+            }
+        }
+    }
+
+    /// Whether to avoid block and braille glyphs in favour of plain ASCII.
+    pub fn ascii_only(&self) -> bool {
+        match self.charset {
+            Charset::Unicode => false,
+            Charset::Ascii => true,
+            Charset::Auto => terminal_lacks_glyphs(),
+        }
+    }
+
+    /// The palette themes are drawn with; never `Auto`.
+    pub fn color_depth(&self) -> ColorDepth {
+        match self.color_depth {
+            ColorDepth::Auto => detect_color_depth(),
+            depth => depth,
+        }
+    }
+}
+
+/// Truecolor when the terminal advertises it through `COLORTERM` or is one
+/// known to support it, 256 colors for `*-256color` terminals and the macOS
+/// Terminal, otherwise the 16 ANSI colors every terminal has.
+fn detect_color_depth() -> ColorDepth {
+    if env::var("COLORTERM").is_ok_and(|c| matches!(c.as_str(), "truecolor" | "24bit"))
+        || env::var_os("WT_SESSION").is_some()
+    {
+        return ColorDepth::TrueColor;
+    }
+    let term = env::var("TERM").unwrap_or_default();
+    match env::var("TERM_PROGRAM").as_deref() {
+        Ok("iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper") => ColorDepth::TrueColor,
+        Ok("Apple_Terminal") => ColorDepth::Ansi256,
+        _ if term.ends_with("-direct") || term == "xterm-kitty" => ColorDepth::TrueColor,
+        _ if term.contains("256color") => ColorDepth::Ansi256,
+        _ => ColorDepth::Ansi16,
+    }
+}
+
+/// Terminals whose fonts commonly lack the block elements used by gauges
+/// and sparklines: the Linux VT, dumb terminals and legacy Windows conhost
+/// (Windows Terminal sets `WT_SESSION`, ConEmu sets `ConEmuANSI`).
+fn terminal_lacks_glyphs() -> bool {
+    if let Ok(term) = env::var("TERM")
+        && matches!(term.as_str(), "linux" | "dumb" | "vt100" | "vt220")
+    {
+        return true;
+    }
+    cfg!(windows)
+        && env::var_os("WT_SESSION").is_none()
+        && env::var_os("TERM_PROGRAM").is_none()
+        && env::var_os("ConEmuANSI").is_none()
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("rustmonitor"));
+    }
+    if cfg!(windows) {
+        return env::var_os("APPDATA").map(|d| PathBuf::from(d).join("rustmonitor"));
+    }
+    env::var_os("HOME").map(|h| PathBuf::from(h).join(".config").join("rustmonitor"))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("config"))
+}
+
+/// `true` / `on` or `false` / `off`.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "on" => Some(true),
+        "false" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Comma-separated values with surrounding whitespace and empties dropped.
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Mbit/s from `300`, `300M` or `2.5G`.
+fn parse_mbps(value: &str) -> Option<u64> {
+    let (number, scale) = match value.to_ascii_uppercase().strip_suffix('G') {
+        Some(n) => (n.to_string(), 1000.0),
+        None => (value.trim_end_matches(['M', 'm']).to_string(), 1.0),
+    };
+    let mbps = (number.trim().parse::<f64>().ok()? * scale).round() as u64;
+    (mbps > 0).then_some(mbps)
+}
+
+/// Parse `key = value` lines, skipping blanks and `#` comments.
+pub fn parse_pairs(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| {
+            // One surrounding pair of quotes, so a value can keep its own
+            let value = v.trim();
+            let value = value.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(value);
+            (k.trim().to_lowercase(), value.to_string())
+        })
+        .collect()
+}
@@ -0,0 +1,289 @@
+//! TCP and UDP sockets with their owning process, for the Connections tab.
+//!
+//! Linux reads `/proc/net/{tcp,tcp6,udp,udp6}` and matches socket inodes
+//! against `/proc/<pid>/fd`; macOS parses `lsof -nP -i` and Windows
+//! `netstat -ano`. Sockets of other users' processes only get an owner when
+//! running as root (or as Administrator on Windows), otherwise the PID is
+//! left empty rather than guessed.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+#[cfg(not(target_os = "linux"))]
+use std::process::Command;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+pub struct Connection {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    /// `None` for listening and unconnected sockets
+    pub remote: Option<SocketAddr>,
+    /// TCP state such as "ESTABLISHED" or "LISTEN"; empty for UDP
+    pub state: String,
+    pub pid: Option<u32>,
+    /// Filled in from the process table by the caller
+    pub process: String,
+}
+
+impl Connection {
+    /// "tcp", "tcp6", "udp" or "udp6".
+    pub fn proto_label(&self) -> &'static str {
+        match (self.protocol, self.local.is_ipv6()) {
+            (Protocol::Tcp, false) => "tcp",
+            (Protocol::Tcp, true) => "tcp6",
+            (Protocol::Udp, false) => "udp",
+            (Protocol::Udp, true) => "udp6",
+        }
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.state == "LISTEN" || (self.protocol == Protocol::Udp && self.remote.is_none())
+    }
+
+    /// Bound to every interface (`0.0.0.0` / `::`) rather than one address.
+    pub fn is_exposed(&self) -> bool {
+        self.local.ip().is_unspecified()
+    }
+}
+
+/// `*:*` for a missing address, `*:port` for a wildcard one.
+pub fn format_addr(addr: Option<&SocketAddr>) -> String {
+    match addr {
+        None => "*:*".into(),
+        Some(a) if a.ip().is_unspecified() => format!("*:{}", a.port()),
+        Some(a) => a.to_string(),
+    }
+}
+
+/// Treat `0.0.0.0:0` and `[::]:0` peers as "no peer".
+fn peer(addr: SocketAddr) -> Option<SocketAddr> {
+    (!(addr.ip().is_unspecified() && addr.port() == 0)).then_some(addr)
+}
+
+#[cfg(target_os = "linux")]
+pub fn query() -> Vec<Connection> {
+    use std::collections::HashMap;
+
+    let owners = socket_owners();
+    let mut connections = Vec::new();
+    for (file, protocol) in [
+        ("tcp", Protocol::Tcp),
+        ("tcp6", Protocol::Tcp),
+        ("udp", Protocol::Udp),
+        ("udp6", Protocol::Udp),
+    ] {
+        let Ok(text) = std::fs::read_to_string(format!("/proc/net/{file}")) else {
+            continue;
+        };
+        for line in text.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                continue;
+            }
+            let (Some(local), Some(remote)) = (parse_proc_addr(fields[1]), parse_proc_addr(fields[2]))
+            else {
+                continue;
+            };
+            let state = match protocol {
+                Protocol::Tcp => tcp_state(fields[3]).to_string(),
+                Protocol::Udp => String::new(),
+            };
+            let inode: u64 = fields[9].parse().unwrap_or(0);
+            connections.push(Connection {
+                protocol,
+                local,
+                remote: peer(remote),
+                state,
+                pid: owners.get(&inode).copied(),
+                process: String::new(),
+            });
+        }
+    }
+    return connections;
+
+    /// Socket inode → PID, from the `socket:[inode]` links in `/proc/<pid>/fd`.
+    fn socket_owners() -> HashMap<u64, u32> {
+        let mut owners = HashMap::new();
+        let Ok(procs) = std::fs::read_dir("/proc") else {
+            return owners;
+        };
+        for entry in procs.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+                continue;
+            };
+            let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                let Ok(target) = std::fs::read_link(fd.path()) else {
+                    continue;
+                };
+                if let Some(inode) = target
+                    .to_str()
+                    .and_then(|t| t.strip_prefix("socket:["))
+                    .and_then(|t| t.strip_suffix(']'))
+                    .and_then(|t| t.parse().ok())
+                {
+                    owners.entry(inode).or_insert(pid);
+                }
+            }
+        }
+        owners
+    }
+}
+
+/// `0100007F:0035` (IPv4) or 32 hex digits (IPv6), each 32-bit word in
+/// host byte order, followed by the port in hex.
+#[cfg(target_os = "linux")]
+fn parse_proc_addr(field: &str) -> Option<SocketAddr> {
+    let (ip, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let words: Vec<u32> = (0..ip.len() / 8)
+        .map(|i| u32::from_str_radix(&ip[i * 8..i * 8 + 8], 16).ok())
+        .collect::<Option<_>>()?;
+    let ip = match words.as_slice() {
+        [w] => IpAddr::V4(Ipv4Addr::from(w.to_ne_bytes())),
+        [a, b, c, d] => {
+            let mut bytes = [0u8; 16];
+            for (chunk, word) in bytes.chunks_mut(4).zip([a, b, c, d]) {
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            let v6 = std::net::Ipv6Addr::from(bytes);
+            // Dual-stack sockets report IPv4 peers as ::ffff:a.b.c.d
+            v6.to_ipv4_mapped().map_or(IpAddr::V6(v6), IpAddr::V4)
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+#[cfg(target_os = "linux")]
+fn tcp_state(hex: &str) -> &'static str {
+    match hex {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Parse `lsof -F` output: a `p<pid>` line per process, then one `f<fd>`
+/// line per socket followed by its `P` protocol, `n` address and `T` state
+/// fields.
+#[cfg(target_os = "macos")]
+pub fn query() -> Vec<Connection> {
+    let Ok(out) = Command::new("lsof").args(["-nP", "-i", "-F", "pfPnT"]).output() else {
+        return Vec::new();
+    };
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut connections: Vec<Connection> = Vec::new();
+    let mut pid = None;
+    let mut current: Option<Connection> = None;
+    for line in text.lines() {
+        let (tag, value) = line.split_at(line.len().min(1));
+        match tag {
+            "p" => pid = value.parse().ok(),
+            "f" => connections.extend(current.take()),
+            "P" => {
+                let protocol = match value {
+                    "TCP" => Protocol::Tcp,
+                    "UDP" => Protocol::Udp,
+                    _ => continue,
+                };
+                current = Some(Connection {
+                    protocol,
+                    local: SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+                    remote: None,
+                    state: String::new(),
+                    pid,
+                    process: String::new(),
+                });
+            }
+            "n" => {
+                if let Some(conn) = current.as_mut() {
+                    let (local, remote) = value.split_once("->").unwrap_or((value, ""));
+                    match parse_host_port(local) {
+                        Some(addr) => conn.local = addr,
+                        None => current = None,
+                    }
+                    if let Some(conn) = current.as_mut() {
+                        conn.remote = parse_host_port(remote).and_then(peer);
+                    }
+                }
+            }
+            "T" => {
+                if let (Some(conn), Some(state)) = (current.as_mut(), value.strip_prefix("ST=")) {
+                    conn.state = state.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+    connections.extend(current);
+    connections
+}
+
+/// Parse `netstat -ano`: `Proto Local Foreign [State] PID`, where UDP rows
+/// have no state column.
+#[cfg(windows)]
+pub fn query() -> Vec<Connection> {
+    let Ok(out) = Command::new("netstat").args(["-ano"]).output() else {
+        return Vec::new();
+    };
+    let text = String::from_utf8_lossy(&out.stdout);
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (protocol, state, pid) = match fields.as_slice() {
+                ["TCP", _, _, state, pid] => (Protocol::Tcp, *state, *pid),
+                ["UDP", _, _, pid] => (Protocol::Udp, "", *pid),
+                _ => return None,
+            };
+            let state = match state {
+                "LISTENING" => "LISTEN",
+                other => other,
+            };
+            Some(Connection {
+                protocol,
+                local: parse_host_port(fields[1])?,
+                remote: parse_host_port(fields[2]).and_then(peer),
+                state: state.to_string(),
+                pid: pid.parse().ok().filter(|&p| p != 0),
+                process: String::new(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub fn query() -> Vec<Connection> {
+    Vec::new()
+}
+
+/// `1.2.3.4:80`, `[::1]:631`, `*:22` or `*:*`; a `*` host is the IPv4
+/// wildcard and a `*` port is 0.
+#[cfg(not(target_os = "linux"))]
+fn parse_host_port(text: &str) -> Option<SocketAddr> {
+    let (host, port) = text.rsplit_once(':')?;
+    let port = if port == "*" { 0 } else { port.parse().ok()? };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    // Drop an IPv6 zone such as fe80::1%en0
+    let host = host.split('%').next().unwrap_or(host);
+    let ip = if host == "*" {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        host.parse().ok()?
+    };
+    Some(SocketAddr::new(ip, port))
+}
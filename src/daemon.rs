@@ -0,0 +1,99 @@
+//! `--daemon`: sample without a terminal and append one line per interval
+//! to a log, for collecting data on a server and analysing it later.
+//!
+//! The sampling is the TUI's own `App::tick`, so the numbers match what the
+//! dashboard shows. Each line is written and flushed on its own, so killing
+//! the daemon at any point leaves a complete file. `--api` still works
+//! alongside, which makes this a headless metrics endpoint as well.
+//! `--remote-helper` is this mode writing a snapshot every tick to stdout,
+//! for a `--remote` TUI reading it over ssh, and `--agent` is this mode
+//! sending them to `--connect` clients over TCP.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::agent::AgentServer;
+use crate::api::ApiServer;
+use crate::app::App;
+use crate::cli::{Cli, LogFormat};
+use crate::config::Config;
+use crate::hooks::Hooks;
+use crate::sample::{CSV_HEADER, Sample};
+use crate::snapshot::Snapshot;
+
+pub fn run(cli: &Cli) -> io::Result<()> {
+    let mut config = Config::load();
+    if cli.remote_helper {
+        // The TUI on the other end serves the API, runs hooks, pushes
+        // metrics and logs alerts itself
+        config.api_listen = None;
+        config.hooks = Hooks::default();
+        config.metrics_output = None;
+        config.syslog = false;
+    }
+    let api_listen = cli.api_listen.clone().or_else(|| config.api_listen.clone());
+    let api = match api_listen {
+        Some(addr) => Some(ApiServer::start(&addr).map_err(|e| {
+            io::Error::new(e.kind(), format!("can't listen on {addr}: {e}"))
+        })?),
+        None => None,
+    };
+    let agent = match &cli.agent_listen {
+        Some(addr) => Some(AgentServer::start(addr).map_err(|e| {
+            io::Error::new(e.kind(), format!("can't listen on {addr}: {e}"))
+        })?),
+        None => None,
+    };
+    let format = cli.log_format.unwrap_or_else(|| LogFormat::for_path(cli.log_path.as_deref()));
+    // An agent only logs when asked to
+    let mut out: Option<Box<dyn Write>> = match &cli.log_path {
+        Some(path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            let empty = file.metadata()?.len() == 0;
+            let mut file: Box<dyn Write> = Box::new(file);
+            if format == LogFormat::Csv && empty {
+                writeln!(file, "{CSV_HEADER}")?;
+            }
+            Some(file)
+        }
+        None if agent.is_some() => None,
+        None => {
+            let mut stdout: Box<dyn Write> = Box::new(io::stdout());
+            if format == LogFormat::Csv {
+                writeln!(stdout, "{CSV_HEADER}")?;
+            }
+            Some(stdout)
+        }
+    };
+
+    let mut app = App::new(config, cli);
+    let interval = Duration::from_secs(cli.log_interval);
+    // CPU usage needs two refreshes, so the first line comes after one tick
+    let mut last_sample: Option<Instant> = None;
+    loop {
+        std::thread::sleep(app.tick_rate);
+        app.poll_tasks();
+        app.tick();
+        if let Some(api) = &api {
+            api.publish(Snapshot::capture(&app).to_json().to_pretty());
+        }
+        if let Some(agent) = &agent
+            && agent.has_clients()
+        {
+            agent.broadcast(&Snapshot::capture(&app).to_json().to_compact());
+        }
+        if let Some(out) = &mut out
+            && last_sample.is_none_or(|at| at.elapsed() >= interval)
+        {
+            let line = match format {
+                LogFormat::JsonLines => Sample::capture(&app).to_json_line(),
+                LogFormat::Csv => Sample::capture(&app).to_csv_line(),
+                LogFormat::Snapshot => Snapshot::capture(&app).to_json().to_compact(),
+            };
+            writeln!(out, "{line}")?;
+            out.flush()?;
+            last_sample = Some(Instant::now());
+        }
+    }
+}
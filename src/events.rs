@@ -0,0 +1,76 @@
+//! Event history: alerts that fired, processes signalled or started, and
+//! errors such as an NVML library that failed to initialise. The status bar
+//! only shows each for three seconds; `E` opens the full list.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::app::format_duration;
+
+/// Oldest events are dropped past this
+pub const EVENT_CAPACITY: usize = 200;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EventLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl EventLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            EventLevel::Info => "info",
+            EventLevel::Warning => "warn",
+            EventLevel::Error => "error",
+        }
+    }
+}
+
+pub struct Event {
+    pub at: Instant,
+    pub level: EventLevel,
+    pub message: String,
+}
+
+impl Event {
+    /// `12s ago`, `3m ago`.
+    pub fn age(&self) -> String {
+        let secs = self.at.elapsed().as_secs();
+        if secs < 60 {
+            format!("{secs}s ago")
+        } else {
+            format!("{} ago", format_duration(secs))
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct EventLog {
+    entries: VecDeque<Event>,
+}
+
+impl EventLog {
+    pub fn push(&mut self, level: EventLevel, message: String) {
+        if self.entries.len() == EVENT_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Event {
+            at: Instant::now(),
+            level,
+            message,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn newest_first(&self) -> impl Iterator<Item = &Event> {
+        self.entries.iter().rev()
+    }
+}
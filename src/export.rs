@@ -0,0 +1,144 @@
+//! Files written from the TUI for attaching to bug reports or plotting
+//! elsewhere: the process table and the graph histories as CSV, named with
+//! the time they were taken so repeated exports don't overwrite each other.
+
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::{App, ProcessInfo};
+
+/// `rustmonitor-processes-20261015-143012.csv` in the working directory.
+pub fn timestamped_name(kind: &str, extension: &str) -> String {
+    format!("rustmonitor-{kind}-{}.{extension}", timestamp_slug(SystemTime::now()))
+}
+
+/// `YYYYMMDD-HHMMSS` in UTC.
+fn timestamp_slug(at: SystemTime) -> String {
+    let secs = at.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let rem = secs % 86400;
+    format!(
+        "{year:04}{month:02}{day:02}-{:02}{:02}{:02}",
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
+
+/// `2026-10-15T14:30:12.500Z`
+fn iso_timestamp(at: SystemTime) -> String {
+    let since_epoch = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let rem = secs % 86400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Days since 1970-01-01 to a proleptic Gregorian date (Howard Hinnant's
+/// `civil_from_days`).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Every column of the Processes table, in table order, with raw numbers
+/// (bytes, seconds) rather than the formatted values.
+pub fn processes_csv<'a>(processes: impl Iterator<Item = &'a ProcessInfo>) -> String {
+    let net = cfg!(feature = "net-accounting");
+    let mut out = String::from("pid,name,cpu_pct,memory_bytes,run_time_secs,disk_read_bytes,disk_write_bytes,disk_read_rate,disk_write_rate");
+    if net {
+        out.push_str(",net_rx_rate,net_tx_rate");
+    }
+    out.push_str(",status,user,threads,virtual_bytes,shared_bytes,swap_bytes\n");
+    for p in processes {
+        out.push_str(&format!(
+            "{},{},{:.1},{},{},{},{},{},{}",
+            p.pid,
+            csv_field(&p.name),
+            p.cpu,
+            p.memory,
+            p.run_time,
+            p.disk_read,
+            p.disk_write,
+            p.disk_read_rate,
+            p.disk_write_rate
+        ));
+        if net {
+            out.push_str(&format!(",{},{}", p.net_rx_rate, p.net_tx_rate));
+        }
+        let threads = p.threads.map(|t| t.to_string()).unwrap_or_default();
+        let shared = p.shared_memory.map(|b| b.to_string()).unwrap_or_default();
+        let swap = p.swap.map(|b| b.to_string()).unwrap_or_default();
+        out.push_str(&format!(
+            ",{},{},{threads},{},{shared},{swap}\n",
+            csv_field(&p.status),
+            csv_field(&p.user),
+            p.virtual_memory
+        ));
+    }
+    out
+}
+
+/// The CPU, memory, network and GPU graph histories, one row per sample,
+/// oldest first. Timestamps count back from now one refresh interval per
+/// sample, so they drift if the interval was changed during the session.
+/// Rows from before the first sample, still zero padding, are left out.
+pub fn history_csv(app: &App) -> String {
+    let mut out = String::from("time,cpu_pct,memory_pct,net_rx_bytes_per_sec,net_tx_bytes_per_sec");
+    for i in 0..app.gpu_util_history.len() {
+        out.push_str(&format!(",gpu{i}_util_pct"));
+    }
+    out.push('\n');
+
+    let len = app.global_cpu_history.len();
+    // Every series ends with the latest refresh; GPUs found later have
+    // zeros in front, like any fresh history
+    let at = |history: &VecDeque<f64>, row: usize| {
+        (row + history.len())
+            .checked_sub(len)
+            .and_then(|i| history.get(i))
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let now = SystemTime::now();
+    for row in len.saturating_sub(app.history_filled)..len {
+        let age = app.tick_rate * (len - 1 - row) as u32;
+        let bytes = |history| (at(history, row) * 1024.0) as u64;
+        out.push_str(&format!(
+            "{},{:.1},{:.1},{},{}",
+            iso_timestamp(now.checked_sub(age).unwrap_or(now)),
+            at(&app.global_cpu_history, row),
+            at(&app.mem_history, row),
+            bytes(&app.net_rx_history),
+            bytes(&app.net_tx_history)
+        ));
+        for history in &app.gpu_util_history {
+            out.push_str(&format!(",{:.0}", at(history, row)));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a field containing a delimiter, quote or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
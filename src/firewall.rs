@@ -0,0 +1,150 @@
+//! Firewall state summary from nftables (Linux) or pf (macOS).
+//!
+//! Both `nft` and `pfctl` usually need root to list rules. When the query
+//! fails the status is reported as unavailable rather than disabled, so a
+//! permission problem never shows up as a false "firewall off" warning.
+
+use std::time::SystemTime;
+
+pub struct FirewallStatus {
+    pub backend: &'static str,
+    /// `None` when the ruleset could not be read
+    pub enabled: Option<bool>,
+    pub rules: usize,
+    pub default_policy: Option<String>,
+    pub last_change: Option<SystemTime>,
+    fingerprint: u64,
+}
+
+impl FirewallStatus {
+    pub fn unavailable(backend: &'static str) -> Self {
+        Self {
+            backend,
+            enabled: None,
+            rules: 0,
+            default_policy: None,
+            last_change: None,
+            fingerprint: 0,
+        }
+    }
+
+    /// Replace with a fresh `query()` result, keeping `last_change` unless
+    /// the ruleset changed.
+    pub fn update(&mut self, mut next: FirewallStatus) {
+        if next.enabled.is_some() && self.enabled.is_some() && next.fingerprint == self.fingerprint
+        {
+            next.last_change = self.last_change;
+        } else if self.enabled.is_some() && next.enabled.is_some() {
+            next.last_change = Some(SystemTime::now());
+        }
+        *self = next;
+    }
+}
+
+pub fn query() -> FirewallStatus {
+    #[cfg(target_os = "linux")]
+    {
+        query_nft()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        query_pf()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        FirewallStatus::unavailable("none")
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn fingerprint(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn config_mtime(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(target_os = "linux")]
+fn query_nft() -> FirewallStatus {
+    use std::process::Command;
+
+    let output = match Command::new("nft").args(["list", "ruleset"]).output() {
+        Ok(out) if out.status.success() => out,
+        _ => return FirewallStatus::unavailable("nftables"),
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+
+    let mut rules = 0;
+    let mut in_chain = false;
+    let mut default_policy = None;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("chain ") && line.ends_with('{') {
+            in_chain = true;
+        } else if line == "}" {
+            in_chain = false;
+        } else if in_chain && line.starts_with("type ") {
+            // "type filter hook input priority filter; policy drop;"
+            if line.contains("hook input")
+                && let Some((_, rest)) = line.split_once("policy ")
+            {
+                default_policy = Some(rest.trim_end_matches(';').trim().to_string());
+            }
+        } else if in_chain && !line.is_empty() && !line.starts_with("comment ") {
+            rules += 1;
+        }
+    }
+
+    FirewallStatus {
+        backend: "nftables",
+        enabled: Some(!text.trim().is_empty()),
+        rules,
+        default_policy,
+        last_change: config_mtime("/etc/nftables.conf"),
+        fingerprint: fingerprint(&text),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn query_pf() -> FirewallStatus {
+    use std::process::Command;
+
+    let info = match Command::new("pfctl").args(["-s", "info"]).output() {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).to_string(),
+        _ => return FirewallStatus::unavailable("pf"),
+    };
+    let enabled = info
+        .lines()
+        .find(|l| l.starts_with("Status:"))
+        .map(|l| l.contains("Enabled"))
+        .unwrap_or(false);
+
+    let rules_text = Command::new("pfctl")
+        .args(["-s", "rules"])
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).to_string())
+        .unwrap_or_default();
+    let rules: Vec<&str> = rules_text.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    // pf evaluates the last matching rule, so a leading "block all" is the default
+    let default_policy = rules.first().map(|r| {
+        if r.starts_with("block") && r.ends_with(" all") {
+            "block".to_string()
+        } else {
+            "pass".to_string()
+        }
+    });
+
+    FirewallStatus {
+        backend: "pf",
+        enabled: Some(enabled),
+        rules: rules.len(),
+        default_policy,
+        last_change: config_mtime("/etc/pf.conf"),
+        fingerprint: fingerprint(&rules_text),
+    }
+}
@@ -0,0 +1,149 @@
+//! Details about one mounted filesystem for the Disks tab popup, and which
+//! mounts are noise.
+//!
+//! sysinfo covers the basics (type, removable, read-only, SSD or HDD); on
+//! Linux the mount options come from `/proc/self/mounts` and the UUID and
+//! label from the `/dev/disk/by-uuid` and `/dev/disk/by-label` symlinks
+//! that udev maintains. Other platforms show what sysinfo knows.
+
+use sysinfo::{Disk, DiskKind};
+
+/// Filesystem types hidden while the disk filter is on: snap and AppImage
+/// squashfs loops, container overlays and memory-backed mounts.
+pub const DEFAULT_HIDDEN_FILESYSTEMS: &[&str] = &[
+    "squashfs",
+    "overlay",
+    "tmpfs",
+    "devtmpfs",
+    "ramfs",
+    "efivarfs",
+    "erofs",
+    "fuse.snapfuse",
+];
+
+/// Whether a mount is a pseudo or read-only image filesystem rather than
+/// real storage. Loop devices are hidden whatever their type.
+pub fn is_pseudo(device: &str, file_system: &str, hidden: &[String]) -> bool {
+    device.starts_with("/dev/loop") || hidden.iter().any(|fs| fs == file_system)
+}
+
+pub struct FilesystemDetail {
+    pub mount: String,
+    pub device: String,
+    pub file_system: String,
+    /// Spinning disk; `None` when the OS doesn't say
+    pub rotational: Option<bool>,
+    pub removable: bool,
+    pub read_only: bool,
+    pub total: u64,
+    pub available: u64,
+    pub mount_options: Option<String>,
+    pub uuid: Option<String>,
+    pub label: Option<String>,
+}
+
+impl FilesystemDetail {
+    pub fn collect(disk: &Disk) -> Self {
+        let mount = disk.mount_point().to_string_lossy().to_string();
+        let device = disk.name().to_string_lossy().to_string();
+        Self {
+            mount_options: mount_options(&mount),
+            uuid: device_alias("by-uuid", &device),
+            label: device_alias("by-label", &device),
+            file_system: disk.file_system().to_string_lossy().to_string(),
+            rotational: match disk.kind() {
+                DiskKind::HDD => Some(true),
+                DiskKind::SSD => Some(false),
+                DiskKind::Unknown(_) => None,
+            },
+            removable: disk.is_removable(),
+            read_only: disk.is_read_only(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+            mount,
+            device,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn mount_options(mount: &str) -> Option<String> {
+    let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+    // Later entries shadow earlier ones mounted at the same place
+    mounts.lines().rev().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let (_, point, _, options) = (fields.next()?, fields.next()?, fields.next()?, fields.next()?);
+        (unescape_mount(point) == mount).then(|| options.to_string())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mount_options(_mount: &str) -> Option<String> {
+    None
+}
+
+/// `/proc/mounts` escapes space, tab, newline and backslash as `\ooo`.
+#[cfg(target_os = "linux")]
+fn unescape_mount(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(i) = rest.find('\\') {
+        out.push_str(&rest[..i]);
+        let code = rest
+            .get(i + 1..i + 4)
+            .and_then(|o| u8::from_str_radix(o, 8).ok());
+        match code {
+            Some(byte) => {
+                out.push(byte as char);
+                rest = &rest[i + 4..];
+            }
+            None => {
+                out.push('\\');
+                rest = &rest[i + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Name of the symlink in `/dev/disk/<dir>` that points at `device`.
+#[cfg(target_os = "linux")]
+fn device_alias(dir: &str, device: &str) -> Option<String> {
+    let target = std::fs::canonicalize(device).ok()?;
+    std::fs::read_dir(std::path::Path::new("/dev/disk").join(dir))
+        .ok()?
+        .flatten()
+        .find(|e| std::fs::canonicalize(e.path()).is_ok_and(|p| p == target))
+        .map(|e| unescape_udev(&e.file_name().to_string_lossy()))
+}
+
+/// udev escapes unsafe characters in link names as `\xHH`.
+#[cfg(target_os = "linux")]
+fn unescape_udev(name: &str) -> String {
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut rest = name.as_bytes();
+    while !rest.is_empty() {
+        let hex = rest
+            .strip_prefix(b"\\x")
+            .and_then(|r| r.get(..2))
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                bytes.push(byte);
+                rest = &rest[4..];
+            }
+            None => {
+                bytes.push(rest[0]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn device_alias(_dir: &str, _device: &str) -> Option<String> {
+    None
+}
@@ -0,0 +1,318 @@
+//! Country and ASN of remote addresses, from MaxMind DB (`.mmdb`) files
+//! such as the free GeoLite2-Country and GeoLite2-ASN databases.
+//!
+//! The format is simple enough to read without a dependency: a binary
+//! search tree over the address bits whose leaves point into a section of
+//! self-describing values, followed by a metadata map. Databases are read
+//! into memory once; only the few fields shown are looked at.
+
+use std::fs;
+use std::net::IpAddr;
+
+/// Precedes the metadata map at the end of the file
+const METADATA_MARKER: &[u8] = b"\xAB\xCD\xEFMaxMind.com";
+/// Bounds pointer chasing in a corrupt file
+const MAX_DEPTH: u8 = 16;
+
+/// Where an address is registered.
+pub struct Origin {
+    pub country: Option<String>,
+    pub asn: Option<u64>,
+    pub organization: Option<String>,
+}
+
+impl Origin {
+    /// `US AS15169 GOOGLE`, or whichever parts are known.
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(country) = &self.country {
+            parts.push(country.clone());
+        }
+        if let Some(asn) = self.asn {
+            parts.push(format!("AS{asn}"));
+        }
+        if let Some(organization) = &self.organization {
+            parts.push(organization.clone());
+        }
+        parts.join(" ")
+    }
+}
+
+/// All configured databases; a country and an ASN database are usually
+/// separate files, so answers are merged.
+pub struct GeoIp {
+    databases: Vec<Mmdb>,
+}
+
+impl GeoIp {
+    /// Open every path, returning the readable ones (if any) and an error
+    /// message per file that couldn't be used.
+    pub fn load(paths: &[String]) -> (Option<Self>, Vec<String>) {
+        let mut databases = Vec::new();
+        let mut errors = Vec::new();
+        for path in paths {
+            match Mmdb::open(path) {
+                Ok(db) => databases.push(db),
+                Err(e) => errors.push(format!("{path}: {e}")),
+            }
+        }
+        let geoip = (!databases.is_empty()).then_some(Self { databases });
+        (geoip, errors)
+    }
+
+    pub fn lookup(&self, ip: IpAddr) -> Option<Origin> {
+        // Dual-stack sockets report IPv4 peers as ::ffff:a.b.c.d
+        let ip = ip.to_canonical();
+        let mut origin = Origin {
+            country: None,
+            asn: None,
+            organization: None,
+        };
+        for db in &self.databases {
+            let Some(record) = db.lookup(ip) else {
+                continue;
+            };
+            // City and Country databases; registered_country covers
+            // anycast and satellite ranges without a located country
+            let country = record
+                .path(&["country", "iso_code"])
+                .or_else(|| record.path(&["registered_country", "iso_code"]))
+                .and_then(Value::as_str);
+            origin.country = origin.country.or(country.map(str::to_string));
+            origin.asn = origin.asn.or(record
+                .path(&["autonomous_system_number"])
+                .and_then(Value::as_uint));
+            origin.organization = origin.organization.or(record
+                .path(&["autonomous_system_organization"])
+                .and_then(Value::as_str)
+                .map(str::to_string));
+        }
+        (origin.country.is_some() || origin.asn.is_some()).then_some(origin)
+    }
+}
+
+struct Mmdb {
+    bytes: Vec<u8>,
+    node_count: usize,
+    /// Bits per record, two records per node: 24, 28 or 32
+    record_size: usize,
+    ip_version: u64,
+    data_start: usize,
+    /// Node IPv4 lookups start from in an IPv6 tree (`::a.b.c.d`)
+    ipv4_root: usize,
+}
+
+impl Mmdb {
+    fn open(path: &str) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        let marker = bytes
+            .windows(METADATA_MARKER.len())
+            .rposition(|w| w == METADATA_MARKER)
+            .ok_or("not a MaxMind DB file")?;
+        let metadata_start = marker + METADATA_MARKER.len();
+        let (metadata, _) = Decoder {
+            data: &bytes[metadata_start..],
+        }
+        .decode(0, 0)
+        .ok_or("unreadable metadata")?;
+        let field = |key: &str| {
+            metadata
+                .path(&[key])
+                .and_then(Value::as_uint)
+                .ok_or(format!("metadata has no {key}"))
+        };
+        let node_count = field("node_count")? as usize;
+        let record_size = field("record_size")? as usize;
+        let ip_version = field("ip_version")?;
+        if ![24, 28, 32].contains(&record_size) {
+            return Err(format!("unsupported record size {record_size}"));
+        }
+        // The tree is followed by 16 zero bytes, then the data section
+        let data_start = node_count
+            .checked_mul(record_size)
+            .and_then(|bits| (bits / 4).checked_add(16))
+            .ok_or("node count out of range")?;
+        if data_start > marker {
+            return Err("truncated search tree".into());
+        }
+
+        let mut db = Self {
+            bytes,
+            node_count,
+            record_size,
+            ip_version,
+            data_start,
+            ipv4_root: 0,
+        };
+        if ip_version == 6 {
+            let mut node = 0;
+            for _ in 0..96 {
+                if node >= node_count {
+                    break;
+                }
+                node = db.record(node, 0).ok_or("truncated search tree")?;
+            }
+            db.ipv4_root = node;
+        }
+        Ok(db)
+    }
+
+    /// Left (`bit` 0) or right record of a tree node.
+    fn record(&self, node: usize, bit: u128) -> Option<usize> {
+        let width = self.record_size / 4;
+        let b = self.bytes.get(node * width..(node + 1) * width)?;
+        Some(match (self.record_size, bit) {
+            (24, 0) => be(&b[0..3]),
+            (24, _) => be(&b[3..6]),
+            // The middle byte holds the high nibble of each record
+            (28, 0) => (b[3] as usize & 0xF0) << 20 | be(&b[0..3]),
+            (28, _) => (b[3] as usize & 0x0F) << 24 | be(&b[4..7]),
+            (_, 0) => be(&b[0..4]),
+            _ => be(&b[4..8]),
+        })
+    }
+
+    fn lookup(&self, ip: IpAddr) -> Option<Value<'_>> {
+        let (address, bits, mut node) = match ip {
+            IpAddr::V4(v4) if self.ip_version == 6 => (u32::from(v4) as u128, 32, self.ipv4_root),
+            IpAddr::V4(v4) => (u32::from(v4) as u128, 32, 0),
+            IpAddr::V6(v6) if self.ip_version == 4 => {
+                (u32::from(v6.to_ipv4_mapped()?) as u128, 32, 0)
+            }
+            IpAddr::V6(v6) => (u128::from(v6), 128, 0),
+        };
+        for i in (0..bits).rev() {
+            if node >= self.node_count {
+                break;
+            }
+            node = self.record(node, (address >> i) & 1)?;
+        }
+        // Equal to node_count means "no data"; below it, the address ran
+        // out before reaching a leaf
+        if node <= self.node_count {
+            return None;
+        }
+        let offset = node.checked_sub(self.node_count + 16)?;
+        let (value, _) = Decoder {
+            data: self.bytes.get(self.data_start..)?,
+        }
+        .decode(offset, 0)?;
+        Some(value)
+    }
+}
+
+/// A decoded data section value; only the types the lookups need are kept.
+enum Value<'a> {
+    Str(&'a str),
+    Uint(u64),
+    Map(Vec<(&'a str, Value<'a>)>),
+    Other,
+}
+
+impl<'a> Value<'a> {
+    fn path(&self, keys: &[&str]) -> Option<&Value<'a>> {
+        keys.iter().try_fold(self, |value, key| match value {
+            Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        })
+    }
+
+    fn as_str(&self) -> Option<&'a str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_uint(&self) -> Option<u64> {
+        match self {
+            Value::Uint(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+struct Decoder<'a> {
+    /// Pointers are offsets from the start of this section
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// The value at `pos` and the offset just past it.
+    fn decode(&self, mut pos: usize, depth: u8) -> Option<(Value<'a>, usize)> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let control = *self.data.get(pos)?;
+        pos += 1;
+        let mut kind = control >> 5;
+
+        if kind == 1 {
+            // Pointer: 1-4 more bytes, size-dependent bias
+            let size = (control >> 3 & 0x3) as usize;
+            let high = (control & 0x7) as usize;
+            let low = be(self.data.get(pos..pos + size + 1)?);
+            let target = match size {
+                0 => high << 8 | low,
+                1 => (high << 16 | low) + 2048,
+                2 => (high << 24 | low) + 526_336,
+                _ => low,
+            };
+            let (value, _) = self.decode(target, depth + 1)?;
+            return Some((value, pos + size + 1));
+        }
+        if kind == 0 {
+            kind = 7u8.checked_add(*self.data.get(pos)?)?;
+            pos += 1;
+        }
+
+        let mut size = (control & 0x1F) as usize;
+        if size >= 29 {
+            let extra = size - 28;
+            let base = match extra {
+                1 => 29,
+                2 => 285,
+                _ => 65_821,
+            };
+            size = base + be(self.data.get(pos..pos + extra)?);
+            pos += extra;
+        }
+
+        match kind {
+            2 => {
+                let text = std::str::from_utf8(self.data.get(pos..pos + size)?).ok()?;
+                Some((Value::Str(text), pos + size))
+            }
+            // uint16, uint32, uint64; uint128 only when it fits
+            5 | 6 | 9 | 10 if size <= 8 => {
+                let bytes = self.data.get(pos..pos + size)?;
+                let n = bytes.iter().fold(0u64, |n, &b| n << 8 | b as u64);
+                Some((Value::Uint(n), pos + size))
+            }
+            7 => {
+                let mut entries = Vec::with_capacity(size);
+                for _ in 0..size {
+                    let (key, next) = self.decode(pos, depth + 1)?;
+                    let (value, next) = self.decode(next, depth + 1)?;
+                    entries.push((key.as_str()?, value));
+                    pos = next;
+                }
+                Some((Value::Map(entries), pos))
+            }
+            11 => {
+                for _ in 0..size {
+                    pos = self.decode(pos, depth + 1)?.1;
+                }
+                Some((Value::Other, pos))
+            }
+            // Boolean keeps its value in the size; containers and the end
+            // marker have no payload
+            12..=14 => Some((Value::Other, pos)),
+            _ => Some((Value::Other, pos + size)),
+        }
+    }
+}
+
+fn be(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0, |n, &b| n << 8 | b as usize)
+}
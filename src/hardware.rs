@@ -0,0 +1,298 @@
+//! Static hardware inventory: motherboard, firmware and memory modules.
+//!
+//! Collected once at startup since none of it changes while running.
+//! Linux reads `/sys/class/dmi/id` (world-readable) and asks `dmidecode`
+//! for memory slots, which needs root — without it the slot list is empty.
+//! macOS parses `system_profiler`, Windows queries CIM via PowerShell.
+//!
+//! Serial numbers and asset tags are collected too but only displayed with
+//! `--show-identifiers`, so screenshots don't leak them by default. On Linux
+//! the serial files are root-only; the asset tag is world-readable.
+
+use std::process::Command;
+
+#[derive(Default)]
+pub struct MemoryModule {
+    pub slot: String,
+    pub size: String,
+    pub kind: String,
+    pub speed: String,
+}
+
+pub struct Vulnerability {
+    pub name: String,
+    pub status: String,
+}
+
+impl Vulnerability {
+    pub fn is_vulnerable(&self) -> bool {
+        self.status.starts_with("Vulnerable")
+    }
+
+    pub fn is_mitigated(&self) -> bool {
+        self.status.starts_with("Mitigation")
+    }
+}
+
+#[derive(Default)]
+pub struct HardwareInfo {
+    pub system_vendor: Option<String>,
+    pub product_name: Option<String>,
+    pub board_vendor: Option<String>,
+    pub board_name: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    pub bios_date: Option<String>,
+    pub system_serial: Option<String>,
+    pub board_serial: Option<String>,
+    pub chassis_serial: Option<String>,
+    pub asset_tag: Option<String>,
+    pub memory_modules: Vec<MemoryModule>,
+    /// Kernel-reported CPU vulnerability status (Linux only)
+    pub cpu_vulnerabilities: Vec<Vulnerability>,
+}
+
+impl HardwareInfo {
+    pub fn collect() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            collect_linux()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            collect_macos()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            collect_windows()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+        {
+            Self::default()
+        }
+    }
+}
+
+/// Trim a value and drop the placeholders vendors leave in DMI fields.
+fn clean(value: &str) -> Option<String> {
+    let v = value.trim();
+    let placeholder = v.is_empty()
+        || v.eq_ignore_ascii_case("To Be Filled By O.E.M.")
+        || v.eq_ignore_ascii_case("Default string")
+        || v.eq_ignore_ascii_case("Not Specified")
+        || v.eq_ignore_ascii_case("System Product Name")
+        || v.eq_ignore_ascii_case("System Serial Number")
+        || v.eq_ignore_ascii_case("Chassis Serial Number")
+        || v.eq_ignore_ascii_case("No Asset Tag")
+        || v.eq_ignore_ascii_case("No Asset Information")
+        || v == "0123456789";
+    (!placeholder).then(|| v.to_string())
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let out = Command::new(program).args(args).output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+/// Split `Key: Value` into a trimmed pair.
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let (k, v) = line.split_once(':')?;
+    Some((k.trim(), v.trim()))
+}
+
+#[cfg(target_os = "linux")]
+fn collect_linux() -> HardwareInfo {
+    let dmi = |name: &str| {
+        std::fs::read_to_string(format!("/sys/class/dmi/id/{name}"))
+            .ok()
+            .and_then(|v| clean(&v))
+    };
+
+    HardwareInfo {
+        system_vendor: dmi("sys_vendor"),
+        product_name: dmi("product_name"),
+        board_vendor: dmi("board_vendor"),
+        board_name: dmi("board_name"),
+        bios_vendor: dmi("bios_vendor"),
+        bios_version: dmi("bios_version"),
+        bios_date: dmi("bios_date"),
+        system_serial: dmi("product_serial"),
+        board_serial: dmi("board_serial"),
+        chassis_serial: dmi("chassis_serial"),
+        asset_tag: dmi("chassis_asset_tag"),
+        memory_modules: run("dmidecode", &["-t", "17"])
+            .map(|text| parse_dmidecode_memory(&text))
+            .unwrap_or_default(),
+        cpu_vulnerabilities: read_cpu_vulnerabilities(),
+    }
+}
+
+/// One entry per file in `/sys/devices/system/cpu/vulnerabilities`, e.g.
+/// `spectre_v2` → "Mitigation: Enhanced / Automatic IBRS; ...".
+#[cfg(target_os = "linux")]
+fn read_cpu_vulnerabilities() -> Vec<Vulnerability> {
+    let Ok(entries) = std::fs::read_dir("/sys/devices/system/cpu/vulnerabilities") else {
+        return Vec::new();
+    };
+    let mut vulns: Vec<Vulnerability> = entries
+        .flatten()
+        .filter_map(|e| {
+            let status = std::fs::read_to_string(e.path()).ok()?;
+            Some(Vulnerability {
+                name: e.file_name().to_string_lossy().to_string(),
+                status: status.trim().to_string(),
+            })
+        })
+        .collect();
+    vulns.sort_by(|a, b| a.name.cmp(&b.name));
+    vulns
+}
+
+/// Parse `dmidecode -t 17` "Memory Device" records, skipping empty slots.
+#[cfg(target_os = "linux")]
+fn parse_dmidecode_memory(text: &str) -> Vec<MemoryModule> {
+    let mut modules = Vec::new();
+    for record in text.split("Memory Device").skip(1) {
+        let mut module = MemoryModule::default();
+        for (key, value) in record.lines().filter_map(key_value) {
+            match key {
+                "Locator" => module.slot = value.to_string(),
+                "Size" => module.size = value.to_string(),
+                "Type" => module.kind = value.to_string(),
+                "Speed" => module.speed = value.to_string(),
+                _ => {}
+            }
+        }
+        let installed = !module.size.is_empty()
+            && !module.size.starts_with("No Module")
+            && module.size != "Unknown";
+        if installed {
+            modules.push(module);
+        }
+    }
+    modules
+}
+
+#[cfg(target_os = "macos")]
+fn collect_macos() -> HardwareInfo {
+    let text = run("system_profiler", &["SPHardwareDataType", "SPMemoryDataType"])
+        .unwrap_or_default();
+
+    let mut info = HardwareInfo {
+        system_vendor: Some("Apple".into()),
+        ..Default::default()
+    };
+    let mut module: Option<MemoryModule> = None;
+    let mut unified = MemoryModule {
+        slot: "Unified".into(),
+        ..Default::default()
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        // Intel Macs list slots as "BANK 0/DIMM0:" headers followed by fields
+        if trimmed.ends_with(':') && (trimmed.starts_with("BANK") || trimmed.contains("DIMM")) {
+            if let Some(m) = module.take().filter(|m| !m.size.is_empty() && m.size != "Empty") {
+                info.memory_modules.push(m);
+            }
+            module = Some(MemoryModule {
+                slot: trimmed.trim_end_matches(':').to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some((key, value)) = key_value(trimmed) else {
+            continue;
+        };
+        match (key, module.as_mut()) {
+            ("Model Name", _) => info.product_name = clean(value),
+            ("Model Identifier", _) => info.board_name = clean(value),
+            ("System Firmware Version", _) => info.bios_version = clean(value),
+            ("Serial Number (system)", _) => info.system_serial = clean(value),
+            ("Size", Some(m)) => m.size = value.to_string(),
+            ("Type", Some(m)) => m.kind = value.to_string(),
+            ("Speed", Some(m)) => m.speed = value.to_string(),
+            // Apple Silicon reports a single unified memory block
+            ("Memory", None) => unified.size = value.to_string(),
+            ("Type", None) => unified.kind = value.to_string(),
+            _ => {}
+        }
+    }
+    if let Some(m) = module.filter(|m| !m.size.is_empty() && m.size != "Empty") {
+        info.memory_modules.push(m);
+    }
+    if info.memory_modules.is_empty() && !unified.size.is_empty() {
+        info.memory_modules.push(unified);
+    }
+    info
+}
+
+#[cfg(target_os = "windows")]
+fn collect_windows() -> HardwareInfo {
+    const SCRIPT: &str = "\
+        $cs = Get-CimInstance Win32_ComputerSystem; \
+        $bb = Get-CimInstance Win32_BaseBoard; \
+        $bios = Get-CimInstance Win32_BIOS; \
+        \"SystemVendor: $($cs.Manufacturer)\"; \
+        \"ProductName: $($cs.Model)\"; \
+        \"BoardVendor: $($bb.Manufacturer)\"; \
+        \"BoardName: $($bb.Product)\"; \
+        \"BiosVendor: $($bios.Manufacturer)\"; \
+        \"BiosVersion: $($bios.SMBIOSBIOSVersion)\"; \
+        \"BiosDate: $($bios.ReleaseDate)\"; \
+        \"SystemSerial: $($bios.SerialNumber)\"; \
+        \"BoardSerial: $($bb.SerialNumber)\"; \
+        $enc = Get-CimInstance Win32_SystemEnclosure; \
+        \"ChassisSerial: $($enc.SerialNumber)\"; \
+        \"AssetTag: $($enc.SMBIOSAssetTag)\"; \
+        Get-CimInstance Win32_PhysicalMemory | ForEach-Object { \
+            \"Module: $($_.DeviceLocator)|$([math]::Round($_.Capacity / 1GB)) GB|$($_.SMBIOSMemoryType)|$($_.Speed) MT/s\" }";
+
+    let text = run("powershell", &["-NoProfile", "-Command", SCRIPT]).unwrap_or_default();
+    let mut info = HardwareInfo::default();
+    for (key, value) in text.lines().filter_map(key_value) {
+        match key {
+            "SystemVendor" => info.system_vendor = clean(value),
+            "ProductName" => info.product_name = clean(value),
+            "BoardVendor" => info.board_vendor = clean(value),
+            "BoardName" => info.board_name = clean(value),
+            "BiosVendor" => info.bios_vendor = clean(value),
+            "BiosVersion" => info.bios_version = clean(value),
+            "BiosDate" => info.bios_date = clean(value),
+            "SystemSerial" => info.system_serial = clean(value),
+            "BoardSerial" => info.board_serial = clean(value),
+            "ChassisSerial" => info.chassis_serial = clean(value),
+            "AssetTag" => info.asset_tag = clean(value),
+            "Module" => {
+                let mut parts = value.split('|').map(str::to_string);
+                info.memory_modules.push(MemoryModule {
+                    slot: parts.next().unwrap_or_default(),
+                    size: parts.next().unwrap_or_default(),
+                    kind: parts.next().map(|t| smbios_memory_type(&t)).unwrap_or_default(),
+                    speed: parts.next().unwrap_or_default(),
+                });
+            }
+            _ => {}
+        }
+    }
+    info
+}
+
+/// SMBIOS memory type codes used by `Win32_PhysicalMemory.SMBIOSMemoryType`.
+#[cfg(target_os = "windows")]
+fn smbios_memory_type(code: &str) -> String {
+    match code.trim() {
+        "24" => "DDR3",
+        "26" => "DDR4",
+        "27" => "LPDDR",
+        "28" => "LPDDR2",
+        "29" => "LPDDR3",
+        "30" => "LPDDR4",
+        "34" => "DDR5",
+        "35" => "LPDDR5",
+        other => other,
+    }
+    .to_string()
+}
@@ -0,0 +1,129 @@
+//! Graph histories saved on quit and loaded at startup, with
+//! `persist_history = on`, so a monitor reopened for a quick check starts
+//! with the last half hour rather than flat lines.
+//!
+//! The file is `rustmonitor/history.json` under `$XDG_STATE_HOME`,
+//! `~/.local/state` or `%LOCALAPPDATA%`, in the layout of a full state
+//! snapshot's `history` field. Samples only line up with the graphs at the
+//! refresh rate they were taken at, so a file from another rate or host is
+//! ignored, and the time the monitor was closed shows as a gap of zeros.
+
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app::App;
+use crate::json::{self, Value};
+use crate::snapshot;
+
+pub fn path() -> Option<PathBuf> {
+    let dir = if let Some(dir) = env::var_os("XDG_STATE_HOME").filter(|d| !d.is_empty()) {
+        PathBuf::from(dir)
+    } else if cfg!(windows) {
+        PathBuf::from(env::var_os("LOCALAPPDATA")?)
+    } else {
+        PathBuf::from(env::var_os("HOME")?).join(".local").join("state")
+    };
+    Some(dir.join("rustmonitor").join("history.json"))
+}
+
+pub fn save(app: &App) -> io::Result<()> {
+    let path = path().ok_or_else(|| io::Error::other("no home directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = Value::object()
+        .with("host", app.hostname.as_str())
+        .with("refresh_ms", app.tick_rate.as_millis() as u64)
+        .with("saved_at", now())
+        .with("samples", app.history_filled)
+        .with("history", snapshot::history(app));
+    fs::write(path, file.to_compact())
+}
+
+/// Put the saved samples in front of the one taken at startup. Call right
+/// after `App::new`. Returns how long ago the file was saved, or `None`
+/// if there is none yet.
+pub fn load(app: &mut App) -> Result<Option<Duration>, String> {
+    let path = path().ok_or("no home directory")?;
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+    let file = json::parse(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+    if file.get("host").and_then(Value::as_str) != Some(app.hostname.as_str()) {
+        return Err("saved on another host".to_string());
+    }
+    let refresh_ms = file.get("refresh_ms").and_then(Value::as_u64).unwrap_or(0);
+    if refresh_ms != app.tick_rate.as_millis() as u64 {
+        return Err(format!("saved at a {refresh_ms}ms refresh rate"));
+    }
+    let saved_at = file.get("saved_at").and_then(Value::as_u64).unwrap_or(0);
+    let ago = Duration::from_secs(now().saturating_sub(saved_at));
+    let gap = (ago.as_millis() / refresh_ms.max(1) as u128) as usize;
+    if gap >= app.history_len {
+        return Err("too old to show".to_string());
+    }
+    let Some(history) = file.get("history") else {
+        return Err("no history in file".to_string());
+    };
+
+    let len = app.history_len;
+    let field = |key: &str| history.get(key).map(Value::as_array).unwrap_or(&[]);
+    restore(&mut app.global_cpu_history, field("cpu_pct"), gap, len);
+    restore(&mut app.mem_history, field("memory_pct"), gap, len);
+    restore(&mut app.net_rx_history, field("net_rx_kb"), gap, len);
+    restore(&mut app.net_tx_history, field("net_tx_kb"), gap, len);
+    restore(&mut app.disk_read_history, field("disk_read_kb"), gap, len);
+    restore(&mut app.disk_write_history, field("disk_write_kb"), gap, len);
+    // Per-core and per-GPU series by position, if the hardware still matches
+    for (key, series) in [
+        ("cpu_core_pct", &mut app.cpu_history),
+        ("gpu_util_pct", &mut app.gpu_util_history),
+    ] {
+        let saved = field(key);
+        if saved.len() == series.len() {
+            for (history, saved) in series.iter_mut().zip(saved) {
+                restore(history, saved.as_array(), gap, len);
+            }
+        }
+    }
+    for saved in field("interfaces") {
+        let name = saved.get("name").and_then(Value::as_str).unwrap_or_default();
+        if let Some((rx, tx)) = app.interface_history.get_mut(name) {
+            let samples = |key| saved.get(key).map(Value::as_array).unwrap_or(&[]);
+            restore(rx, samples("rx_kb"), gap, len);
+            restore(tx, samples("tx_kb"), gap, len);
+        }
+    }
+    let samples = file.get("samples").and_then(Value::as_u64).unwrap_or(0) as usize;
+    app.history_filled = (samples + gap + app.history_filled).min(len);
+    Ok(Some(ago))
+}
+
+/// `saved`, `gap` zeros and the newest sample of `history`, trimmed to
+/// the newest `len` and zero-padded in front like a fresh history.
+fn restore(history: &mut VecDeque<f64>, saved: &[Value], gap: usize, len: usize) {
+    let latest = history.back().copied().unwrap_or(0.0);
+    history.clear();
+    history.extend(saved.iter().map(|v| v.as_f64().unwrap_or(0.0)));
+    history.extend(std::iter::repeat_n(0.0, gap));
+    history.push_back(latest);
+    while history.len() > len {
+        history.pop_front();
+    }
+    while history.len() < len {
+        history.push_front(0.0);
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
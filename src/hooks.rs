@@ -0,0 +1,126 @@
+//! Commands run on events, from the `on_alert`, `on_bandwidth` and
+//! `on_process_kill` config keys, e.g.
+//!
+//! ```text
+//! on_alert = notify-send "RustMonitor" {message}
+//! on_process_kill = ./log.sh {pid} {name}
+//! ```
+//!
+//! Each runs through the shell (`sh -c`, `cmd /C` on Windows) in the
+//! background. `{placeholder}`s are replaced with the event's values,
+//! quoted for the shell so a process name can't inject commands, and the
+//! same values are set as `RUSTMONITOR_*` environment variables along with
+//! `RUSTMONITOR_EVENT`.
+
+use std::process::Command;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum HookEvent {
+    /// A critical alert fired; `key`, `message`
+    Alert,
+    /// An interface crossed `bandwidth_threshold`; `interface`, `percent`
+    Bandwidth,
+    /// A process was signalled from the Processes tab; `pid`, `name`,
+    /// `signal`
+    ProcessKill,
+}
+
+impl HookEvent {
+    /// Config key, also the `RUSTMONITOR_EVENT` value without `on_`.
+    pub fn key(self) -> &'static str {
+        match self {
+            HookEvent::Alert => "on_alert",
+            HookEvent::Bandwidth => "on_bandwidth",
+            HookEvent::ProcessKill => "on_process_kill",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Hooks {
+    pub on_alert: Option<String>,
+    pub on_bandwidth: Option<String>,
+    pub on_process_kill: Option<String>,
+}
+
+impl Hooks {
+    pub fn command(&self, event: HookEvent) -> Option<&str> {
+        match event {
+            HookEvent::Alert => self.on_alert.as_deref(),
+            HookEvent::Bandwidth => self.on_bandwidth.as_deref(),
+            HookEvent::ProcessKill => self.on_process_kill.as_deref(),
+        }
+        .filter(|c| !c.is_empty())
+    }
+}
+
+/// Run `template` for `event` and wait for it. `Err` carries the exit
+/// status and first line of stderr for the event history.
+pub fn run(event: HookEvent, template: &str, vars: &[(&str, String)]) -> Result<(), String> {
+    let mut cmd = shell(&expand(template, vars));
+    cmd.env("RUSTMONITOR_EVENT", event.key().trim_start_matches("on_"));
+    for (name, value) in vars {
+        cmd.env(format!("RUSTMONITOR_{}", name.to_uppercase()), value);
+    }
+    let output = cmd.output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(match stderr.lines().next() {
+        Some(line) => format!("{}: {line}", output.status),
+        None => output.status.to_string(),
+    })
+}
+
+/// Replace each `{name}` with its quoted value; unknown ones are left as is.
+/// One pass, so a value containing `{name}` isn't expanded again.
+fn expand(template: &str, vars: &[(&str, String)]) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .find('}')
+            .and_then(|end| Some((end, vars.iter().find(|(name, _)| *name == &after[..end])?)));
+        match value {
+            Some((end, (_, value))) => {
+                out.push_str(&quote(value));
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(target_os = "windows")]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(not(target_os = "windows"))]
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// cmd.exe has no escape for `"` inside quotes and expands `%VAR%` even
+/// there, so both are dropped.
+#[cfg(target_os = "windows")]
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace(['"', '%'], ""))
+}
@@ -0,0 +1,119 @@
+//! Details about one network interface for the Network tab popup.
+//!
+//! sysinfo supplies the addresses, MAC and MTU; on Linux the link state,
+//! negotiated speed, duplex and kernel driver come from
+//! `/sys/class/net/<iface>`. Other platforms show what sysinfo knows.
+
+use std::net::IpAddr;
+
+use sysinfo::IpNetwork;
+
+use crate::app::NetworkInterface;
+
+pub struct InterfaceDetail {
+    pub name: String,
+    pub mac_address: String,
+    pub addresses: Vec<IpNetwork>,
+    pub mtu: u64,
+    pub received: u64,
+    pub transmitted: u64,
+    /// Packets dropped since boot
+    pub drops: Option<(u64, u64)>,
+    pub link: LinkInfo,
+}
+
+impl InterfaceDetail {
+    pub fn collect(iface: &NetworkInterface) -> Self {
+        Self {
+            name: iface.name.clone(),
+            mac_address: iface.mac_address.clone(),
+            addresses: iface.addresses.clone(),
+            mtu: iface.mtu,
+            received: iface.total_received,
+            transmitted: iface.total_transmitted,
+            drops: read_drops(&iface.name),
+            link: iface.link.clone(),
+        }
+    }
+}
+
+/// Physical link properties; `None` where the OS or driver doesn't say
+/// (virtual interfaces have no speed or duplex).
+#[derive(Clone, Default)]
+pub struct LinkInfo {
+    /// `up`, `down`, `dormant`, …
+    pub state: Option<String>,
+    pub speed_mbps: Option<u32>,
+    pub duplex: Option<String>,
+    pub driver: Option<String>,
+}
+
+impl LinkInfo {
+    #[cfg(target_os = "linux")]
+    pub fn read(name: &str) -> Self {
+        use std::fs;
+        use std::path::Path;
+
+        let dir = Path::new("/sys/class/net").join(name);
+        let attr = |file: &str| {
+            fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty() && s != "unknown")
+        };
+        Self {
+            state: attr("operstate"),
+            // -1 (or a read error) when there is no carrier
+            speed_mbps: attr("speed")
+                .and_then(|s| s.parse::<i64>().ok())
+                .and_then(|s| u32::try_from(s).ok()),
+            duplex: attr("duplex"),
+            driver: fs::read_link(dir.join("device/driver"))
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string())),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read(_name: &str) -> Self {
+        Self::default()
+    }
+}
+
+/// Cumulative (RX, TX) dropped packets from
+/// `/sys/class/net/<iface>/statistics`; sysinfo doesn't expose drops.
+#[cfg(target_os = "linux")]
+pub fn read_drops(name: &str) -> Option<(u64, u64)> {
+    let dir = std::path::Path::new("/sys/class/net").join(name).join("statistics");
+    let counter = |file: &str| -> Option<u64> {
+        std::fs::read_to_string(dir.join(file)).ok()?.trim().parse().ok()
+    };
+    Some((counter("rx_dropped")?, counter("tx_dropped")?))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_drops(_name: &str) -> Option<(u64, u64)> {
+    None
+}
+
+/// Dotted netmask for an IPv4 prefix length, e.g. 24 → `255.255.255.0`.
+pub fn netmask(network: &IpNetwork) -> Option<String> {
+    match network.addr {
+        IpAddr::V4(_) => {
+            let mask = u32::MAX.checked_shl(32 - (network.prefix as u32).min(32)).unwrap_or(0);
+            Some(std::net::Ipv4Addr::from(mask).to_string())
+        }
+        IpAddr::V6(_) => None,
+    }
+}
+
+/// `1 Gbit/s`, `100 Mbit/s`.
+pub fn format_speed(mbps: u32) -> String {
+    if mbps >= 1000 && mbps.is_multiple_of(1000) {
+        format!("{} Gbit/s", mbps / 1000)
+    } else if mbps >= 1000 {
+        format!("{:.1} Gbit/s", mbps as f64 / 1000.0)
+    } else {
+        format!("{mbps} Mbit/s")
+    }
+}
@@ -0,0 +1,402 @@
+//! Key bindings for the main view. Every action has default keys that
+//! `key.<action> = k1, k2` lines in the config file replace; the help popup
+//! and footer hints are drawn from the same table so they always show the
+//! keys in effect.
+//!
+//! Keys are single characters (`h`, `?`, `C`) or names: `Esc`, `Enter`,
+//! `Tab`, `BackTab`, `Space`, `Backspace`, `Up`, `Down`, `Left`, `Right`,
+//! `PageUp`, `PageDown`, `Home`, `End`, `Comma`, `F1`–`F12`, optionally
+//! prefixed with `Ctrl+`; `g g` is a two-key sequence. `keymap = vim`
+//! starts from the vim profile instead. Prompts and confirmations keep
+//! their fixed keys.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::Tab;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Quit,
+    NextTab,
+    PrevTab,
+    GoTab(Tab),
+    Help,
+    Theme,
+    LargeUi,
+    Events,
+    RateUnits,
+    Faster,
+    Slower,
+    ZoomIn,
+    ZoomOut,
+    ShorterHistory,
+    LongerHistory,
+    CpuChart,
+    MemoryChart,
+    NetworkChart,
+    Command,
+    Run,
+    CommandOutput,
+    Snapshot,
+    Down,
+    Up,
+    PageDown,
+    PageUp,
+    HalfPageDown,
+    HalfPageUp,
+    Top,
+    Bottom,
+    ScrollLeft,
+    ScrollRight,
+    Search,
+    NextMatch,
+    PrevMatch,
+    Sort,
+    Kill,
+    KillAll,
+    Profile,
+    Export,
+    Cmdline,
+    Columns,
+    ProcessDetail,
+    Parent,
+    CopyPid,
+    CopyCommand,
+    GpuProcess,
+    ResetPeaks,
+    InterfaceDetail,
+    InterfaceGraph,
+    DiskDetail,
+    DiskFilter,
+    ReverseDns,
+}
+
+pub struct ActionInfo {
+    pub action: Action,
+    /// Config key after `key.`
+    pub name: &'static str,
+    /// Heading in the help popup
+    pub section: &'static str,
+    /// Tab the action is limited to, `None` for everywhere
+    pub scope: Option<Tab>,
+    pub description: &'static str,
+    defaults: &'static [&'static str],
+}
+
+const fn info(
+    action: Action,
+    name: &'static str,
+    section: &'static str,
+    scope: Option<Tab>,
+    description: &'static str,
+    defaults: &'static [&'static str],
+) -> ActionInfo {
+    ActionInfo { action, name, section, scope, description, defaults }
+}
+
+/// Every action in help-popup order.
+pub const ACTIONS: &[ActionInfo] = &[
+    info(Action::Quit, "quit", "General", None, "Quit application", &["q", "Esc"]),
+    info(Action::NextTab, "next_tab", "General", None, "Next tab", &["Tab"]),
+    info(Action::PrevTab, "prev_tab", "General", None, "Previous tab", &["BackTab"]),
+    info(Action::GoTab(Tab::Overview), "tab_overview", "General", None, "Overview tab", &["1"]),
+    info(Action::GoTab(Tab::Processes), "tab_processes", "General", None, "Processes tab", &["2"]),
+    info(Action::GoTab(Tab::SystemInfo), "tab_system", "General", None, "System tab", &["3"]),
+    info(Action::GoTab(Tab::NetworkDetail), "tab_network", "General", None, "Network tab", &["4"]),
+    info(Action::GoTab(Tab::Disks), "tab_disks", "General", None, "Disks tab", &["5"]),
+    info(Action::GoTab(Tab::Connections), "tab_connections", "General", None, "Connections tab", &["6"]),
+    info(Action::Help, "help", "General", None, "Toggle help", &["?"]),
+    info(Action::Theme, "theme", "General", None, "Cycle theme", &["t"]),
+    info(Action::LargeUi, "large_ui", "General", None, "Toggle large UI", &["L"]),
+    info(Action::Events, "events", "General", None, "Event history", &["E"]),
+    info(Action::RateUnits, "rate_units", "General", None, "Network rates in bits / bytes", &["b"]),
+    info(Action::Faster, "faster", "General", None, "Refresh faster (down to 250ms)", &["+", "="]),
+    info(Action::Slower, "slower", "General", None, "Refresh slower (up to 10s)", &["-"]),
+    info(Action::ZoomIn, "zoom_in", "General", None, "Zoom graphs in: 30m → 5m → 1m", &["["]),
+    info(Action::ZoomOut, "zoom_out", "General", None, "Zoom graphs out: 1m → 5m → 30m", &["]"]),
+    info(Action::ShorterHistory, "shorter_history", "General", None, "Keep a shorter graph history", &["{"]),
+    info(Action::LongerHistory, "longer_history", "General", None, "Keep a longer graph history", &["}"]),
+    info(Action::CpuChart, "cpu_chart", "General", None, "CPU graph as line chart", &["C"]),
+    info(Action::MemoryChart, "memory_chart", "General", None, "Memory graph as line chart", &["M"]),
+    info(Action::NetworkChart, "network_chart", "General", None, "Network graph as line chart", &["N"]),
+    info(Action::Command, "command", "General", None, "Command prompt", &[":"]),
+    info(Action::Run, "run", "General", None, "Launch a detached command and pin it", &["R"]),
+    info(Action::CommandOutput, "command_output", "General", None, "Show output of last command", &["o"]),
+    info(Action::Snapshot, "snapshot", "General", None, "Save a full JSON snapshot (with histories)", &["S"]),
+    info(Action::Down, "down", "Navigation", None, "Scroll down", &["Down", "j"]),
+    info(Action::Up, "up", "Navigation", None, "Scroll up", &["Up", "k"]),
+    info(Action::PageDown, "page_down", "Navigation", None, "Page down", &["PageDown"]),
+    info(Action::PageUp, "page_up", "Navigation", None, "Page up", &["PageUp"]),
+    info(Action::HalfPageDown, "half_page_down", "Navigation", None, "Half a page down", &[]),
+    info(Action::HalfPageUp, "half_page_up", "Navigation", None, "Half a page up", &[]),
+    info(Action::Top, "top", "Navigation", None, "Jump to top", &["Home"]),
+    info(Action::Bottom, "bottom", "Navigation", None, "Jump to bottom", &["End"]),
+    info(Action::ScrollLeft, "scroll_left", "Processes", Some(Tab::Processes), "Scroll long names left", &["Left"]),
+    info(Action::ScrollRight, "scroll_right", "Processes", Some(Tab::Processes), "Scroll long names right", &["Right"]),
+    info(Action::Search, "search", "Processes", None, "Search processes (connections on that tab)", &["/"]),
+    info(Action::NextMatch, "next_match", "Processes", None, "Next search match (wraps)", &[]),
+    info(Action::PrevMatch, "prev_match", "Processes", None, "Previous search match (wraps)", &[]),
+    info(Action::Sort, "sort", "Processes", None, "Cycle sort (processes, disks, connections)", &["s"]),
+    info(Action::Kill, "kill", "Processes", None, "Kill selected process", &["x"]),
+    info(Action::KillAll, "kill_all", "Processes", Some(Tab::Processes), "Kill every process matching the search", &["X"]),
+    info(Action::Profile, "profile", "Processes", None, "Profile selected process with perf", &["p"]),
+    info(Action::Export, "export", "Processes", Some(Tab::Processes), "Export the process table to CSV", &["e"]),
+    info(Action::Cmdline, "cmdline", "Processes", Some(Tab::Processes), "Full command lines in the Name column", &["c"]),
+    info(Action::Columns, "columns", "Processes", Some(Tab::Processes), "Choose table columns", &["v", "F2"]),
+    info(Action::ProcessDetail, "process_detail", "Processes", Some(Tab::Processes), "View process details", &["Enter"]),
+    info(Action::Parent, "parent", "Processes", Some(Tab::Processes), "Select the parent process", &["^"]),
+    info(Action::CopyPid, "copy_pid", "Processes", Some(Tab::Processes), "Copy the selected PID to the clipboard", &["y"]),
+    info(Action::CopyCommand, "copy_command", "Processes", Some(Tab::Processes), "Copy the selected command line", &["Y"]),
+    info(Action::GpuProcess, "gpu_process", "Overview", Some(Tab::Overview), "Jump to selected GPU process", &["Enter"]),
+    info(Action::ResetPeaks, "reset_peaks", "System", Some(Tab::SystemInfo), "Reset session peaks", &["r"]),
+    info(Action::InterfaceDetail, "interface_detail", "Network", Some(Tab::NetworkDetail), "Interface details", &["Enter"]),
+    info(Action::InterfaceGraph, "interface_graph", "Network", Some(Tab::NetworkDetail), "Graph selected interface / all", &["i"]),
+    info(Action::DiskDetail, "disk_detail", "Disks", Some(Tab::Disks), "Filesystem details", &["Enter"]),
+    info(Action::DiskFilter, "disk_filter", "Disks", None, "Show / hide pseudo and duplicate filesystems", &["f"]),
+    info(Action::ReverseDns, "reverse_dns", "Connections", Some(Tab::Connections), "Toggle reverse DNS for remote addresses", &["n"]),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+    ctrl: bool,
+}
+
+impl Key {
+    pub fn parse(text: &str) -> Option<Self> {
+        let (ctrl, name) = match text.get(..5) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("ctrl+") && text.len() > 5 => (true, &text[5..]),
+            _ => (false, text),
+        };
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_ascii_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backtab" | "shift+tab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "comma" => KeyCode::Char(','),
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "delete" | "del" => KeyCode::Delete,
+                f => KeyCode::F(f.strip_prefix('f')?.parse().ok().filter(|n| (1..=12).contains(n))?),
+            },
+        };
+        Some(Self { code, ctrl })
+    }
+
+    /// Shift is left out: terminals report `C` as Shift+c.
+    pub fn from_event(event: &KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            _ => "?".to_string(),
+        };
+        if self.ctrl { format!("Ctrl+{name}") } else { name }
+    }
+}
+
+/// One key, or a sequence such as vim's `g g`.
+#[derive(Clone, PartialEq)]
+pub struct Binding(Vec<Key>);
+
+impl Binding {
+    /// Keys separated by spaces; sequences are at most two keys long.
+    fn parse(text: &str) -> Option<Self> {
+        let keys = text
+            .split_whitespace()
+            .map(Key::parse)
+            .collect::<Option<Vec<_>>>()?;
+        (1..=2).contains(&keys.len()).then_some(Self(keys))
+    }
+
+    pub fn label(&self) -> String {
+        self.0.iter().map(Key::label).collect()
+    }
+}
+
+/// What a key press means given the key before it.
+pub enum Lookup {
+    Action(Action),
+    /// The start of a sequence; wait for the next key
+    Prefix,
+    None,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Profile {
+    Default,
+    /// `g g` / `G`, `Ctrl+d` / `Ctrl+u`, `h` / `l` and `n` / `N`
+    Vim,
+}
+
+impl Profile {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Profile::Default),
+            "vim" => Some(Profile::Vim),
+            _ => None,
+        }
+    }
+
+    /// Bindings applied over the defaults, like `key.<action>` lines.
+    fn bindings(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Profile::Default => &[],
+            Profile::Vim => &[
+                ("top", "g g, Home"),
+                ("bottom", "G, End"),
+                ("half_page_down", "Ctrl+d"),
+                ("half_page_up", "Ctrl+u"),
+                ("prev_tab", "h, BackTab"),
+                ("next_tab", "l, Tab"),
+                // n / N step through search matches, so the network chart
+                // and reverse DNS move out of their way
+                ("network_chart", "Ctrl+n"),
+                ("reverse_dns", "r"),
+                ("next_match", "n"),
+                ("prev_match", "N"),
+            ],
+        }
+    }
+}
+
+/// Bindings for each action, in `ACTIONS` order.
+pub struct Keymap {
+    keys: Vec<Vec<Binding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            keys: ACTIONS
+                .iter()
+                .map(|info| info.defaults.iter().filter_map(|k| Binding::parse(k)).collect())
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// A profile with the config file's `key.<action>` lines on top.
+    pub fn new(profile: Profile, overrides: &[(String, String)]) -> Self {
+        let mut keymap = Self::default();
+        for (name, keys) in profile.bindings() {
+            keymap.bind(name, keys);
+        }
+        for (name, keys) in overrides {
+            keymap.bind(name, keys);
+        }
+        keymap
+    }
+
+    /// Replace the keys of the action called `name` with a comma-separated
+    /// list, taking them away from actions they would clash with. Unknown
+    /// actions and lists with an unknown key are ignored.
+    fn bind(&mut self, name: &str, value: &str) {
+        let Some(index) = ACTIONS.iter().position(|info| info.name == name) else {
+            return;
+        };
+        let Some(keys) = value
+            .split(',')
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(Binding::parse)
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+        let scope = ACTIONS[index].scope;
+        for (other, info) in self.keys.iter_mut().zip(ACTIONS) {
+            if info.scope.is_none() || scope.is_none() || info.scope == scope {
+                other.retain(|k| !keys.contains(k));
+            }
+        }
+        self.keys[index] = keys;
+    }
+
+    /// The action for a key press on `tab`, after `prefix` if the last key
+    /// started a sequence. Actions limited to the tab win over ones that
+    /// apply everywhere.
+    pub fn lookup(&self, prefix: Option<Key>, key: Key, tab: Tab) -> Lookup {
+        let pressed: Vec<Key> = prefix.into_iter().chain([key]).collect();
+        let in_scope = |info: &&ActionInfo| info.scope.is_none() || info.scope == Some(tab);
+        let bound = |info: &&ActionInfo| self.keys(info.action).iter().any(|b| b.0 == pressed);
+        if let Some(info) = ACTIONS
+            .iter()
+            .filter(bound)
+            .find(|info| info.scope == Some(tab))
+            .or_else(|| ACTIONS.iter().filter(bound).find(|info| info.scope.is_none()))
+        {
+            return Lookup::Action(info.action);
+        }
+        let starts = ACTIONS.iter().filter(in_scope).any(|info| {
+            self.keys(info.action)
+                .iter()
+                .any(|b| b.0.len() > pressed.len() && b.0.starts_with(&pressed))
+        });
+        if starts { Lookup::Prefix } else { Lookup::None }
+    }
+
+    /// The action for a single key press, ignoring sequences.
+    pub fn action(&self, event: &KeyEvent, tab: Tab) -> Option<Action> {
+        match self.lookup(None, Key::from_event(event), tab) {
+            Lookup::Action(action) => Some(action),
+            _ => None,
+        }
+    }
+
+    pub fn keys(&self, action: Action) -> &[Binding] {
+        let index = ACTIONS.iter().position(|info| info.action == action).unwrap_or(0);
+        &self.keys[index]
+    }
+
+    /// All of an action's keys for the help popup, e.g. `q / Esc`.
+    pub fn label(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(Binding::label)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// The first key of an action, for footer hints.
+    pub fn hint(&self, action: Action) -> String {
+        match self.keys(action).first() {
+            Some(b) if b.0 == [Key { code: KeyCode::Enter, ctrl: false }] => "⏎".to_string(),
+            Some(b) => b.label(),
+            None => String::new(),
+        }
+    }
+}
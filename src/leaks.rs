@@ -0,0 +1,95 @@
+//! Slow memory leak heuristic.
+//!
+//! A point-in-time view can't tell a process that is merely big from one
+//! that keeps growing. Every few seconds each process's resident memory is
+//! sampled into a rolling window; once the window spans a couple of minutes,
+//! a process whose RSS never went down over it and grew faster than the
+//! configured slope is listed in the Insights panel. Any drop within the
+//! window clears the flag, so caches that get trimmed aren't reported.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Sample every 5s, or every tick at slower refresh rates.
+pub const LEAK_SAMPLE: Duration = Duration::from_secs(5);
+/// Samples kept per process: five minutes of history at 5s.
+const WINDOW: usize = 60;
+/// Samples needed before a process can be judged, so startup growth isn't
+/// mistaken for a leak.
+const MIN_SAMPLES: usize = 24;
+
+pub struct LeakSuspect {
+    pub pid: u32,
+    pub name: String,
+    pub memory: u64,
+    /// Average growth over the window, bytes per minute
+    pub growth_per_min: f64,
+    /// How long the growth has been observed, seconds
+    pub window_secs: u64,
+}
+
+struct Track {
+    name: String,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+#[derive(Default)]
+pub struct LeakDetector {
+    tracks: HashMap<u32, Track>,
+}
+
+impl LeakDetector {
+    /// Record the current RSS of each `(pid, name, memory)`; processes that
+    /// are gone are forgotten, and a reused PID starts a fresh track.
+    pub fn sample(&mut self, processes: impl Iterator<Item = (u32, String, u64)>) {
+        let now = Instant::now();
+        let mut seen = HashMap::with_capacity(self.tracks.len());
+        for (pid, name, memory) in processes {
+            let mut track = self
+                .tracks
+                .remove(&pid)
+                .filter(|t| t.name == name)
+                .unwrap_or_else(|| Track {
+                    name,
+                    samples: VecDeque::with_capacity(WINDOW),
+                });
+            if track.samples.len() == WINDOW {
+                track.samples.pop_front();
+            }
+            track.samples.push_back((now, memory));
+            seen.insert(pid, track);
+        }
+        self.tracks = seen;
+    }
+
+    /// Processes growing monotonically by at least `min_slope` bytes per
+    /// minute, fastest first.
+    pub fn suspects(&self, min_slope: f64) -> Vec<LeakSuspect> {
+        let mut suspects: Vec<LeakSuspect> = self
+            .tracks
+            .iter()
+            .filter(|(_, t)| t.samples.len() >= MIN_SAMPLES)
+            .filter(|(_, t)| {
+                t.samples
+                    .iter()
+                    .zip(t.samples.iter().skip(1))
+                    .all(|((_, a), (_, b))| b >= a)
+            })
+            .filter_map(|(&pid, t)| {
+                let (start, first) = *t.samples.front()?;
+                let (end, last) = *t.samples.back()?;
+                let secs = end.duration_since(start).as_secs_f64();
+                let growth_per_min = last.saturating_sub(first) as f64 / secs * 60.0;
+                (secs > 0.0 && growth_per_min >= min_slope).then(|| LeakSuspect {
+                    pid,
+                    name: t.name.clone(),
+                    memory: last,
+                    growth_per_min,
+                    window_secs: secs as u64,
+                })
+            })
+            .collect();
+        suspects.sort_by(|a, b| b.growth_per_min.total_cmp(&a.growth_per_min));
+        suspects
+    }
+}
@@ -0,0 +1,147 @@
+//! Linux GPU monitoring via sysfs (`/sys/class/drm/card*/device`).
+//!
+//! amdgpu exposes most of its telemetry as plain text files: busy
+//! percentages, VRAM counters, DPM clock tables and an hwmon directory
+//! with temperatures, fan and power readings. These helpers read them
+//! without any extra dependencies or elevated privileges.
+//!
+//! Intel i915/Xe don't provide a busy percentage, so utilization is derived
+//! from RC6 (idle) residency between two samples, the same signal
+//! `intel_gpu_top` shows as "RC6".
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Read a sysfs file and parse its trimmed contents.
+pub fn read_value<T: std::str::FromStr>(path: &Path) -> Option<T> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// First `hwmon/hwmonN` directory under a DRM device, if any.
+pub fn hwmon_dir(device_path: &Path) -> Option<PathBuf> {
+    fs::read_dir(device_path.join("hwmon"))
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.is_dir())
+}
+
+/// Read a temperature (°C) whose `tempN_label` matches `label`,
+/// e.g. "edge", "junction" or "mem" on amdgpu.
+pub fn temp_by_label(hwmon: &Path, label: &str) -> Option<u32> {
+    (1..=8).find_map(|n| {
+        let name = fs::read_to_string(hwmon.join(format!("temp{n}_label"))).ok()?;
+        if name.trim() != label {
+            return None;
+        }
+        read_value::<u32>(&hwmon.join(format!("temp{n}_input"))).map(|t| t / 1000)
+    })
+}
+
+/// Current clock (MHz) from an amdgpu DPM table such as `pp_dpm_sclk`.
+///
+/// The active level is marked with a trailing `*`:
+/// ```text
+/// 0: 500Mhz
+/// 1: 1800Mhz *
+/// ```
+pub fn dpm_clock_mhz(path: &Path) -> Option<u32> {
+    let table = fs::read_to_string(path).ok()?;
+    parse_dpm_clock(&table)
+}
+
+fn parse_dpm_clock(table: &str) -> Option<u32> {
+    let line = table.lines().find(|l| l.trim_end().ends_with('*'))?;
+    let (_, rest) = line.split_once(':')?;
+    let mhz = rest.trim().trim_end_matches('*').trim();
+    mhz.to_lowercase().trim_end_matches("mhz").trim().parse().ok()
+}
+
+/// Readings taken from a device's hwmon directory.
+#[derive(Default)]
+pub struct HwmonReadings {
+    pub temperature: Option<u32>,
+    pub temp_junction: Option<u32>,
+    pub temp_memory: Option<u32>,
+    pub fan_rpm: Option<u32>,
+    pub power_mw: Option<u32>,
+    pub power_cap_mw: Option<u32>,
+}
+
+pub fn read_hwmon(device_path: &Path) -> HwmonReadings {
+    let Some(hwmon) = hwmon_dir(device_path) else {
+        return HwmonReadings::default();
+    };
+
+    // power1_average is reported by older kernels, power1_input by newer ones
+    let power_uw = read_value::<u64>(&hwmon.join("power1_average"))
+        .or_else(|| read_value::<u64>(&hwmon.join("power1_input")));
+
+    HwmonReadings {
+        // temp1 is the edge sensor on amdgpu and the only sensor elsewhere
+        temperature: read_value::<u32>(&hwmon.join("temp1_input")).map(|t| t / 1000),
+        temp_junction: temp_by_label(&hwmon, "junction"),
+        temp_memory: temp_by_label(&hwmon, "mem"),
+        fan_rpm: read_value(&hwmon.join("fan1_input")),
+        power_mw: power_uw.map(|uw| (uw / 1000) as u32), // microwatts → milliwatts
+        power_cap_mw: read_value::<u64>(&hwmon.join("power1_cap")).map(|uw| (uw / 1000) as u32),
+    }
+}
+
+/// Kernel driver bound to a DRM device, e.g. "amdgpu", "i915" or "xe".
+pub fn driver_name(device_path: &Path) -> Option<String> {
+    let target = fs::read_link(device_path.join("driver")).ok()?;
+    Some(target.file_name()?.to_string_lossy().to_string())
+}
+
+pub fn is_intel_driver(driver: &str) -> bool {
+    driver == "i915" || driver == "xe"
+}
+
+/// Candidate locations of the RC6 residency counter (milliseconds).
+fn rc6_residency_ms(card_path: &Path, device_path: &Path) -> Option<u64> {
+    [
+        card_path.join("gt/gt0/rc6_residency_ms"),
+        card_path.join("power/rc6_residency_ms"),
+        device_path.join("tile0/gt0/gtidle/idle_residency_ms"),
+    ]
+    .iter()
+    .find_map(|p| read_value(p))
+}
+
+/// Actual GPU frequency (MHz) for i915 or Xe.
+pub fn intel_act_freq_mhz(card_path: &Path, device_path: &Path) -> Option<u32> {
+    [
+        card_path.join("gt/gt0/rps_act_freq_mhz"),
+        card_path.join("gt_act_freq_mhz"),
+        device_path.join("tile0/gt0/freq0/act_freq"),
+    ]
+    .iter()
+    .find_map(|p| read_value(p))
+}
+
+/// Tracks RC6 residency per card between ticks to derive busy percentage.
+#[derive(Default)]
+pub struct IntelGpuSampler {
+    prev: HashMap<PathBuf, (u64, Instant)>,
+}
+
+impl IntelGpuSampler {
+    /// Busy percentage since the previous call for this card.
+    /// First call for a card returns None (needs two samples for a delta).
+    pub fn utilization(&mut self, card_path: &Path, device_path: &Path) -> Option<u32> {
+        let idle_ms = rc6_residency_ms(card_path, device_path)?;
+        let now = Instant::now();
+        let prev = self.prev.insert(card_path.to_path_buf(), (idle_ms, now));
+
+        let (prev_idle, prev_time) = prev?;
+        let wall_ms = now.duration_since(prev_time).as_millis() as f64;
+        if wall_ms <= 0.0 {
+            return None;
+        }
+        let idle_ratio = (idle_ms.saturating_sub(prev_idle) as f64 / wall_ms).min(1.0);
+        Some(((1.0 - idle_ratio) * 100.0).round() as u32)
+    }
+}
@@ -2,14 +2,23 @@
 //!
 //! This module uses undocumented IOReport functions from IOKit to read
 //! GPU utilization, frequency, and power data without requiring sudo.
-//! Based on the approach used by macmon and socpowerbud projects.
+//! The same subscription also covers the CPU cluster performance states
+//! and the Energy Model, giving E-core vs P-core utilization, cluster
+//! frequency and package power. Based on the approach used by macmon and
+//! socpowerbud projects.
 //!
 //! WARNING: This uses private Apple APIs that may break on future macOS versions.
+//! libIOReport is therefore opened at runtime rather than linked, so the
+//! binary still starts where it is missing and simply skips these metrics.
 
 #![allow(non_snake_case, non_upper_case_globals, dead_code)]
 
 use std::ffi::c_void;
 use std::ptr;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::app::CpuCluster;
 
 // ─── Core Foundation types ───────────────────────────────────────────
 
@@ -20,6 +29,7 @@ type CFStringRef = *const c_void;
 type CFNumberRef = *const c_void;
 type CFAllocatorRef = *const c_void;
 type CFArrayRef = *const c_void;
+type CFDataRef = *const c_void;
 type CFIndex = isize;
 
 const kCFAllocatorDefault: CFAllocatorRef = ptr::null();
@@ -57,8 +67,68 @@ extern "C" {
         cStr: *const u8,
         encoding: u32,
     ) -> CFStringRef;
+    fn CFDictionaryGetCount(theDict: CFDictionaryRef) -> CFIndex;
+    fn CFDictionaryCreateMutableCopy(
+        alloc: CFAllocatorRef,
+        capacity: CFIndex,
+        theDict: CFDictionaryRef,
+    ) -> CFMutableDictionaryRef;
+    fn CFDataGetLength(theData: CFDataRef) -> CFIndex;
+    fn CFDataGetBytePtr(theData: CFDataRef) -> *const u8;
+
+    // IOKit for temperature
+    fn IOServiceMatching(name: *const u8) -> CFMutableDictionaryRef;
+    fn IOServiceGetMatchingServices(
+        mainPort: u32,
+        matching: CFMutableDictionaryRef,
+        existing: *mut u32,
+    ) -> i32;
+    fn IOIteratorNext(iterator: u32) -> u32;
+    fn IORegistryEntryCreateCFProperties(
+        entry: u32,
+        properties: *mut CFMutableDictionaryRef,
+        allocator: CFAllocatorRef,
+        options: u32,
+    ) -> i32;
+    fn IOObjectRelease(object: u32) -> i32;
+    fn IORegistryEntryGetName(entry: u32, name: *mut u8) -> i32;
+}
+
+// ─── IOReport private API, resolved at runtime ───────────────────────
+
+const IOREPORT_PATH: &str = "/usr/lib/libIOReport.dylib";
+
+/// Declares the IOReport functions as fields of a table loaded from the
+/// dylib, plus a same-named wrapper for each so call sites read like
+/// ordinary FFI. The wrappers may only be called once `ioreport()` has
+/// returned `Some`, which `AppleGpuSampler::new` checks.
+macro_rules! ioreport_api {
+    ($(fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        struct IoReport {
+            _lib: libloading::Library,
+            $($name: unsafe extern "C" fn($($ty),*) $(-> $ret)?,)*
+        }
+
+        impl IoReport {
+            unsafe fn load() -> Option<Self> {
+                unsafe {
+                    let lib = libloading::Library::new(IOREPORT_PATH).ok()?;
+                    Some(Self {
+                        $($name: *lib.get(concat!(stringify!($name), "\0").as_bytes()).ok()?,)*
+                        _lib: lib,
+                    })
+                }
+            }
+        }
+
+        $(unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+            let api = ioreport().expect("IOReport called before it was loaded");
+            unsafe { (api.$name)($($arg),*) }
+        })*
+    };
+}
 
-    // IOReport private API (from /usr/lib/libIOReport.dylib)
+ioreport_api! {
     fn IOReportCopyChannelsInGroup(
         group: CFStringRef,
         subgroup: CFStringRef,
@@ -66,7 +136,6 @@ extern "C" {
         b: u64,
         c: u64,
     ) -> CFDictionaryRef;
-
     fn IOReportCreateSubscription(
         a: CFTypeRef,
         channels: CFDictionaryRef,
@@ -74,39 +143,32 @@ extern "C" {
         c: u64,
         d: CFTypeRef,
     ) -> IOReportSubscriptionRef;
-
     fn IOReportCreateSamples(
         subscription: IOReportSubscriptionRef,
         channels: CFMutableDictionaryRef,
         a: CFTypeRef,
     ) -> CFDictionaryRef;
-
     fn IOReportCreateSamplesDelta(
         prev: CFDictionaryRef,
         current: CFDictionaryRef,
         a: CFTypeRef,
     ) -> CFDictionaryRef;
-
     fn IOReportChannelGetGroup(channel: CFDictionaryRef) -> CFStringRef;
     fn IOReportChannelGetSubGroup(channel: CFDictionaryRef) -> CFStringRef;
     fn IOReportChannelGetChannelName(channel: CFDictionaryRef) -> CFStringRef;
     fn IOReportSimpleGetIntegerValue(channel: CFDictionaryRef, a: *mut i32) -> i64;
+    fn IOReportChannelGetUnitLabel(channel: CFDictionaryRef) -> CFStringRef;
+    fn IOReportMergeChannels(a: CFDictionaryRef, b: CFDictionaryRef, nil: CFTypeRef);
+    fn IOReportStateGetCount(channel: CFDictionaryRef) -> i32;
+    fn IOReportStateGetNameForIndex(channel: CFDictionaryRef, index: i32) -> CFStringRef;
+    fn IOReportStateGetResidency(channel: CFDictionaryRef, index: i32) -> i64;
+}
 
-    // IOKit for temperature
-    fn IOServiceMatching(name: *const u8) -> CFMutableDictionaryRef;
-    fn IOServiceGetMatchingServices(
-        mainPort: u32,
-        matching: CFMutableDictionaryRef,
-        existing: *mut u32,
-    ) -> i32;
-    fn IOIteratorNext(iterator: u32) -> u32;
-    fn IORegistryEntryCreateCFProperties(
-        entry: u32,
-        properties: *mut CFMutableDictionaryRef,
-        allocator: CFAllocatorRef,
-        options: u32,
-    ) -> i32;
-    fn IOObjectRelease(object: u32) -> i32;
+/// The IOReport table, opened on first use; `None` when the dylib or one
+/// of its symbols is missing.
+fn ioreport() -> Option<&'static IoReport> {
+    static IOREPORT: OnceLock<Option<IoReport>> = OnceLock::new();
+    IOREPORT.get_or_init(|| unsafe { IoReport::load() }).as_ref()
 }
 
 const kCFStringEncodingUTF8: u32 = 0x08000100;
@@ -169,6 +231,9 @@ pub struct AppleGpuSampler {
     subscription: IOReportSubscriptionRef,
     channels: CFMutableDictionaryRef,
     prev_sample: Option<CFDictionaryRef>,
+    prev_time: Instant,
+    ecpu_freqs: Vec<u32>, // DVFS table for E-clusters, MHz
+    pcpu_freqs: Vec<u32>, // DVFS table for P-clusters, MHz
 }
 
 /// Metrics returned from a single sample delta
@@ -178,49 +243,66 @@ pub struct AppleGpuMetrics {
     pub temperature: u32,     // degrees C
     pub power_mw: Option<u32>, // milliwatts
     pub freq_mhz: Option<u32>,
+    pub cpu_clusters: Vec<CpuCluster>,
+    pub cpu_power_mw: Option<u32>,
+    pub ane_power_mw: Option<u32>,
+    pub package_power_mw: Option<u32>,
 }
 
 impl AppleGpuSampler {
     /// Initialize IOReport subscription for GPU channels.
     /// Returns None if IOReport is unavailable (non-Apple Silicon, etc.)
     pub fn new() -> Option<Self> {
+        ioreport()?;
         unsafe {
-            let group = cfstr("GPU");
-            let channels = IOReportCopyChannelsInGroup(group, ptr::null(), 0, 0, 0);
-            CFRelease(group);
-
+            let channels = copy_channels("GPU", None);
             if channels.is_null() {
                 return None;
             }
 
-            // Also subscribe to Energy Model channels for power data
-            let energy_group = cfstr("Energy Model");
-            let energy_channels =
-                IOReportCopyChannelsInGroup(energy_group, ptr::null(), 0, 0, 0);
-            CFRelease(energy_group);
+            // Merge Energy Model (power) and CPU cluster performance states
+            // into the same subscription so one delta covers everything
+            for (group, subgroup) in [
+                ("Energy Model", None),
+                ("CPU Stats", Some("CPU Complex Performance States")),
+            ] {
+                let extra = copy_channels(group, subgroup);
+                if !extra.is_null() {
+                    IOReportMergeChannels(channels, extra, ptr::null());
+                    CFRelease(extra as CFTypeRef);
+                }
+            }
+
+            let size = CFDictionaryGetCount(channels);
+            let merged = CFDictionaryCreateMutableCopy(kCFAllocatorDefault, size, channels);
+            CFRelease(channels as CFTypeRef);
+            if merged.is_null() {
+                return None;
+            }
 
             let mut sub_channels: CFMutableDictionaryRef = ptr::null_mut();
             let subscription = IOReportCreateSubscription(
                 ptr::null(),
-                channels,
+                merged,
                 &mut sub_channels,
                 0,
                 ptr::null(),
             );
-
-            CFRelease(channels as CFTypeRef);
-            if !energy_channels.is_null() {
-                CFRelease(energy_channels as CFTypeRef);
-            }
+            CFRelease(merged as CFTypeRef);
 
             if subscription.is_null() || sub_channels.is_null() {
                 return None;
             }
 
+            let (ecpu_freqs, pcpu_freqs) = read_cpu_dvfs_tables();
+
             Some(Self {
                 subscription,
                 channels: sub_channels,
                 prev_sample: None,
+                prev_time: Instant::now(),
+                ecpu_freqs,
+                pcpu_freqs,
             })
         }
     }
@@ -235,6 +317,8 @@ impl AppleGpuSampler {
                 return None;
             }
 
+            let now = Instant::now();
+            let elapsed_secs = now.duration_since(self.prev_time).as_secs_f64();
             let result = if let Some(prev) = self.prev_sample {
                 let delta = IOReportCreateSamplesDelta(prev, current, ptr::null());
                 CFRelease(prev);
@@ -242,7 +326,7 @@ impl AppleGpuSampler {
                 if delta.is_null() {
                     None
                 } else {
-                    let metrics = self.parse_delta(delta);
+                    let metrics = self.parse_delta(delta, elapsed_secs);
                     CFRelease(delta);
                     Some(metrics)
                 }
@@ -251,6 +335,7 @@ impl AppleGpuSampler {
             };
 
             self.prev_sample = Some(current);
+            self.prev_time = now;
 
             // Get temperature separately via IOKit
             let temperature = read_gpu_temperature().unwrap_or(0);
@@ -262,12 +347,16 @@ impl AppleGpuSampler {
         }
     }
 
-    unsafe fn parse_delta(&self, delta: CFDictionaryRef) -> AppleGpuMetrics {
+    unsafe fn parse_delta(&self, delta: CFDictionaryRef, elapsed_secs: f64) -> AppleGpuMetrics {
         let mut gpu_name = String::from("Apple GPU");
         let mut gpu_busy: i64 = 0;
         let mut gpu_total: i64 = 0;
         let mut power_mw: Option<u32> = None;
         let mut freq_mhz: Option<u32> = None;
+        let mut cpu_clusters: Vec<CpuCluster> = Vec::new();
+        let mut cluster_power: Vec<(bool, u32)> = Vec::new(); // (is_efficiency, mW)
+        let mut cpu_power_mw: Option<u32> = None;
+        let mut ane_power_mw: Option<u32> = None;
 
         // The delta is a CFDictionary with an "IOReportChannels" array
         let channels_key = cfstr("IOReportChannels");
@@ -281,6 +370,10 @@ impl AppleGpuSampler {
                 temperature: 0,
                 power_mw: None,
                 freq_mhz: None,
+                cpu_clusters,
+                cpu_power_mw: None,
+                ane_power_mw: None,
+                package_power_mw: None,
             };
         }
 
@@ -315,13 +408,45 @@ impl AppleGpuSampler {
                 }
             }
 
-            if group == "Energy Model" && name.contains("GPU") {
+            if group == "Energy Model" {
+                // Energy over the sample interval; mJ per second is mW
                 let val = IOReportSimpleGetIntegerValue(channel, ptr::null_mut());
-                if val > 0 {
-                    power_mw = Some(val as u32);
+                let unit = cfstring_to_string(IOReportChannelGetUnitLabel(channel))
+                    .unwrap_or_default();
+                let mw = energy_to_mw(val, &unit, elapsed_secs);
+                if name == "CPU Energy" {
+                    cpu_power_mw = mw;
+                } else if name.starts_with("ANE") {
+                    // Some chips split the Neural Engine into ANE0, ANE1, ...
+                    if let Some(mw) = mw {
+                        ane_power_mw = Some(ane_power_mw.unwrap_or(0) + mw);
+                    }
+                } else if name.contains("GPU") {
+                    if let Some(mw) = mw {
+                        power_mw = Some(mw);
+                    }
+                } else if name.ends_with("_CPU") && name.contains("ACC") {
+                    // "EACC_CPU", "PACC0_CPU", ... — per-cluster energy
+                    if let Some(mw) = mw {
+                        cluster_power.push((name.starts_with('E'), mw));
+                    }
                 }
             }
 
+            if group == "CPU Stats" && subgroup == "CPU Complex Performance States" {
+                // Cluster channels are named "ECPU", "PCPU", "PCPU1", ...
+                let efficiency = name.starts_with('E');
+                let freqs = if efficiency { &self.ecpu_freqs } else { &self.pcpu_freqs };
+                let (utilization, freq) = cluster_residency(channel, freqs);
+                cpu_clusters.push(CpuCluster {
+                    name: name.clone(),
+                    efficiency,
+                    utilization,
+                    freq_mhz: freq,
+                    power_mw: None,
+                });
+            }
+
             // Try to get a better GPU name
             if group == "GPU" && gpu_name == "Apple GPU" && !name.is_empty() {
                 if name.starts_with("Apple") || name.starts_with("M") {
@@ -336,12 +461,31 @@ impl AppleGpuSampler {
             0
         };
 
+        // Energy channels don't name clusters the same way as the state
+        // channels, so pair them up in order within each cluster type
+        for efficiency in [true, false] {
+            let powers = cluster_power.iter().filter(|(e, _)| *e == efficiency);
+            let clusters = cpu_clusters.iter_mut().filter(|c| c.efficiency == efficiency);
+            for (cluster, (_, mw)) in clusters.zip(powers) {
+                cluster.power_mw = Some(*mw);
+            }
+        }
+
+        let package_power_mw = [cpu_power_mw, power_mw, ane_power_mw]
+            .into_iter()
+            .flatten()
+            .reduce(|a, b| a + b);
+
         AppleGpuMetrics {
             gpu_name,
             utilization: utilization.min(100),
             temperature: 0, // filled in by caller
             power_mw,
             freq_mhz,
+            cpu_clusters,
+            cpu_power_mw,
+            ane_power_mw,
+            package_power_mw,
         }
     }
 }
@@ -357,6 +501,130 @@ impl Drop for AppleGpuSampler {
     }
 }
 
+unsafe fn copy_channels(group: &str, subgroup: Option<&str>) -> CFDictionaryRef {
+    let group_ref = cfstr(group);
+    let subgroup_ref = subgroup.map(|s| cfstr(s)).unwrap_or(ptr::null());
+    let channels = IOReportCopyChannelsInGroup(group_ref, subgroup_ref, 0, 0, 0);
+    CFRelease(group_ref);
+    if !subgroup_ref.is_null() {
+        CFRelease(subgroup_ref);
+    }
+    channels
+}
+
+/// Convert an Energy Model delta to milliwatts using its unit label.
+fn energy_to_mw(value: i64, unit: &str, elapsed_secs: f64) -> Option<u32> {
+    if value <= 0 || elapsed_secs <= 0.0 {
+        return None;
+    }
+    let millijoules = match unit.trim() {
+        "mJ" => value as f64,
+        "uJ" => value as f64 / 1_000.0,
+        "nJ" => value as f64 / 1_000_000.0,
+        _ => return None,
+    };
+    Some((millijoules / elapsed_secs) as u32)
+}
+
+/// Active residency (0-100%) and residency-weighted frequency of a CPU
+/// cluster state channel. Idle states come first, followed by one state
+/// per DVFS level in the same order as the frequency table.
+unsafe fn cluster_residency(channel: CFDictionaryRef, freqs: &[u32]) -> (u32, Option<u32>) {
+    let count = IOReportStateGetCount(channel);
+    let mut total: i64 = 0;
+    let mut active: i64 = 0;
+    let mut weighted_freq: f64 = 0.0;
+    let mut level = 0usize;
+
+    for idx in 0..count {
+        let state = cfstring_to_string(IOReportStateGetNameForIndex(channel, idx))
+            .unwrap_or_default();
+        let residency = IOReportStateGetResidency(channel, idx);
+        total += residency;
+        if matches!(state.as_str(), "IDLE" | "DOWN" | "OFF") {
+            continue;
+        }
+        active += residency;
+        if let Some(&mhz) = freqs.get(level) {
+            weighted_freq += mhz as f64 * residency as f64;
+        }
+        level += 1;
+    }
+
+    if total <= 0 {
+        return (0, None);
+    }
+    let utilization = ((active as f64 / total as f64) * 100.0).round() as u32;
+    let freq = (active > 0 && !freqs.is_empty())
+        .then(|| (weighted_freq / active as f64) as u32);
+    (utilization.min(100), freq)
+}
+
+/// Read the E- and P-cluster DVFS frequency tables from the `pmgr` IORegistry
+/// entry (`voltage-states1-sram` / `voltage-states5-sram`).
+fn read_cpu_dvfs_tables() -> (Vec<u32>, Vec<u32>) {
+    unsafe {
+        let matching = IOServiceMatching(b"AppleARMIODevice\0".as_ptr());
+        if matching.is_null() {
+            return (Vec::new(), Vec::new());
+        }
+        let mut iterator: u32 = 0;
+        if IOServiceGetMatchingServices(0, matching, &mut iterator) != 0 {
+            return (Vec::new(), Vec::new());
+        }
+
+        let mut tables = (Vec::new(), Vec::new());
+        loop {
+            let entry = IOIteratorNext(iterator);
+            if entry == 0 {
+                break;
+            }
+
+            let mut name_buf = [0u8; 128];
+            let is_pmgr = IORegistryEntryGetName(entry, name_buf.as_mut_ptr()) == 0
+                && name_buf.starts_with(b"pmgr\0");
+
+            let mut props: CFMutableDictionaryRef = ptr::null_mut();
+            if is_pmgr
+                && IORegistryEntryCreateCFProperties(entry, &mut props, kCFAllocatorDefault, 0) == 0
+                && !props.is_null()
+            {
+                tables = (
+                    dvfs_table(props as CFDictionaryRef, "voltage-states1-sram"),
+                    dvfs_table(props as CFDictionaryRef, "voltage-states5-sram"),
+                );
+                CFRelease(props as CFTypeRef);
+            }
+            IOObjectRelease(entry);
+            if is_pmgr {
+                break;
+            }
+        }
+        IOObjectRelease(iterator);
+        tables
+    }
+}
+
+/// Decode a DVFS table: pairs of little-endian u32 (frequency, voltage).
+/// Frequencies are in Hz on M1–M3 and kHz on later chips.
+unsafe fn dvfs_table(props: CFDictionaryRef, key: &str) -> Vec<u32> {
+    let key_ref = cfstr(key);
+    let data = CFDictionaryGetValue(props, key_ref);
+    CFRelease(key_ref);
+    if data.is_null() {
+        return Vec::new();
+    }
+
+    let len = CFDataGetLength(data) as usize;
+    let bytes = std::slice::from_raw_parts(CFDataGetBytePtr(data), len);
+    bytes
+        .chunks_exact(8)
+        .map(|pair| u32::from_le_bytes([pair[0], pair[1], pair[2], pair[3]]))
+        .filter(|&f| f > 0)
+        .map(|f| if f >= 10_000_000 { f / 1_000_000 } else { f / 1_000 })
+        .collect()
+}
+
 // ─── Temperature via IOKit ───────────────────────────────────────────
 
 /// Read GPU temperature from AppleSMC via IOKit.
@@ -1,99 +1,429 @@
+mod agent;
+mod alerts;
+mod api;
 mod app;
+mod cli;
+mod clipboard;
+mod config;
+mod connections;
+mod daemon;
+#[cfg(target_os = "linux")]
+mod diskstats;
+mod events;
+mod export;
+mod firewall;
+mod fsinfo;
+mod geoip;
+mod ifinfo;
+mod hardware;
+mod history;
+mod hooks;
+mod json;
+mod keymap;
+mod leaks;
+mod meminfo;
+mod metrics;
+#[cfg(feature = "net-accounting")]
+mod netacct;
+#[cfg(feature = "nvml")]
+mod nvml;
+mod nvidia_smi;
+mod peaks;
+mod ping;
+#[cfg(target_os = "linux")]
+mod procmem;
+mod profiler;
+mod rdns;
+mod remote;
+mod sample;
+#[cfg(target_os = "linux")]
+mod linux_gpu;
 #[cfg(target_os = "macos")]
 mod macos_gpu;
+mod smart;
+mod snapshot;
+mod spawn;
+mod status_line;
+mod syslog;
+mod summary;
+mod sysctl;
+mod tasks;
 mod theme;
 mod ui;
+mod watchdog;
+mod wifi;
+#[cfg(target_os = "windows")]
+mod windows_gpu;
 
 use std::io;
 use std::time::{Duration, Instant};
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEventKind, KeyModifiers,
+};
+use crossterm::execute;
 use ratatui::DefaultTerminal;
 
 use app::{App, InputMode};
+use cli::Cli;
+use config::Config;
+use keymap::{Action, Key, Lookup};
 
 fn main() -> io::Result<()> {
+    let cli = match Cli::parse() {
+        cli::Action::Run(cli) => cli,
+        cli::Action::Exit(msg) => {
+            println!("{msg}");
+            return Ok(());
+        }
+        cli::Action::Error(msg) => {
+            eprintln!("{msg}");
+            std::process::exit(2);
+        }
+        cli::Action::Snapshot(path) => return write_snapshot(path.as_deref()),
+        cli::Action::TmuxStatus => {
+            println!("{}", status_line::render(&Config::load()));
+            return Ok(());
+        }
+        cli::Action::Diff(a, b) => match snapshot::diff_files(&a, &b) {
+            Ok(report) => {
+                println!("{report}");
+                return Ok(());
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        },
+    };
+
+    if cli.daemon {
+        if let Err(e) = daemon::run(&cli) {
+            eprintln!("rustmonitor: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Before taking over the terminal, so a missing ssh is a plain error
+    let remote = match (&cli.remote, &cli.connect) {
+        (Some(host), _) => match remote::RemoteFeed::ssh(host, &Config::load().remote_command) {
+            Ok(feed) => Some(feed),
+            Err(e) => {
+                eprintln!("rustmonitor: can't run ssh: {e}");
+                std::process::exit(1);
+            }
+        },
+        (None, Some(addr)) => Some(remote::RemoteFeed::connect(addr)),
+        (None, None) => None,
+    };
+
     let terminal = ratatui::init();
-    let result = run(terminal);
+    // Pasted text arrives as one Event::Paste instead of a burst of keys
+    let _ = execute!(io::stdout(), EnableBracketedPaste);
+    let result = run(terminal, &cli, remote);
+    let _ = execute!(io::stdout(), DisableBracketedPaste);
     ratatui::restore();
-    result
+    let exit = result?;
+    if let Some(error) = exit.history_error {
+        eprintln!("rustmonitor: can't save graph history: {error}");
+    }
+    if let Some(summary) = exit.summary {
+        println!("{summary}");
+    }
+    Ok(())
 }
 
-fn run(mut terminal: DefaultTerminal) -> io::Result<()> {
-    let mut app = App::new();
-    let tick_rate = Duration::from_millis(500);
+/// Sample once (two refreshes, so CPU usage is meaningful) and write JSON.
+fn write_snapshot(path: Option<&str>) -> io::Result<()> {
+    let mut app = App::new(Config::load(), &Cli::default());
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.max(Duration::from_millis(500)));
+    app.tick();
+    let json = snapshot::Snapshot::capture(&app).to_json().to_pretty();
+    match path {
+        Some(path) => {
+            std::fs::write(path, json + "\n")?;
+            eprintln!("Snapshot written to {path}");
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+/// What's left to print once the terminal is restored.
+struct Exit {
+    /// The session summary, when one was asked for
+    summary: Option<String>,
+    history_error: Option<io::Error>,
+}
+
+fn run(
+    mut terminal: DefaultTerminal,
+    cli: &Cli,
+    remote: Option<remote::RemoteFeed>,
+) -> io::Result<Exit> {
+    let config = Config::load();
+    let want_summary = config.exit_summary || cli.exit_summary;
+    // A remote host's graphs aren't this machine's history
+    let persist_history = config.persist_history && remote.is_none();
+    let mut attention = alerts::Attention::new(config.attention, config.alert_sound.clone());
+    let watchdog = watchdog::Watchdog::new(config.watchdog_file.clone());
+    let api_listen = cli.api_listen.clone().or_else(|| config.api_listen.clone());
+    let mut app = App::new(config, cli);
+    if let Some(feed) = remote {
+        app.attach_remote(feed);
+    }
+    if persist_history {
+        match history::load(&mut app) {
+            Ok(Some(ago)) => {
+                let ago = app::format_duration(ago.as_secs());
+                app.events.push(
+                    events::EventLevel::Info,
+                    format!("Graph history restored, saved {ago} ago"),
+                )
+            }
+            Ok(None) => {}
+            Err(e) => app.events.push(
+                events::EventLevel::Info,
+                format!("Graph history not restored: {e}"),
+            ),
+        }
+    }
+    let api = api_listen.and_then(|addr| match api::ApiServer::start(&addr) {
+        Ok(server) => {
+            app.events.push(
+                events::EventLevel::Info,
+                format!("API listening on http://{addr}{}", api::SNAPSHOT_PATH),
+            );
+            Some(server)
+        }
+        Err(e) => {
+            app.log_event(events::EventLevel::Error, format!("API: can't listen on {addr}: {e}"));
+            None
+        }
+    });
     let mut last_tick = Instant::now();
 
     loop {
+        app.poll_tasks();
         terminal.draw(|frame| ui::draw(frame, &mut app))?;
 
-        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
-        if event::poll(timeout)?
-            && let Event::Key(key) = event::read()?
-        {
-                if key.kind != KeyEventKind::Press {
-                    continue;
+        let mut timeout = app.tick_rate.saturating_sub(last_tick.elapsed());
+        if app.tasks.has_visible() {
+            // Wake up often enough to animate the footer spinner
+            timeout = timeout.min(tasks::SPINNER_INTERVAL);
+        }
+        let event = if event::poll(timeout)? {
+            Some(event::read()?)
+        } else {
+            None
+        };
+        if let Some(Event::Paste(text)) = &event {
+            app.paste(text);
+        }
+        if let Some(Event::Key(key)) = event {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            if app.show_help {
+                match app.keymap.action(&key, app.active_tab) {
+                    Some(Action::Down) => app.scroll_help(1),
+                    Some(Action::Up) => app.scroll_help(-1),
+                    Some(Action::PageDown) => app.scroll_help(10),
+                    Some(Action::PageUp) => app.scroll_help(-10),
+                    Some(Action::HalfPageDown) => app.scroll_help(5),
+                    Some(Action::HalfPageUp) => app.scroll_help(-5),
+                    Some(Action::Top) => app.scroll_help(isize::MIN),
+                    Some(Action::Bottom) => app.scroll_help(isize::MAX),
+                    _ => app.toggle_help(),
                 }
+                continue;
+            }
 
-                if app.show_help {
-                    app.toggle_help();
-                    continue;
+            // Process detail popup
+            if app.show_process_detail {
+                match app.keymap.action(&key, app.active_tab) {
+                    Some(Action::Down) => app.detail_child_move(1),
+                    Some(Action::Up) => app.detail_child_move(-1),
+                    Some(Action::ProcessDetail) if app.jump_to_child() => {}
+                    Some(Action::Parent) => app.select_parent(),
+                    _ => app.close_detail(),
                 }
+                continue;
+            }
 
-                // Process detail popup
-                if app.show_process_detail {
-                    app.close_detail();
-                    continue;
+            if app.show_events {
+                match app.keymap.action(&key, app.active_tab) {
+                    Some(Action::Down) => app.events_scroll_down(),
+                    Some(Action::Up) => app.events_scroll_up(),
+                    _ => app.toggle_events(),
                 }
+                continue;
+            }
 
-                if app.kill_confirm.is_some() {
-                    match key.code {
-                        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_kill(),
-                        _ => app.cancel_kill(),
-                    }
-                    continue;
+            if app.profile_report.is_some() {
+                app.profile_report = None;
+                continue;
+            }
+
+            if app.disk_detail.is_some() {
+                app.disk_detail = None;
+                continue;
+            }
+
+            if app.interface_detail.is_some() {
+                app.interface_detail = None;
+                continue;
+            }
+
+            if app.show_command_output {
+                app.toggle_command_output();
+                continue;
+            }
+
+            if app.column_chooser.is_some() {
+                let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+                match key.code {
+                    KeyCode::Char(' ') | KeyCode::Enter => app.toggle_chosen_column(),
+                    KeyCode::Char('K') => app.move_chosen_column(false),
+                    KeyCode::Char('J') => app.move_chosen_column(true),
+                    KeyCode::Up if shift => app.move_chosen_column(false),
+                    KeyCode::Down if shift => app.move_chosen_column(true),
+                    _ => match app.keymap.action(&key, app.active_tab) {
+                        Some(Action::Down) => app.column_chooser_move(true),
+                        Some(Action::Up) => app.column_chooser_move(false),
+                        _ => app.toggle_column_chooser(),
+                    },
+                }
+                continue;
+            }
+
+            if app.kill_all_confirm.is_some() {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_kill_all(),
+                    _ => app.kill_all_confirm = None,
                 }
+                continue;
+            }
 
-                if app.input_mode == InputMode::Search {
-                    match key.code {
-                        KeyCode::Esc => app.exit_search(),
-                        KeyCode::Enter => {
-                            app.input_mode = InputMode::Normal;
-                        }
-                        KeyCode::Backspace => app.search_pop(),
-                        KeyCode::Char(c) => app.search_push(c),
-                        _ => {}
+            if app.kill_confirm.is_some() {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_kill(),
+                    _ => app.cancel_kill(),
+                }
+                continue;
+            }
+
+            if app.input_mode == InputMode::Search {
+                match key.code {
+                    KeyCode::Esc => app.exit_search(),
+                    KeyCode::Enter => {
+                        app.input_mode = InputMode::Normal;
                     }
-                    continue;
+                    KeyCode::Backspace => app.search_pop(),
+                    KeyCode::Tab => app.cycle_search_scope(),
+                    KeyCode::Char(c) => app.search_push(c),
+                    _ => {}
                 }
+                continue;
+            }
 
+            if app.input_mode == InputMode::Command {
                 match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                    KeyCode::Tab => app.next_tab(),
-                    KeyCode::BackTab => app.prev_tab(),
-                    KeyCode::Down | KeyCode::Char('j') => app.scroll_down(),
-                    KeyCode::Up | KeyCode::Char('k') => app.scroll_up(),
-                    KeyCode::PageDown => app.page_down(),
-                    KeyCode::PageUp => app.page_up(),
-                    KeyCode::Home => app.scroll_to_top(),
-                    KeyCode::End => app.scroll_to_bottom(),
-                    KeyCode::Char('s') => app.toggle_sort(),
-                    KeyCode::Char('t') => app.toggle_theme(),
-                    KeyCode::Char('?') => app.toggle_help(),
-                    KeyCode::Char('/') => app.enter_search(),
-                    KeyCode::Char('x') => app.request_kill(),
-                    KeyCode::Enter => app.show_detail(),
-                    KeyCode::Char('1') => app.active_tab = app::Tab::Overview,
-                    KeyCode::Char('2') => app.active_tab = app::Tab::Processes,
-                    KeyCode::Char('3') => app.active_tab = app::Tab::SystemInfo,
-                    KeyCode::Char('4') => app.active_tab = app::Tab::NetworkDetail,
+                    KeyCode::Esc => app.exit_command(),
+                    KeyCode::Enter => app.execute_command(),
+                    KeyCode::Backspace => app.command_pop(),
+                    KeyCode::Char(c) => app.command_push(c),
                     _ => {}
                 }
+                continue;
+            }
+
+            let pressed = Key::from_event(&key);
+            let action = match app.keymap.lookup(app.key_prefix.take(), pressed, app.active_tab) {
+                Lookup::Action(action) => action,
+                Lookup::Prefix => {
+                    app.key_prefix = Some(pressed);
+                    continue;
+                }
+                Lookup::None => continue,
+            };
+            match action {
+                Action::Quit => break,
+                Action::NextTab => app.next_tab(),
+                Action::PrevTab => app.prev_tab(),
+                Action::GoTab(tab) => app.active_tab = tab,
+                Action::Down => app.scroll_down(),
+                Action::Up => app.scroll_up(),
+                Action::PageDown => app.page_down(),
+                Action::PageUp => app.page_up(),
+                Action::HalfPageDown => app.half_page_down(),
+                Action::HalfPageUp => app.half_page_up(),
+                Action::NextMatch => app.next_match(true),
+                Action::PrevMatch => app.next_match(false),
+                Action::ScrollLeft => app.scroll_names(false),
+                Action::ScrollRight => app.scroll_names(true),
+                Action::Top => app.scroll_to_top(),
+                Action::Bottom => app.scroll_to_bottom(),
+                Action::Sort => app.toggle_sort(),
+                Action::Theme => app.toggle_theme(),
+                Action::LargeUi => app.toggle_large_ui(),
+                Action::RateUnits => app.toggle_rate_units(),
+                Action::CpuChart => app.toggle_line_chart(app::Graph::Cpu),
+                Action::MemoryChart => app.toggle_line_chart(app::Graph::Memory),
+                Action::NetworkChart => app.toggle_line_chart(app::Graph::Network),
+                Action::Events => app.toggle_events(),
+                Action::Snapshot => app.save_snapshot(),
+                Action::Faster => app.change_refresh_rate(true),
+                Action::Slower => app.change_refresh_rate(false),
+                Action::ZoomOut => app.zoom_graphs(true),
+                Action::ZoomIn => app.zoom_graphs(false),
+                Action::LongerHistory => app.change_history_len(true),
+                Action::ShorterHistory => app.change_history_len(false),
+                Action::Export => app.export_processes(),
+                Action::Columns => app.toggle_column_chooser(),
+                Action::Cmdline => app.toggle_cmdline(),
+                Action::Help => app.toggle_help(),
+                Action::Search => app.enter_search(),
+                Action::Kill => app.request_kill(),
+                Action::KillAll => app.request_kill_all(),
+                Action::Command => app.enter_command(),
+                Action::CommandOutput => app.toggle_command_output(),
+                Action::Profile => app.profile_selected(),
+                Action::DiskFilter => app.toggle_disk_filter(),
+                Action::InterfaceGraph => app.toggle_interface_graph(),
+                Action::ResetPeaks => app.reset_peaks(),
+                Action::ReverseDns => app.toggle_reverse_dns(),
+                Action::GpuProcess => app.jump_to_gpu_process(),
+                Action::DiskDetail => app.show_disk_detail(),
+                Action::InterfaceDetail => app.show_interface_detail(),
+                Action::ProcessDetail => app.show_detail(),
+                Action::Parent => app.select_parent(),
+                Action::Run => app.enter_run(),
+                Action::CopyPid => app.copy_selected(false),
+                Action::CopyCommand => app.copy_selected(true),
+            }
         }
 
-        if last_tick.elapsed() >= tick_rate {
+        if last_tick.elapsed() >= app.tick_rate {
             app.tick();
+            attention.signal(&app.alerts, &app.fired_alerts)?;
+            watchdog.ping();
+            if let Some(api) = &api {
+                api.publish(snapshot::Snapshot::capture(&app).to_json().to_pretty());
+            }
             last_tick = Instant::now();
         }
     }
+
+    if app.tasks.has_visible() {
+        app.set_status("Finishing background tasks…".into());
+        terminal.draw(|frame| ui::draw(frame, &mut app))?;
+    }
+    let abandoned = app.shutdown();
+    Ok(Exit {
+        summary: want_summary.then(|| summary::render(&app, &abandoned)),
+        history_error: persist_history.then(|| history::save(&app).err()).flatten(),
+    })
 }
@@ -0,0 +1,54 @@
+//! What "used" memory is made of, from `/proc/meminfo` (Linux only;
+//! elsewhere `read` finds nothing).
+//!
+//! Linux fills idle RAM with page cache, so a busy machine's used figure
+//! says little until it's split into what the kernel could hand back
+//! (buffers and cache) and what it couldn't. Committed memory is what
+//! processes have been promised, which can run past RAM long before
+//! anything is actually swapped.
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy)]
+pub struct MemoryBreakdown {
+    pub total: u64,
+    pub free: u64,
+    pub buffers: u64,
+    /// Page cache and reclaimable slab, less shared memory, as htop counts it
+    pub cached: u64,
+    pub available: u64,
+    pub committed: u64,
+    /// What the kernel will commit under strict overcommit
+    pub commit_limit: u64,
+}
+
+impl MemoryBreakdown {
+    /// Neither free nor reclaimable: process memory, kernel and shmem.
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.free + self.buffers + self.cached)
+    }
+}
+
+pub fn read() -> Option<MemoryBreakdown> {
+    let text = std::fs::read_to_string("/proc/meminfo").ok()?;
+    // "Cached:          123456 kB"
+    let fields: HashMap<&str, u64> = text
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let kb = rest.split_whitespace().next()?.parse::<u64>().ok()?;
+            Some((key, kb * 1024))
+        })
+        .collect();
+    let field = |key: &str| fields.get(key).copied();
+    Some(MemoryBreakdown {
+        total: field("MemTotal")?,
+        free: field("MemFree")?,
+        buffers: field("Buffers").unwrap_or(0),
+        cached: (field("Cached").unwrap_or(0) + field("SReclaimable").unwrap_or(0))
+            .saturating_sub(field("Shmem").unwrap_or(0)),
+        available: field("MemAvailable")?,
+        committed: field("Committed_AS").unwrap_or(0),
+        commit_limit: field("CommitLimit").unwrap_or(0),
+    })
+}
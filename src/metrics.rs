@@ -0,0 +1,326 @@
+//! Push samples to an existing metrics pipeline so a dashboard can chart a
+//! machine where only RustMonitor runs. `metrics_output` picks the target:
+//!
+//! - `statsd://host:8125`: every field of a daemon sample as a gauge
+//!   (`prefix.cpu_pct:12.5|g`) in one UDP datagram.
+//! - `graphite://host:2003`: plaintext lines (`prefix.cpu_pct 12.5
+//!   1792080730`) over a short-lived TCP connection.
+//! - `influx://host:8086/bucket?org=org`: InfluxDB line protocol through
+//!   the v2 HTTP write API (`metrics_token` for auth), with `system`,
+//!   `net`, `disk` and `gpu` measurements tagged by host, interface, mount
+//!   and GPU. Plain HTTP only; put a local proxy in front for TLS.
+//! - `influx-file:/path/metrics.lp`: the same lines appended to a file,
+//!   for `influx write` or Telegraf's tail input.
+//!
+//! Pushes happen every `metrics_interval` seconds from the TUI, `--daemon`
+//! and `--agent` alike, on a background thread so a slow collector never
+//! stalls a tick.
+
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::sample::Sample;
+
+const STATSD_PORT: u16 = 8125;
+const GRAPHITE_PORT: u16 = 2003;
+const INFLUX_PORT: u16 = 8086;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone)]
+pub enum MetricsTarget {
+    /// `host:port`
+    Statsd(String),
+    Graphite(String),
+    Influx {
+        addr: String,
+        org: String,
+        bucket: String,
+    },
+    /// Line protocol appended to this file
+    InfluxFile(String),
+}
+
+impl MetricsTarget {
+    /// `statsd://host[:port]`, `graphite://host[:port]`,
+    /// `influx://host[:port]/bucket?org=org` or `influx-file:path`.
+    pub fn parse(value: &str) -> Option<Self> {
+        if let Some(path) = value.strip_prefix("influx-file:") {
+            return (!path.is_empty()).then(|| MetricsTarget::InfluxFile(path.to_string()));
+        }
+        let (scheme, rest) = value.split_once("://")?;
+        let (addr, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if addr.is_empty() {
+            return None;
+        }
+        let with_port = |default: u16| {
+            let has_port = addr
+                .rsplit_once(':')
+                .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+            if has_port {
+                addr.to_string()
+            } else {
+                format!("{addr}:{default}")
+            }
+        };
+        match scheme {
+            "statsd" => Some(MetricsTarget::Statsd(with_port(STATSD_PORT))),
+            "graphite" => Some(MetricsTarget::Graphite(with_port(GRAPHITE_PORT))),
+            "influx" => {
+                let (bucket, query) = path.split_once('?')?;
+                let org = query.split('&').find_map(|p| p.strip_prefix("org="))?;
+                if bucket.is_empty() || org.is_empty() {
+                    return None;
+                }
+                Some(MetricsTarget::Influx {
+                    addr: with_port(INFLUX_PORT),
+                    org: org.to_string(),
+                    bucket: bucket.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            MetricsTarget::Statsd(addr) => format!("statsd://{addr}"),
+            MetricsTarget::Graphite(addr) => format!("graphite://{addr}"),
+            MetricsTarget::Influx { addr, bucket, .. } => format!("influx://{addr}/{bucket}"),
+            MetricsTarget::InfluxFile(path) => path.clone(),
+        }
+    }
+}
+
+pub struct MetricsExporter {
+    pub target: MetricsTarget,
+    /// InfluxDB API token
+    pub token: Option<String>,
+    /// May contain `{host}`; InfluxDB tags the host instead
+    prefix: String,
+    interval: Duration,
+    last_push: Option<Instant>,
+    /// The last push failed; only the first failure and the recovery are
+    /// worth an event
+    pub failing: bool,
+}
+
+impl MetricsExporter {
+    pub fn new(target: MetricsTarget, token: Option<String>, prefix: String, interval_secs: u64) -> Self {
+        Self {
+            target,
+            token,
+            prefix,
+            interval: Duration::from_secs(interval_secs),
+            last_push: None,
+            failing: false,
+        }
+    }
+
+    /// Whether a push is due; if so, the interval restarts now.
+    pub fn due(&mut self) -> bool {
+        if self.last_push.is_some_and(|at| at.elapsed() < self.interval) {
+            return false;
+        }
+        self.last_push = Some(Instant::now());
+        true
+    }
+
+    /// The current state in the target's wire format.
+    pub fn payload(&self, app: &App) -> String {
+        let sample = Sample::capture(app);
+        match self.target {
+            MetricsTarget::Statsd(_) | MetricsTarget::Graphite(_) => self.gauges(&sample, &app.hostname),
+            MetricsTarget::Influx { .. } | MetricsTarget::InfluxFile(_) => line_protocol(app, &sample),
+        }
+    }
+
+    /// StatsD or Graphite lines, with `{host}` in the prefix replaced by
+    /// `host` (dots become underscores, as dots separate path levels).
+    fn gauges(&self, sample: &Sample, host: &str) -> String {
+        let prefix = self.prefix.replace("{host}", &host.replace('.', "_"));
+        let mut out = String::new();
+        for (name, value) in sample.gauges() {
+            let value = number(value);
+            match self.target {
+                MetricsTarget::Statsd(_) => out.push_str(&format!("{prefix}.{name}:{value}|g\n")),
+                MetricsTarget::Graphite(_) => {
+                    out.push_str(&format!("{prefix}.{name} {value} {}\n", sample.timestamp))
+                }
+                MetricsTarget::Influx { .. } | MetricsTarget::InfluxFile(_) => {}
+            }
+        }
+        out
+    }
+}
+
+/// One `system` line plus a `net`, `disk` and `gpu` line per interface,
+/// mount and GPU, all with the sample's timestamp in nanoseconds.
+fn line_protocol(app: &App, sample: &Sample) -> String {
+    let host = [("host", app.hostname.as_str())];
+    let ns = sample.timestamp * 1_000_000_000;
+    let mut out = String::new();
+    let system: Vec<(&str, String)> = sample
+        .gauges()
+        .into_iter()
+        .map(|(name, value)| (name, number(value)))
+        .collect();
+    push_line(&mut out, "system", &host, &system, ns);
+    for iface in &app.network_interfaces {
+        push_line(
+            &mut out,
+            "net",
+            &[("host", app.hostname.as_str()), ("interface", &iface.name)],
+            &[
+                ("rx_rate", format!("{}i", iface.received)),
+                ("tx_rate", format!("{}i", iface.transmitted)),
+                ("total_received", format!("{}i", iface.total_received)),
+                ("total_transmitted", format!("{}i", iface.total_transmitted)),
+            ],
+            ns,
+        );
+    }
+    for disk in &app.disk_devices {
+        push_line(
+            &mut out,
+            "disk",
+            &[
+                ("host", app.hostname.as_str()),
+                ("mount", &disk.mount),
+                ("device", &disk.device),
+                ("fstype", &disk.file_system),
+            ],
+            &[
+                ("used", format!("{}i", disk.used)),
+                ("total", format!("{}i", disk.total)),
+                ("used_pct", number(disk.usage_pct())),
+                ("read_rate", format!("{}i", disk.read_per_sec)),
+                ("write_rate", format!("{}i", disk.write_per_sec)),
+            ],
+            ns,
+        );
+    }
+    for (i, gpu) in app.gpus.iter().enumerate() {
+        let index = i.to_string();
+        push_line(
+            &mut out,
+            "gpu",
+            &[
+                ("host", app.hostname.as_str()),
+                ("gpu", &index),
+                ("name", &gpu.name),
+            ],
+            &[
+                ("utilization", format!("{}i", gpu.utilization)),
+                ("temperature", format!("{}i", gpu.temperature)),
+                ("memory_used", format!("{}i", gpu.memory_used)),
+                ("memory_total", format!("{}i", gpu.memory_total)),
+            ],
+            ns,
+        );
+    }
+    out
+}
+
+/// `measurement,tag=value field=value timestamp`; empty tags are left out
+/// since line protocol doesn't allow them.
+fn push_line(
+    out: &mut String,
+    measurement: &str,
+    tags: &[(&str, &str)],
+    fields: &[(&str, String)],
+    ns: u64,
+) {
+    out.push_str(measurement);
+    for (key, value) in tags.iter().filter(|(_, v)| !v.is_empty()) {
+        out.push_str(&format!(",{key}={}", escape_tag(value)));
+    }
+    let fields: Vec<String> = fields.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    out.push_str(&format!(" {} {ns}\n", fields.join(",")));
+}
+
+/// Commas, equals signs and spaces are backslash-escaped in tag values.
+fn escape_tag(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Everything but unreserved characters as `%XX`, so an org or bucket name
+/// with `&`, `#` or spaces stays one query parameter.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Deliver one payload. Runs on a background thread.
+pub fn send(target: &MetricsTarget, token: Option<&str>, payload: &str) -> io::Result<()> {
+    match target {
+        MetricsTarget::Statsd(addr) => {
+            let addr = resolve(addr)?;
+            let local = if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+            UdpSocket::bind(local)?.send_to(payload.as_bytes(), addr)?;
+            Ok(())
+        }
+        MetricsTarget::Graphite(addr) => connect(addr)?.write_all(payload.as_bytes()),
+        MetricsTarget::Influx { addr, org, bucket } => {
+            let mut stream = connect(addr)?;
+            stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+            let auth = token.map(|t| format!("Authorization: Token {t}\r\n")).unwrap_or_default();
+            let (org, bucket) = (percent_encode(org), percent_encode(bucket));
+            write!(
+                stream,
+                "POST /api/v2/write?org={org}&bucket={bucket} HTTP/1.1\r\nHost: {addr}\r\n{auth}\
+                 Content-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{payload}",
+                payload.len()
+            )?;
+            let mut status = String::new();
+            BufReader::new(&stream).read_line(&mut status)?;
+            // `HTTP/1.1 204 No Content`
+            match status.split_whitespace().nth(1) {
+                Some(code) if code.starts_with('2') => Ok(()),
+                _ => Err(io::Error::other(format!("InfluxDB answered {}", status.trim()))),
+            }
+        }
+        MetricsTarget::InfluxFile(path) => OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(payload.as_bytes()),
+    }
+}
+
+fn connect(addr: &str) -> io::Result<TcpStream> {
+    let stream = TcpStream::connect_timeout(&resolve(addr)?, CONNECT_TIMEOUT)?;
+    stream.set_write_timeout(Some(CONNECT_TIMEOUT))?;
+    Ok(stream)
+}
+
+fn resolve(addr: &str) -> io::Result<SocketAddr> {
+    addr.to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{addr} did not resolve")))
+}
+
+/// Whole numbers without a fraction, anything else to two places.
+fn number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
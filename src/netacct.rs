@@ -0,0 +1,153 @@
+//! Per-process network rates, for the Net RX/TX columns.
+//!
+//! Neither OS keeps a per-process byte counter, so this samples counters
+//! that do exist and attributes them: on Linux the TCP_INFO byte counts of
+//! every socket via `ss -tinp` (UDP has no per-socket counters and is not
+//! counted), on macOS the per-process totals from `nettop`. Sockets of other
+//! users' processes are only attributed when running as root. Windows only
+//! exposes this through ETW, which is not supported.
+
+use std::collections::HashMap;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+use std::time::Instant;
+
+/// Cumulative bytes of one socket (Linux) or process (macOS).
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+pub struct SocketBytes {
+    /// Identifies the counter between samples
+    key: String,
+    pid: u32,
+    received: u64,
+    sent: u64,
+}
+
+/// Turns cumulative counters into per-process rates between samples.
+#[derive(Default)]
+pub struct NetAccounting {
+    previous: HashMap<String, (u64, u64)>,
+    sampled_at: Option<Instant>,
+    /// PID → (received, sent) bytes per second
+    rates: HashMap<u32, (u64, u64)>,
+}
+
+impl NetAccounting {
+    pub fn update(&mut self, counters: Vec<SocketBytes>) {
+        let now = Instant::now();
+        let mut totals: HashMap<u32, (u64, u64)> = HashMap::new();
+        let mut previous = HashMap::with_capacity(counters.len());
+        for c in counters {
+            // A socket opened since the last sample moved all of its bytes
+            // within the interval
+            let (rx0, tx0) = self.previous.get(&c.key).copied().unwrap_or((0, 0));
+            let total = totals.entry(c.pid).or_default();
+            total.0 += c.received.saturating_sub(rx0);
+            total.1 += c.sent.saturating_sub(tx0);
+            previous.insert(c.key, (c.received, c.sent));
+        }
+        // The first sample has nothing to diff against
+        self.rates = match self.sampled_at {
+            Some(then) => {
+                let secs = now.duration_since(then).as_secs_f64().max(0.001);
+                totals
+                    .into_iter()
+                    .map(|(pid, (rx, tx))| (pid, ((rx as f64 / secs) as u64, (tx as f64 / secs) as u64)))
+                    .collect()
+            }
+            None => HashMap::new(),
+        };
+        self.previous = previous;
+        self.sampled_at = Some(now);
+    }
+
+    /// (received, sent) bytes per second.
+    pub fn rate(&self, pid: u32) -> (u64, u64) {
+        self.rates.get(&pid).copied().unwrap_or((0, 0))
+    }
+}
+
+/// `ss -tinpH` prints each socket on one line (addresses and
+/// `users:(("name",pid=N,fd=M))`) followed by an indented TCP_INFO line
+/// with `bytes_acked:` and `bytes_received:`.
+#[cfg(target_os = "linux")]
+pub fn query() -> Vec<SocketBytes> {
+    let Ok(out) = Command::new("ss").args(["-tinpH"]).output() else {
+        return Vec::new();
+    };
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut counters = Vec::new();
+    let mut socket: Option<(String, u32)> = None;
+    for line in text.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // A socket shared after fork() is counted for its first owner
+            let pid = line
+                .split_once("pid=")
+                .and_then(|(_, rest)| rest.split([',', ')']).next())
+                .and_then(|p| p.parse().ok());
+            socket = match (fields.get(3), fields.get(4), pid) {
+                (Some(local), Some(remote), Some(pid)) => Some((format!("{local} {remote}"), pid)),
+                _ => None,
+            };
+            continue;
+        }
+        let Some((key, pid)) = socket.take() else {
+            continue;
+        };
+        let counter = |name: &str| {
+            line.split_whitespace()
+                .find_map(|f| f.strip_prefix(name))
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0)
+        };
+        counters.push(SocketBytes {
+            key,
+            pid,
+            received: counter("bytes_received:"),
+            sent: counter("bytes_acked:"),
+        });
+    }
+    counters
+}
+
+/// `nettop -P -L 1 -x` prints a CSV header, then one line per process with
+/// a timestamp, `name.pid` (the column with an empty header) and the
+/// cumulative byte counts asked for by `-J`.
+#[cfg(target_os = "macos")]
+pub fn query() -> Vec<SocketBytes> {
+    let Ok(out) = Command::new("nettop")
+        .args(["-P", "-L", "1", "-x", "-J", "bytes_in,bytes_out"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut lines = text.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let column = |name: &str| header.split(',').position(|h| h.trim() == name);
+    let (Some(name_col), Some(in_col), Some(out_col)) =
+        (column(""), column("bytes_in"), column("bytes_out"))
+    else {
+        return Vec::new();
+    };
+    lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let process = *fields.get(name_col)?;
+            let (_, pid) = process.rsplit_once('.')?;
+            Some(SocketBytes {
+                key: process.to_string(),
+                pid: pid.parse().ok()?,
+                received: fields.get(in_col)?.trim().parse().ok()?,
+                sent: fields.get(out_col)?.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn query() -> Vec<SocketBytes> {
+    Vec::new()
+}
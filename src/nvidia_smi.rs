@@ -0,0 +1,60 @@
+//! NVIDIA fallback for when NVML can't be loaded.
+//!
+//! Containers often ship `nvidia-smi` without a matching `libnvidia-ml`, and
+//! a driver/library version mismatch makes `Nvml::init()` fail outright.
+//! `nvidia-smi` links its own copy, so parsing its CSV output still gets
+//! utilization, memory and temperature in those setups.
+
+use std::process::Command;
+
+use crate::app::GpuInfo;
+
+const FIELDS: &str = "name,temperature.gpu,utilization.gpu,memory.used,memory.total,\
+fan.speed,power.draw,power.limit,clocks.gr,clocks.mem";
+
+/// One `GpuInfo` per GPU, or `None` when `nvidia-smi` is missing or fails
+/// (no driver, no NVIDIA GPU).
+pub fn query() -> Option<Vec<GpuInfo>> {
+    let out = Command::new("nvidia-smi")
+        .args([
+            &format!("--query-gpu={FIELDS}"),
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&out.stdout);
+    Some(text.lines().filter_map(parse_line).collect())
+}
+
+/// Parse one CSV row in `FIELDS` order. Memory is in MiB and power in watts.
+fn parse_line(line: &str) -> Option<GpuInfo> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() < 10 {
+        return None;
+    }
+    let mib = |s: &str| number(s).map(|v| v as u64 * 1024 * 1024);
+    let watts_to_mw = |s: &str| number(s).map(|v| (v * 1000.0) as u32);
+    let whole = |s: &str| number(s).map(|v| v as u32);
+
+    Some(GpuInfo {
+        name: fields[0].to_string(),
+        temperature: whole(fields[1]).unwrap_or(0),
+        utilization: whole(fields[2]).unwrap_or(0),
+        memory_used: mib(fields[3]).unwrap_or(0),
+        memory_total: mib(fields[4]).unwrap_or(0),
+        fan_speed: whole(fields[5]),
+        power_usage: watts_to_mw(fields[6]),
+        power_limit: watts_to_mw(fields[7]),
+        core_clock_mhz: whole(fields[8]),
+        memory_clock_mhz: whole(fields[9]),
+        ..Default::default()
+    })
+}
+
+/// Unsupported readings come back as `[N/A]` or `[Not Supported]`.
+fn number(s: &str) -> Option<f64> {
+    s.parse().ok()
+}
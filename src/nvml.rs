@@ -0,0 +1,72 @@
+//! Runtime loading of NVML (`libnvidia-ml` / `nvml.dll`).
+//!
+//! nvml-wrapper opens the library with `dlopen`, but only under one name,
+//! and drivers install it in different places: distro packages put it on the
+//! loader path, containers under `/usr/local/nvidia`, WSL under
+//! `/usr/lib/wsl/lib` and older Windows drivers in the NVSMI folder. Trying
+//! each lets one binary work on GPU-less boxes and any NVIDIA setup, and the
+//! returned status says which library was used or why none was.
+
+use std::ffi::OsStr;
+
+use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::Nvml;
+
+#[cfg(target_os = "linux")]
+const CANDIDATES: &[&str] = &[
+    "libnvidia-ml.so.1",
+    "libnvidia-ml.so",
+    "/usr/local/nvidia/lib64/libnvidia-ml.so.1",
+    "/usr/lib/wsl/lib/libnvidia-ml.so.1",
+];
+#[cfg(windows)]
+const CANDIDATES: &[&str] = &[
+    "nvml.dll",
+    r"C:\Program Files\NVIDIA Corporation\NVSMI\nvml.dll",
+];
+#[cfg(not(any(target_os = "linux", windows)))]
+const CANDIDATES: &[&str] = &[];
+
+const NO_DRIVER: &str = "libnvidia-ml not found (no NVIDIA driver)";
+const UNSUPPORTED: &str = "not available on this platform";
+
+/// Whether a status returned without a handle is a real failure, rather
+/// than the normal case of a machine with no NVIDIA GPU.
+pub fn is_failure(status: &str) -> bool {
+    status != NO_DRIVER && status != UNSUPPORTED
+}
+
+/// Open NVML from `custom_path` if set, otherwise the first candidate that
+/// loads. Returns the handle, if any, and a one-line status for the UI.
+pub fn load(custom_path: Option<&str>) -> (Option<Nvml>, String) {
+    let candidates: Vec<&str> = match custom_path {
+        Some(path) => vec![path],
+        None => CANDIDATES.to_vec(),
+    };
+    if candidates.is_empty() {
+        return (None, UNSUPPORTED.into());
+    }
+
+    // A library that loads but fails to initialise (driver not loaded,
+    // version mismatch) explains more than a later "not found"
+    let mut init_error = None;
+    for path in &candidates {
+        match Nvml::builder().lib_path(OsStr::new(path)).init() {
+            Ok(nvml) => {
+                let status = match nvml.sys_driver_version() {
+                    Ok(driver) => format!("{path} (driver {driver})"),
+                    Err(_) => path.to_string(),
+                };
+                return (Some(nvml), status);
+            }
+            Err(NvmlError::LibloadingError(_)) => {}
+            Err(e) if init_error.is_none() => init_error = Some(format!("{path}: {e}")),
+            Err(_) => {}
+        }
+    }
+    let status = init_error.unwrap_or_else(|| match custom_path {
+        Some(path) => format!("{path} not found"),
+        None => NO_DRIVER.into(),
+    });
+    (None, status)
+}
@@ -0,0 +1,56 @@
+//! Highest values seen this session, for reading off a load test's
+//! results after the fact on the System tab.
+
+use std::time::Instant;
+
+#[derive(Clone, Copy)]
+pub struct Peak {
+    pub value: f64,
+    pub at: Instant,
+}
+
+pub struct Peaks {
+    /// Start of the current window; `r` on the System tab restarts it
+    pub since: Instant,
+    /// Global CPU %
+    pub cpu: Option<Peak>,
+    /// Used RAM in bytes
+    pub memory: Option<Peak>,
+    /// Bytes per refresh, as shown on the Network tab
+    pub net_rx: Option<Peak>,
+    pub net_tx: Option<Peak>,
+    /// Hottest GPU in °C
+    pub gpu_temp: Option<Peak>,
+}
+
+impl Default for Peaks {
+    fn default() -> Self {
+        Self {
+            since: Instant::now(),
+            cpu: None,
+            memory: None,
+            net_rx: None,
+            net_tx: None,
+            gpu_temp: None,
+        }
+    }
+}
+
+impl Peaks {
+    pub fn record(&mut self, cpu: f64, memory: u64, net_rx: u64, net_tx: u64, gpu_temp: Option<u32>) {
+        let now = Instant::now();
+        bump(&mut self.cpu, cpu, now);
+        bump(&mut self.memory, memory as f64, now);
+        bump(&mut self.net_rx, net_rx as f64, now);
+        bump(&mut self.net_tx, net_tx as f64, now);
+        if let Some(t) = gpu_temp {
+            bump(&mut self.gpu_temp, t as f64, now);
+        }
+    }
+}
+
+fn bump(slot: &mut Option<Peak>, value: f64, at: Instant) {
+    if slot.is_none_or(|p| value > p.value) {
+        *slot = Some(Peak { value, at });
+    }
+}
@@ -0,0 +1,128 @@
+//! Latency monitor for the Network tab: one echo request to the default
+//! gateway (or the configured `ping_target`) every `ping_interval`
+//! seconds, through the system `ping` so no raw-socket privileges are
+//! needed.
+
+use std::collections::VecDeque;
+use std::process::Command;
+
+/// Samples kept for the chart and the loss figure
+pub const PING_SAMPLES: usize = 60;
+
+#[derive(Default)]
+pub struct PingMonitor {
+    /// Host being pinged; found on the first sample when not configured
+    pub target: Option<String>,
+    /// Whether `target` is the detected default gateway
+    pub is_gateway: bool,
+    /// Round-trip time in ms, `None` for a lost reply
+    pub history: VecDeque<Option<f64>>,
+}
+
+impl PingMonitor {
+    pub fn new(target: Option<String>) -> Self {
+        Self {
+            target,
+            ..Self::default()
+        }
+    }
+
+    pub fn record(&mut self, rtt: Option<f64>) {
+        if self.history.len() == PING_SAMPLES {
+            self.history.pop_front();
+        }
+        self.history.push_back(rtt);
+    }
+
+    /// The most recent sample; `None` before the first reply comes back
+    /// or when it was lost.
+    pub fn last(&self) -> Option<f64> {
+        self.history.back().copied().flatten()
+    }
+
+    pub fn loss_pct(&self) -> f64 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        let lost = self.history.iter().filter(|s| s.is_none()).count();
+        lost as f64 / self.history.len() as f64 * 100.0
+    }
+}
+
+/// One echo request with a one-second timeout; the RTT in ms, or `None`
+/// when no reply came back (or `ping` isn't installed).
+pub fn ping(host: &str) -> Option<f64> {
+    let mut cmd = Command::new("ping");
+    if cfg!(windows) {
+        cmd.args(["-n", "1", "-w", "1000", host]);
+    } else if cfg!(target_os = "macos") {
+        // -W is in milliseconds on macOS
+        cmd.args(["-c", "1", "-W", "1000", host]);
+    } else {
+        cmd.args(["-c", "1", "-W", "1", host]);
+    }
+    let out = cmd.output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    parse_rtt(&String::from_utf8_lossy(&out.stdout))
+}
+
+/// `time=12.3 ms` (Linux, macOS), `time=12ms` or `time<1ms` (Windows).
+fn parse_rtt(output: &str) -> Option<f64> {
+    let start = output.find("time=").or_else(|| output.find("time<"))?;
+    let rest = &output[start + 5..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// `/proc/net/route` lists the default route with destination `00000000`
+/// and the gateway as little-endian hex.
+#[cfg(target_os = "linux")]
+pub fn default_gateway() -> Option<String> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    routes.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.get(1) != Some(&"00000000") {
+            return None;
+        }
+        let gateway = u32::from_str_radix(fields.get(2)?, 16).ok()?;
+        (gateway != 0).then(|| std::net::Ipv4Addr::from(gateway.to_le_bytes()).to_string())
+    })
+}
+
+/// `route -n get default` prints `gateway: 192.168.1.1`.
+#[cfg(target_os = "macos")]
+pub fn default_gateway() -> Option<String> {
+    let out = Command::new("route").args(["-n", "get", "default"]).output().ok()?;
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("gateway:"))
+        .map(|g| g.trim().to_string())
+}
+
+/// `route print -4 0.0.0.0` lists the default route as
+/// `0.0.0.0  0.0.0.0  <gateway>  <interface>  <metric>`.
+#[cfg(windows)]
+pub fn default_gateway() -> Option<String> {
+    let out = Command::new("route")
+        .args(["print", "-4", "0.0.0.0"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&out.stdout).lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["0.0.0.0", "0.0.0.0", gateway, ..] if gateway.parse::<std::net::Ipv4Addr>().is_ok() => {
+                Some(gateway.to_string())
+            }
+            _ => None,
+        }
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub fn default_gateway() -> Option<String> {
+    None
+}
@@ -0,0 +1,36 @@
+//! Per-process memory sysinfo doesn't break out, from `/proc/<pid>`
+//! (Linux only).
+//!
+//! `statm` counts pages: resident pages backed by a file or shared with
+//! other processes (libraries, shared memory) versus the rest, which is
+//! the process's own heap and stack. `status` has what's been swapped out.
+
+use std::sync::OnceLock;
+
+fn page_size() -> u64 {
+    static PAGE_SIZE: OnceLock<u64> = OnceLock::new();
+    // SAFETY: sysconf has no preconditions
+    *PAGE_SIZE.get_or_init(|| match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        size if size > 0 => size as u64,
+        _ => 4096,
+    })
+}
+
+/// Resident bytes shared with other processes, `None` once the process
+/// is gone or its `statm` is unreadable.
+pub fn shared_bytes(pid: u32) -> Option<u64> {
+    let statm = std::fs::read_to_string(format!("/proc/{pid}/statm")).ok()?;
+    // size resident shared text lib data dt
+    let shared: u64 = statm.split_whitespace().nth(2)?.parse().ok()?;
+    Some(shared * page_size())
+}
+
+/// Bytes swapped out (`VmSwap`), `None` for kernel threads, which have
+/// no address space, and processes that are gone.
+pub fn swap_bytes(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let line = status.lines().find_map(|l| l.strip_prefix("VmSwap:"))?;
+    // "    1234 kB"
+    let kb: u64 = line.split_whitespace().next()?.parse().ok()?;
+    Some(kb * 1024)
+}
@@ -0,0 +1,132 @@
+//! One-shot CPU profile of a process using `perf` (Linux only).
+//!
+//! Samples the process at 99 Hz for `profile_secs` with `perf record`, then
+//! flattens `perf report` into a list of the hottest functions. It needs
+//! `perf` installed and either root or `kernel.perf_event_paranoid` ≤ 1;
+//! perf's own error message is passed through when that isn't the case.
+
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+/// How long `p` samples the selected process unless `profile_secs` says
+pub const DEFAULT_PROFILE_SECS: u64 = 5;
+pub const MAX_PROFILE_SECS: u64 = 300;
+/// Functions below this share of samples are left out of the summary.
+#[cfg(target_os = "linux")]
+const PERCENT_LIMIT: &str = "0.5";
+
+pub struct ProfileReport {
+    pub pid: u32,
+    pub name: String,
+    pub samples: u64,
+    /// How long it sampled for
+    pub secs: u64,
+    /// (share of samples in percent, symbol), hottest first
+    pub functions: Vec<(f64, String)>,
+}
+
+#[cfg(target_os = "linux")]
+pub fn profile(pid: u32, name: String, secs: u64) -> Result<ProfileReport, String> {
+    let data = std::env::temp_dir().join(format!("rustmonitor-perf-{pid}.data"));
+    let data_arg = data.to_string_lossy().to_string();
+    let secs_arg = secs.to_string();
+
+    let record = Command::new("perf")
+        .args(["record", "-F", "99", "-p", &pid.to_string(), "-o", &data_arg, "--", "sleep", &secs_arg])
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => "perf is not installed".to_string(),
+            _ => format!("perf: {e}"),
+        })?;
+    if !record.status.success() {
+        let _ = std::fs::remove_file(&data);
+        return Err(perf_error(&record.stderr));
+    }
+
+    let report = Command::new("perf")
+        .args([
+            "report",
+            "-i",
+            &data_arg,
+            "--stdio",
+            "--no-children",
+            "--sort",
+            "symbol",
+            "--percent-limit",
+            PERCENT_LIMIT,
+        ])
+        .output();
+    let _ = std::fs::remove_file(&data);
+    let report = report.map_err(|e| format!("perf: {e}"))?;
+    if !report.status.success() {
+        return Err(perf_error(&report.stderr));
+    }
+
+    let (samples, functions) = parse_report(&String::from_utf8_lossy(&report.stdout));
+    Ok(ProfileReport {
+        pid,
+        name,
+        samples,
+        secs,
+        functions,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn profile(_pid: u32, _name: String, _secs: u64) -> Result<ProfileReport, String> {
+    Err("profiling needs perf, which is Linux only".into())
+}
+
+/// First meaningful line of perf's stderr, skipping its "Error:" header.
+#[cfg(target_os = "linux")]
+fn perf_error(stderr: &[u8]) -> String {
+    let text = String::from_utf8_lossy(stderr);
+    text.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && *l != "Error:")
+        .map(|l| format!("perf: {l}"))
+        .unwrap_or_else(|| "perf failed".into())
+}
+
+/// Parse `perf report --stdio --sort symbol`:
+///
+/// ```text
+/// # Samples: 495  of event 'cpu-clock:pppH'
+/// ...
+///     23.03%  [k] _raw_spin_unlock_irqrestore
+///      8.08%  [.] main
+/// ```
+#[cfg(target_os = "linux")]
+fn parse_report(text: &str) -> (u64, Vec<(f64, String)>) {
+    let mut samples = 0;
+    let mut functions = Vec::new();
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix("# Samples:") {
+            samples = rest.split_whitespace().next().map(parse_count).unwrap_or(0);
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let (Some(pct), Some(_kind)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Some(pct) = pct.strip_suffix('%').and_then(|p| p.parse::<f64>().ok()) else {
+            continue;
+        };
+        functions.push((pct, parts.collect::<Vec<_>>().join(" ")));
+    }
+    (samples, functions)
+}
+
+/// perf abbreviates large sample counts: "495", "12K", "3M".
+#[cfg(target_os = "linux")]
+fn parse_count(s: &str) -> u64 {
+    let (digits, scale) = match s.chars().last() {
+        Some('K') => (&s[..s.len() - 1], 1_000),
+        Some('M') => (&s[..s.len() - 1], 1_000_000),
+        _ => (s, 1),
+    };
+    digits.parse::<u64>().map(|n| n * scale).unwrap_or(0)
+}
@@ -0,0 +1,113 @@
+//! Reverse DNS for the Connections tab's remote addresses.
+//!
+//! Lookups go through the system resolver (`getnameinfo`, so `/etc/hosts`,
+//! mDNS and the configured DNS servers all apply) on a background task, a
+//! batch at a time, and every answer, including "no name", is cached for
+//! the session so each address is only asked about once. Windows shows
+//! plain addresses.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// Addresses resolved per background task; a slow server can take seconds
+/// per lookup, so keep batches small and the newest sockets coming in
+pub const LOOKUP_BATCH: usize = 16;
+/// Forget everything past this many entries rather than grow forever
+const MAX_CACHED: usize = 4096;
+
+#[derive(Default)]
+pub struct DnsCache {
+    names: HashMap<IpAddr, Option<String>>,
+}
+
+impl DnsCache {
+    pub fn name(&self, ip: &IpAddr) -> Option<&str> {
+        self.names.get(ip).and_then(|n| n.as_deref())
+    }
+
+    /// Addresses not looked up yet, at most one batch.
+    pub fn unresolved(&self, ips: impl Iterator<Item = IpAddr>) -> Vec<IpAddr> {
+        let mut missing: Vec<IpAddr> = Vec::new();
+        for ip in ips {
+            if missing.len() == LOOKUP_BATCH {
+                break;
+            }
+            if !self.names.contains_key(&ip) && !missing.contains(&ip) {
+                missing.push(ip);
+            }
+        }
+        missing
+    }
+
+    pub fn insert(&mut self, results: Vec<(IpAddr, Option<String>)>) {
+        if self.names.len() + results.len() > MAX_CACHED {
+            self.names.clear();
+        }
+        self.names.extend(results);
+    }
+}
+
+/// Host name for `ip`, or `None` when it has no PTR record.
+#[cfg(unix)]
+pub fn lookup(ip: IpAddr) -> Option<String> {
+    use std::ffi::CStr;
+    use std::mem;
+
+    // NI_MAXHOST from <netdb.h>
+    const HOST_LEN: usize = 1025;
+    let mut host = [0 as libc::c_char; HOST_LEN];
+
+    // SAFETY: the sockaddr is zero-initialised and fully describes `ip`;
+    // getnameinfo writes a NUL-terminated name of at most HOST_LEN bytes
+    let rc = unsafe {
+        match ip {
+            IpAddr::V4(v4) => {
+                let mut sin: libc::sockaddr_in = mem::zeroed();
+                sin.sin_family = libc::AF_INET as libc::sa_family_t;
+                sin.sin_addr.s_addr = u32::from_ne_bytes(v4.octets());
+                #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+                {
+                    sin.sin_len = mem::size_of::<libc::sockaddr_in>() as u8;
+                }
+                libc::getnameinfo(
+                    &sin as *const _ as *const libc::sockaddr,
+                    mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    HOST_LEN as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+            IpAddr::V6(v6) => {
+                let mut sin6: libc::sockaddr_in6 = mem::zeroed();
+                sin6.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                sin6.sin6_addr.s6_addr = v6.octets();
+                #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+                {
+                    sin6.sin6_len = mem::size_of::<libc::sockaddr_in6>() as u8;
+                }
+                libc::getnameinfo(
+                    &sin6 as *const _ as *const libc::sockaddr,
+                    mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    HOST_LEN as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+    };
+    if rc != 0 {
+        return None;
+    }
+    // SAFETY: getnameinfo succeeded, so `host` holds a NUL-terminated string
+    let name = unsafe { CStr::from_ptr(host.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+#[cfg(not(unix))]
+pub fn lookup(_ip: IpAddr) -> Option<String> {
+    None
+}
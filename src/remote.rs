@@ -0,0 +1,230 @@
+//! Monitor another machine from this terminal, with snapshots coming from
+//! one of two places:
+//!
+//! - `--remote user@host`: `ssh` runs `rustmonitor --remote-helper` there,
+//!   which is daemon mode writing one compact snapshot per tick to stdout;
+//!   no terminal is needed on that side. `BatchMode` is set because a
+//!   password prompt can't work under the TUI: use keys or an agent.
+//! - `--connect host:port`: a TCP connection to `rustmonitor --agent`
+//!   (see `agent.rs`), which sends the same lines. The connection is
+//!   retried every few seconds, so the agent can be restarted underneath,
+//!   and counts as dropped after `READ_TIMEOUT` without a line.
+//!
+//! A reader thread parses each line and the UI thread takes them in
+//! `App::tick` instead of refreshing local counters, so every tab fed by
+//! the snapshot shows the remote host.
+
+use std::io::{self, BufRead, BufReader, Read};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use crate::json;
+use crate::snapshot::Snapshot;
+
+/// Wait between attempts to reach an agent
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+/// Three of the slowest refresh interval an agent can run at. A network
+/// that drops silently sends no FIN, so without this a read blocks forever
+/// and the client never reconnects.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest line read from an agent or helper. A snapshot of a few thousand
+/// processes is well under a megabyte; anything longer is dropped rather
+/// than buffered without end.
+const MAX_LINE: u64 = 16 * 1024 * 1024;
+
+enum Received {
+    Snapshot(Box<Snapshot>),
+    /// A line that isn't a snapshot this version can read
+    Malformed(String),
+    /// The connection ended or couldn't be made, and why
+    Closed(String),
+}
+
+pub struct RemoteFeed {
+    /// As given to `--remote` or `--connect`
+    pub host: String,
+    /// Seconds since the remote host booted, from the latest snapshot
+    pub uptime: u64,
+    pub load_average: [f64; 3],
+    /// Cleared while there is no connection
+    pub connected: bool,
+    rx: Receiver<Received>,
+    /// The ssh process; `None` for `--connect`
+    child: Option<Child>,
+}
+
+impl RemoteFeed {
+    /// Start `ssh host command --remote-helper`; `command` is the path to
+    /// rustmonitor on the remote side.
+    pub fn ssh(host: &str, command: &str) -> io::Result<Self> {
+        let mut child = Command::new("ssh")
+            .args(["-T", "-o", "BatchMode=yes", "--", host])
+            .arg(format!("{command} --remote-helper"))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+        // Drained on its own so a chatty ssh can't fill the pipe and stall
+        let last_error = thread::spawn(move || {
+            BufReader::new(stderr)
+                .lines()
+                .map_while(Result::ok)
+                .filter(|l| !l.trim().is_empty())
+                .last()
+        });
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let Some(reason) = forward(stdout, &tx) else {
+                return;
+            };
+            let reason = last_error.join().ok().flatten().unwrap_or(reason);
+            let _ = tx.send(Received::Closed(reason));
+        });
+        Ok(Self::new(host, rx, Some(child)))
+    }
+
+    /// Connect to an agent at `addr`, reconnecting whenever it drops.
+    pub fn connect(addr: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let target = addr.to_string();
+        thread::spawn(move || {
+            // Report an outage once, not on every retry
+            let mut reported = false;
+            loop {
+                let reason = match TcpStream::connect(&target) {
+                    Ok(stream) => {
+                        reported = false;
+                        if let Err(e) = stream.set_read_timeout(Some(READ_TIMEOUT)) {
+                            format!("can't set a read timeout: {e}")
+                        } else {
+                            match forward(stream, &tx) {
+                                Some(reason) => reason,
+                                None => return,
+                            }
+                        }
+                    }
+                    Err(e) => format!("can't connect: {e}"),
+                };
+                if !reported && tx.send(Received::Closed(reason)).is_err() {
+                    return;
+                }
+                reported = true;
+                thread::sleep(RECONNECT_DELAY);
+            }
+        });
+        Self::new(addr, rx, None)
+    }
+
+    fn new(host: &str, rx: Receiver<Received>, child: Option<Child>) -> Self {
+        Self {
+            host: host.to_string(),
+            uptime: 0,
+            load_average: [0.0; 3],
+            connected: true,
+            rx,
+            child,
+        }
+    }
+
+    /// Snapshots received since the last call, oldest first. A line that
+    /// fails to parse, or the connection ending, comes through as `Err`.
+    pub fn drain(&mut self) -> Vec<Result<Snapshot, String>> {
+        let mut received = Vec::new();
+        loop {
+            match self.rx.try_recv() {
+                Ok(Received::Snapshot(snapshot)) => {
+                    self.connected = true;
+                    received.push(Ok(*snapshot));
+                }
+                Ok(Received::Malformed(e)) => received.push(Err(format!("unreadable snapshot: {e}"))),
+                Ok(Received::Closed(reason)) => {
+                    self.connected = false;
+                    received.push(Err(reason));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.connected = false;
+                    break;
+                }
+            }
+        }
+        received
+    }
+}
+
+/// Parse snapshot lines from `reader` until it ends, returning why.
+/// `None` once the feed has been dropped and there is no one left to send to.
+fn forward(reader: impl Read, tx: &Sender<Received>) -> Option<String> {
+    // A newer agent would otherwise fill the event history twice a second
+    let mut reported_malformed = false;
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let mut read = (&mut reader).take(MAX_LINE).read_until(b'\n', &mut line);
+        let oversize = matches!(read, Ok(n) if n as u64 == MAX_LINE) && line.last() != Some(&b'\n');
+        if oversize {
+            read = skip_line(&mut reader).map(|()| line.len());
+        }
+        match read {
+            Ok(0) => return Some("connection closed".into()),
+            Ok(_) => {}
+            // The read timeout, which counts as a disconnect
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                return Some(format!("no data for {}s", READ_TIMEOUT.as_secs()));
+            }
+            Err(e) => return Some(format!("connection lost: {e}")),
+        }
+        let parsed = if oversize {
+            Err(format!("line longer than {} MB", MAX_LINE / (1024 * 1024)))
+        } else {
+            json::parse(&String::from_utf8_lossy(&line)).and_then(|v| Snapshot::from_json(&v))
+        };
+        let item = match parsed {
+            Ok(snapshot) => Received::Snapshot(Box::new(snapshot)),
+            Err(_) if reported_malformed => continue,
+            Err(e) => {
+                reported_malformed = true;
+                Received::Malformed(e)
+            }
+        };
+        if tx.send(item).is_err() {
+            return None;
+        }
+    }
+}
+
+/// Discard the rest of an oversize line, up to and including its newline.
+fn skip_line(reader: &mut impl BufRead) -> io::Result<()> {
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(());
+        }
+        match buf.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(());
+            }
+            None => {
+                let len = buf.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+impl Drop for RemoteFeed {
+    fn drop(&mut self) {
+        // Closing the connection stops the helper on the other side
+        if let Some(child) = &mut self.child {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! The headline numbers at one moment: what `--daemon` logs per line and
+//! what the metric sinks push. Rates are as the dashboard shows them.
+//!
+//! JSON Lines records are versioned like snapshots (`"schema": "sample_v1"`):
+//! fields are only added within a version, and renaming or removing one
+//! bumps it.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::App;
+use crate::json::Value;
+
+pub const CSV_HEADER: &str = "timestamp,cpu_pct,load_1,memory_used,memory_total,swap_used,\
+net_rx_rate,net_tx_rate,disk_read_rate,disk_write_rate,gpu_util_pct,gpu_temp_c,processes";
+/// Current JSON Lines record format; bump when a field is renamed or
+/// removed, as with the snapshot `SCHEMA_VERSION`.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// One log line's worth of the current state.
+pub struct Sample {
+    pub timestamp: u64,
+    pub cpu_pct: f32,
+    pub load_1: f64,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub swap_used: u64,
+    pub net_rx_rate: u64,
+    pub net_tx_rate: u64,
+    pub disk_read_rate: u64,
+    pub disk_write_rate: u64,
+    /// Busiest and hottest GPU; `None` without one
+    pub gpu_util_pct: Option<u32>,
+    pub gpu_temp_c: Option<u32>,
+    pub processes: usize,
+}
+
+impl Sample {
+    pub fn capture(app: &App) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            cpu_pct: app.global_cpu,
+            load_1: app.load_average()[0],
+            memory_used: app.used_memory,
+            memory_total: app.total_memory,
+            swap_used: app.used_swap,
+            net_rx_rate: app.net_rx,
+            net_tx_rate: app.net_tx,
+            disk_read_rate: app.disk_read,
+            disk_write_rate: app.disk_write,
+            gpu_util_pct: app.gpus.iter().map(|g| g.utilization).max(),
+            gpu_temp_c: app.gpus.iter().map(|g| g.temperature).max(),
+            processes: app.processes.len(),
+        }
+    }
+
+    pub fn to_json_line(&self) -> String {
+        Value::object()
+            .with("schema", format!("sample_v{SCHEMA_VERSION}"))
+            .with("schema_version", SCHEMA_VERSION)
+            .with("timestamp", self.timestamp)
+            .with("cpu_pct", self.cpu_pct)
+            .with("load_1", self.load_1)
+            .with("memory_used", self.memory_used)
+            .with("memory_total", self.memory_total)
+            .with("swap_used", self.swap_used)
+            .with("net_rx_rate", self.net_rx_rate)
+            .with("net_tx_rate", self.net_tx_rate)
+            .with("disk_read_rate", self.disk_read_rate)
+            .with("disk_write_rate", self.disk_write_rate)
+            .with("gpu_util_pct", self.gpu_util_pct)
+            .with("gpu_temp_c", self.gpu_temp_c)
+            .with("processes", self.processes)
+            .to_compact()
+    }
+
+    pub fn to_csv_line(&self) -> String {
+        let opt = |v: Option<u32>| v.map(|v| v.to_string()).unwrap_or_default();
+        format!(
+            "{},{:.1},{:.2},{},{},{},{},{},{},{},{},{},{}",
+            self.timestamp,
+            self.cpu_pct,
+            self.load_1,
+            self.memory_used,
+            self.memory_total,
+            self.swap_used,
+            self.net_rx_rate,
+            self.net_tx_rate,
+            self.disk_read_rate,
+            self.disk_write_rate,
+            opt(self.gpu_util_pct),
+            opt(self.gpu_temp_c),
+            self.processes
+        )
+    }
+
+    /// Numeric fields by log column name, for the metric sinks; a missing
+    /// GPU leaves its fields out rather than reporting 0.
+    pub fn gauges(&self) -> Vec<(&'static str, f64)> {
+        let mut gauges = vec![
+            ("cpu_pct", self.cpu_pct as f64),
+            ("load_1", self.load_1),
+            ("memory_used", self.memory_used as f64),
+            ("memory_total", self.memory_total as f64),
+            ("swap_used", self.swap_used as f64),
+            ("net_rx_rate", self.net_rx_rate as f64),
+            ("net_tx_rate", self.net_tx_rate as f64),
+            ("disk_read_rate", self.disk_read_rate as f64),
+            ("disk_write_rate", self.disk_write_rate as f64),
+        ];
+        if let Some(util) = self.gpu_util_pct {
+            gauges.push(("gpu_util_pct", util as f64));
+        }
+        if let Some(temp) = self.gpu_temp_c {
+            gauges.push(("gpu_temp_c", temp as f64));
+        }
+        gauges.push(("processes", self.processes as f64));
+        gauges
+    }
+}
@@ -0,0 +1,175 @@
+//! Drive health from `smartctl --json` (smartmontools).
+//!
+//! smartctl needs root for most devices; without it the drive shows as
+//! unknown with smartctl's own message instead of a false "healthy". Queries
+//! take a second or more per drive, so they run as a background task when
+//! the Disks tab is open and are repeated only every few minutes.
+//!
+//! On Linux, drive temperature is also available without root from the
+//! `nvme` and `drivetemp` hwmon drivers, cheap enough to read every tick.
+
+use std::process::Command;
+
+use crate::json::{self, Value};
+
+#[derive(Clone, Default)]
+pub struct SmartHealth {
+    /// Overall SMART self-assessment; `None` when it couldn't be read
+    pub passed: Option<bool>,
+    /// ATA attribute 5, or NVMe media errors
+    pub reallocated: Option<u64>,
+    /// Share of rated endurance used, 0–100+ (SSDs only)
+    pub wear_used: Option<u32>,
+    pub temperature: Option<u32>,
+    /// Why the query failed (not installed, permission denied, ...)
+    pub error: Option<String>,
+}
+
+impl SmartHealth {
+    pub fn is_failing(&self) -> bool {
+        self.passed == Some(false)
+    }
+
+    /// Passed, but with early warning signs worth a look.
+    pub fn is_degraded(&self) -> bool {
+        self.reallocated.is_some_and(|n| n > 0) || self.wear_used.is_some_and(|w| w >= 90)
+    }
+}
+
+/// Query each device, in order.
+pub fn query_all(devices: &[String]) -> Vec<(String, SmartHealth)> {
+    devices.iter().map(|d| (d.clone(), query(d))).collect()
+}
+
+fn query(device: &str) -> SmartHealth {
+    let out = match Command::new("smartctl").args(["--json", "-H", "-A", device]).output() {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return unknown("smartctl is not installed");
+        }
+        Err(e) => return unknown(&e.to_string()),
+    };
+    // smartctl's exit status is a bitmask that is non-zero for plenty of
+    // healthy drives, so go by the JSON alone
+    match json::parse(&String::from_utf8_lossy(&out.stdout)) {
+        Ok(value) => parse(&value),
+        Err(_) => unknown("unreadable smartctl output"),
+    }
+}
+
+fn unknown(reason: &str) -> SmartHealth {
+    SmartHealth {
+        error: Some(reason.to_string()),
+        ..Default::default()
+    }
+}
+
+fn parse(value: &Value) -> SmartHealth {
+    let passed = match value.get("smart_status").and_then(|s| s.get("passed")) {
+        Some(Value::Bool(b)) => Some(*b),
+        _ => None,
+    };
+    let temperature = value
+        .get("temperature")
+        .and_then(|t| t.get("current"))
+        .and_then(Value::as_u64)
+        .map(|t| t as u32);
+
+    let mut health = SmartHealth {
+        passed,
+        temperature,
+        ..Default::default()
+    };
+
+    if let Some(log) = value.get("nvme_smart_health_information_log") {
+        health.wear_used = log.get("percentage_used").and_then(Value::as_u64).map(|p| p as u32);
+        health.reallocated = log.get("media_errors").and_then(Value::as_u64);
+    }
+
+    let attributes = value
+        .get("ata_smart_attributes")
+        .and_then(|a| a.get("table"))
+        .map(Value::as_array)
+        .unwrap_or_default();
+    for attr in attributes {
+        let id = attr.get("id").and_then(Value::as_u64);
+        let normalized = attr.get("value").and_then(Value::as_u64);
+        let raw = attr.get("raw").and_then(|r| r.get("value")).and_then(Value::as_u64);
+        match id {
+            Some(5) => health.reallocated = raw,
+            // Vendor wear indicators count down from 100% life remaining
+            Some(177 | 231 | 233) if health.wear_used.is_none() => {
+                health.wear_used = normalized.map(|v| 100u64.saturating_sub(v) as u32);
+            }
+            _ => {}
+        }
+    }
+
+    if health.passed.is_none() {
+        health.error = value
+            .get("smartctl")
+            .and_then(|s| s.get("messages"))
+            .map(Value::as_array)
+            .and_then(|m| m.first())
+            .and_then(|m| m.get("string"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| Some("no SMART data".into()));
+    }
+    health
+}
+
+/// Composite temperature (°C) of a whole disk from hwmon, e.g. `/dev/nvme0n1`.
+///
+/// Depending on the kernel, the hwmon device hangs off the NVMe controller
+/// (`device/hwmonN`) or its PCI parent (`device/device/hwmon/hwmonN`);
+/// drivetemp puts it under the SCSI device (`device/hwmon/hwmonN`).
+#[cfg(target_os = "linux")]
+pub fn hwmon_temperature(disk: &str) -> Option<u32> {
+    let name = disk.strip_prefix("/dev/")?;
+    let device = std::path::Path::new("/sys/block").join(name).join("device");
+    [device.clone(), device.join("device")]
+        .iter()
+        .flat_map(|dir| [dir.clone(), dir.join("hwmon")])
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("hwmon"))
+        .find_map(|e| {
+            let millis: u32 = std::fs::read_to_string(e.path().join("temp1_input"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            Some(millis / 1000)
+        })
+}
+
+/// The whole-disk device a partition lives on, which is what smartctl wants:
+/// `/dev/nvme0n1p2` → `/dev/nvme0n1`, `/dev/sda1` → `/dev/sda`,
+/// `/dev/disk3s1` → `/dev/disk3`. Returns `None` for things that aren't
+/// block devices (tmpfs, overlay, network shares).
+pub fn whole_disk(device: &str) -> Option<String> {
+    let name = device.strip_prefix("/dev/")?;
+    #[cfg(target_os = "linux")]
+    {
+        let sys = std::path::Path::new("/sys/class/block").join(name);
+        if !sys.exists() {
+            return None;
+        }
+        if sys.join("partition").exists() {
+            // /sys/class/block/sda1 links into .../block/sda/sda1
+            let parent = std::fs::canonicalize(&sys).ok()?.parent()?.file_name()?.to_owned();
+            return Some(format!("/dev/{}", parent.to_string_lossy()));
+        }
+        Some(device.to_string())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let digits: String = name
+            .strip_prefix("disk")?
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        (!digits.is_empty()).then(|| format!("/dev/disk{digits}"))
+    }
+}
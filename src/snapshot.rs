@@ -0,0 +1,661 @@
+//! Point-in-time captures of the monitored state and diffs between them.
+//!
+//! `rustmonitor snapshot [file]` writes one as JSON; `rustmonitor diff a b`
+//! compares two, e.g. before and after a config change or deploy.
+//!
+//! The format is versioned (`"schema": "snapshot_v1"`) so scripts can rely
+//! on it: fields are only ever added within a version, and anything renamed
+//! or removed bumps it. Reading is lenient: missing fields fall back to
+//! zero/empty so older or hand-edited files still load, and files from
+//! before versioning are read as v1, which they match. Files from a newer
+//! version are refused rather than half-read.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::{format_bytes, format_duration, App};
+use crate::json::{self, Value};
+
+/// Processes whose CPU or memory moved less than this are left out of a diff.
+const CPU_THRESHOLD: f32 = 5.0;
+const MEMORY_THRESHOLD: u64 = 10 * 1024 * 1024;
+/// Longest list printed per diff section before summarising the rest.
+const MAX_LISTED: usize = 20;
+/// Current snapshot format; bump when a field is renamed or removed.
+/// Daemon JSON Lines records carry their own, `sample::SCHEMA_VERSION`.
+pub const SCHEMA_VERSION: u64 = 1;
+
+pub struct SnapshotDisk {
+    pub mount: String,
+    pub device: String,
+    pub file_system: String,
+    pub used: u64,
+    pub total: u64,
+    /// Bytes per second
+    pub read_rate: u64,
+    pub write_rate: u64,
+}
+
+pub struct SnapshotGpu {
+    pub name: String,
+    pub utilization: u32,
+    pub temperature: u32,
+    pub memory_used: u64,
+    pub memory_total: u64,
+}
+
+pub struct SnapshotInterface {
+    pub name: String,
+    /// Rates as shown on the Network tab
+    pub rx_rate: u64,
+    pub tx_rate: u64,
+    /// Bytes since boot
+    pub total_received: u64,
+    pub total_transmitted: u64,
+    pub mtu: u64,
+}
+
+pub struct SnapshotProcess {
+    pub pid: u32,
+    pub name: String,
+    pub cpu: f32,
+    pub memory: u64,
+    pub status: String,
+    /// Seconds
+    pub run_time: u64,
+    /// Bytes per second
+    pub disk_read_rate: u64,
+    pub disk_write_rate: u64,
+}
+
+pub struct Snapshot {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub hostname: String,
+    pub os: String,
+    pub kernel: String,
+    pub arch: String,
+    /// Seconds since boot
+    pub uptime: u64,
+    pub cpu_brand: String,
+    pub cpu_cores: usize,
+    pub cpu_usage: f32,
+    pub cpu_per_core: Vec<f32>,
+    pub load_average: [f64; 3],
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub swap_used: u64,
+    pub swap_total: u64,
+    /// All disks, as on the Overview tab's I/O graphs
+    pub disk_read_rate: u64,
+    pub disk_write_rate: u64,
+    pub disks: Vec<SnapshotDisk>,
+    pub gpus: Vec<SnapshotGpu>,
+    pub network: Vec<SnapshotInterface>,
+    pub processes: Vec<SnapshotProcess>,
+}
+
+impl Snapshot {
+    pub fn capture(app: &App) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            hostname: app.hostname.clone(),
+            os: format!("{} {}", app.os_name, app.os_version),
+            kernel: app.kernel_version.clone(),
+            arch: app.cpu_arch.clone(),
+            uptime: app.uptime(),
+            cpu_brand: app.cpu_brand.clone(),
+            cpu_cores: app.cpu_count,
+            cpu_usage: app.global_cpu,
+            cpu_per_core: app
+                .cpu_history
+                .iter()
+                .map(|h| h.back().copied().unwrap_or(0.0) as f32)
+                .collect(),
+            load_average: app.load_average(),
+            memory_used: app.used_memory,
+            memory_total: app.total_memory,
+            swap_used: app.used_swap,
+            swap_total: app.total_swap,
+            disk_read_rate: app.disk_read,
+            disk_write_rate: app.disk_write,
+            disks: app
+                .disk_devices
+                .iter()
+                .map(|d| SnapshotDisk {
+                    mount: d.mount.clone(),
+                    device: d.device.clone(),
+                    file_system: d.file_system.clone(),
+                    used: d.used,
+                    total: d.total,
+                    read_rate: d.read_per_sec,
+                    write_rate: d.write_per_sec,
+                })
+                .collect(),
+            gpus: app
+                .gpus
+                .iter()
+                .map(|g| SnapshotGpu {
+                    name: g.name.clone(),
+                    utilization: g.utilization,
+                    temperature: g.temperature,
+                    memory_used: g.memory_used,
+                    memory_total: g.memory_total,
+                })
+                .collect(),
+            network: app
+                .network_interfaces
+                .iter()
+                .map(|i| SnapshotInterface {
+                    name: i.name.clone(),
+                    rx_rate: i.received,
+                    tx_rate: i.transmitted,
+                    total_received: i.total_received,
+                    total_transmitted: i.total_transmitted,
+                    mtu: i.mtu,
+                })
+                .collect(),
+            processes: app
+                .processes
+                .iter()
+                .map(|p| SnapshotProcess {
+                    pid: p.pid,
+                    name: p.name.clone(),
+                    cpu: p.cpu,
+                    memory: p.memory,
+                    status: p.status.clone(),
+                    run_time: p.run_time,
+                    disk_read_rate: p.disk_read_rate,
+                    disk_write_rate: p.disk_write_rate,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        Value::object()
+            .with("schema", format!("snapshot_v{SCHEMA_VERSION}"))
+            .with("schema_version", SCHEMA_VERSION)
+            .with("timestamp", self.timestamp)
+            .with("hostname", self.hostname.as_str())
+            .with("os", self.os.as_str())
+            .with("kernel", self.kernel.as_str())
+            .with("arch", self.arch.as_str())
+            .with("uptime", self.uptime)
+            .with(
+                "cpu",
+                Value::object()
+                    .with("brand", self.cpu_brand.as_str())
+                    .with("cores", self.cpu_cores)
+                    .with("usage", self.cpu_usage)
+                    .with("per_core", self.cpu_per_core.clone()),
+            )
+            .with("load_average", self.load_average.to_vec())
+            .with(
+                "memory",
+                Value::object()
+                    .with("used", self.memory_used)
+                    .with("total", self.memory_total),
+            )
+            .with(
+                "swap",
+                Value::object()
+                    .with("used", self.swap_used)
+                    .with("total", self.swap_total),
+            )
+            .with(
+                "disk_io",
+                Value::object()
+                    .with("read_rate", self.disk_read_rate)
+                    .with("write_rate", self.disk_write_rate),
+            )
+            .with(
+                "disks",
+                Value::Array(
+                    self.disks
+                        .iter()
+                        .map(|d| {
+                            Value::object()
+                                .with("mount", d.mount.as_str())
+                                .with("device", d.device.as_str())
+                                .with("file_system", d.file_system.as_str())
+                                .with("used", d.used)
+                                .with("total", d.total)
+                                .with("read_rate", d.read_rate)
+                                .with("write_rate", d.write_rate)
+                        })
+                        .collect(),
+                ),
+            )
+            .with(
+                "gpus",
+                Value::Array(
+                    self.gpus
+                        .iter()
+                        .map(|g| {
+                            Value::object()
+                                .with("name", g.name.as_str())
+                                .with("utilization", g.utilization)
+                                .with("temperature", g.temperature)
+                                .with("memory_used", g.memory_used)
+                                .with("memory_total", g.memory_total)
+                        })
+                        .collect(),
+                ),
+            )
+            .with(
+                "network",
+                Value::Array(
+                    self.network
+                        .iter()
+                        .map(|i| {
+                            Value::object()
+                                .with("name", i.name.as_str())
+                                .with("rx_rate", i.rx_rate)
+                                .with("tx_rate", i.tx_rate)
+                                .with("total_received", i.total_received)
+                                .with("total_transmitted", i.total_transmitted)
+                                .with("mtu", i.mtu)
+                        })
+                        .collect(),
+                ),
+            )
+            .with(
+                "processes",
+                Value::Array(
+                    self.processes
+                        .iter()
+                        .map(|p| {
+                            Value::object()
+                                .with("pid", p.pid)
+                                .with("name", p.name.as_str())
+                                .with("cpu", p.cpu)
+                                .with("memory", p.memory)
+                                .with("status", p.status.as_str())
+                                .with("run_time", p.run_time)
+                                .with("disk_read_rate", p.disk_read_rate)
+                                .with("disk_write_rate", p.disk_write_rate)
+                        })
+                        .collect(),
+                ),
+            )
+    }
+
+    pub fn from_json(value: &Value) -> Result<Self, String> {
+        if !matches!(value, Value::Object(_)) {
+            return Err("not a snapshot: expected a JSON object".into());
+        }
+        let version = schema_version(value)?;
+        if version > SCHEMA_VERSION {
+            return Err(format!(
+                "snapshot uses schema v{version}, this rustmonitor only reads up to v{SCHEMA_VERSION}"
+            ));
+        }
+        let str_at = |v: &Value, key: &str| {
+            v.get(key).and_then(Value::as_str).unwrap_or_default().to_string()
+        };
+        let u64_at = |v: &Value, key: &str| v.get(key).and_then(Value::as_u64).unwrap_or(0);
+        let f64_at = |v: &Value, key: &str| v.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+        let null = Value::Null;
+        let cpu = value.get("cpu").unwrap_or(&null);
+        let memory = value.get("memory").unwrap_or(&null);
+        let swap = value.get("swap").unwrap_or(&null);
+        let disk_io = value.get("disk_io").unwrap_or(&null);
+        let load: Vec<f64> = value
+            .get("load_average")
+            .map(|l| l.as_array().iter().filter_map(Value::as_f64).collect())
+            .unwrap_or_default();
+
+        Ok(Self {
+            timestamp: u64_at(value, "timestamp"),
+            hostname: str_at(value, "hostname"),
+            os: str_at(value, "os"),
+            kernel: str_at(value, "kernel"),
+            arch: str_at(value, "arch"),
+            uptime: u64_at(value, "uptime"),
+            cpu_brand: str_at(cpu, "brand"),
+            cpu_cores: u64_at(cpu, "cores") as usize,
+            cpu_usage: f64_at(cpu, "usage") as f32,
+            cpu_per_core: cpu
+                .get("per_core")
+                .map(Value::as_array)
+                .unwrap_or_default()
+                .iter()
+                .map(|c| c.as_f64().unwrap_or(0.0) as f32)
+                .collect(),
+            load_average: [
+                load.first().copied().unwrap_or(0.0),
+                load.get(1).copied().unwrap_or(0.0),
+                load.get(2).copied().unwrap_or(0.0),
+            ],
+            memory_used: u64_at(memory, "used"),
+            memory_total: u64_at(memory, "total"),
+            swap_used: u64_at(swap, "used"),
+            swap_total: u64_at(swap, "total"),
+            disk_read_rate: u64_at(disk_io, "read_rate"),
+            disk_write_rate: u64_at(disk_io, "write_rate"),
+            disks: value
+                .get("disks")
+                .map(Value::as_array)
+                .unwrap_or_default()
+                .iter()
+                .map(|d| SnapshotDisk {
+                    mount: str_at(d, "mount"),
+                    device: str_at(d, "device"),
+                    file_system: str_at(d, "file_system"),
+                    used: u64_at(d, "used"),
+                    total: u64_at(d, "total"),
+                    read_rate: u64_at(d, "read_rate"),
+                    write_rate: u64_at(d, "write_rate"),
+                })
+                .collect(),
+            gpus: value
+                .get("gpus")
+                .map(Value::as_array)
+                .unwrap_or_default()
+                .iter()
+                .map(|g| SnapshotGpu {
+                    name: str_at(g, "name"),
+                    utilization: u64_at(g, "utilization") as u32,
+                    temperature: u64_at(g, "temperature") as u32,
+                    memory_used: u64_at(g, "memory_used"),
+                    memory_total: u64_at(g, "memory_total"),
+                })
+                .collect(),
+            network: value
+                .get("network")
+                .map(Value::as_array)
+                .unwrap_or_default()
+                .iter()
+                .map(|i| SnapshotInterface {
+                    name: str_at(i, "name"),
+                    rx_rate: u64_at(i, "rx_rate"),
+                    tx_rate: u64_at(i, "tx_rate"),
+                    total_received: u64_at(i, "total_received"),
+                    total_transmitted: u64_at(i, "total_transmitted"),
+                    mtu: u64_at(i, "mtu"),
+                })
+                .collect(),
+            processes: value
+                .get("processes")
+                .map(Value::as_array)
+                .unwrap_or_default()
+                .iter()
+                .map(|p| SnapshotProcess {
+                    pid: u64_at(p, "pid") as u32,
+                    name: str_at(p, "name"),
+                    cpu: f64_at(p, "cpu") as f32,
+                    memory: u64_at(p, "memory"),
+                    status: str_at(p, "status"),
+                    run_time: u64_at(p, "run_time"),
+                    disk_read_rate: u64_at(p, "disk_read_rate"),
+                    disk_write_rate: u64_at(p, "disk_write_rate"),
+                })
+                .collect(),
+        })
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        let value = json::parse(&text).map_err(|e| format!("{path}: {e}"))?;
+        Self::from_json(&value).map_err(|e| format!("{path}: {e}"))
+    }
+}
+
+/// A snapshot plus what only the TUI has: the graph histories (oldest
+/// sample first, one per refresh, rates in KB per refresh as graphed) and
+/// the event history. Written by `S`; `from_json` reads it like any other
+/// snapshot and ignores the extra fields.
+pub fn full_state(app: &App) -> Value {
+    let events = app
+        .events
+        .newest_first()
+        .map(|e| {
+            Value::object()
+                .with("age_secs", e.at.elapsed().as_secs())
+                .with("level", e.level.label())
+                .with("message", e.message.as_str())
+        })
+        .collect();
+    Snapshot::capture(app)
+        .to_json()
+        .with("history", history(app))
+        .with("events", Value::Array(events))
+}
+
+/// The graph histories, for `full_state` and the saved history file.
+pub fn history(app: &App) -> Value {
+    let series = |history: &VecDeque<f64>| history.iter().copied().collect::<Vec<_>>();
+    let interfaces = app
+        .network_interfaces
+        .iter()
+        .filter_map(|i| {
+            let (rx, tx) = app.interface_history.get(&i.name)?;
+            Some(
+                Value::object()
+                    .with("name", i.name.as_str())
+                    .with("rx_kb", series(rx))
+                    .with("tx_kb", series(tx)),
+            )
+        })
+        .collect();
+    let mut history = Value::object()
+        .with("cpu_pct", series(&app.global_cpu_history))
+        .with("cpu_core_pct", app.cpu_history.iter().map(series).collect::<Vec<_>>())
+        .with("memory_pct", series(&app.mem_history))
+        .with("net_rx_kb", series(&app.net_rx_history))
+        .with("net_tx_kb", series(&app.net_tx_history))
+        .with("interfaces", Value::Array(interfaces))
+        .with("disk_read_kb", series(&app.disk_read_history))
+        .with("disk_write_kb", series(&app.disk_write_history))
+        .with("gpu_util_pct", app.gpu_util_history.iter().map(series).collect::<Vec<_>>());
+    if let Some(ping) = &app.ping {
+        history = history.with(
+            "ping",
+            Value::object()
+                .with("target", ping.target.as_deref())
+                .with("rtt_ms", ping.history.iter().copied().collect::<Vec<_>>()),
+        );
+    }
+    history
+}
+
+/// Version of a parsed snapshot. Unversioned files predate the field and
+/// have the v1 layout; `schema_version` wins if it disagrees with `schema`.
+fn schema_version(value: &Value) -> Result<u64, String> {
+    if let Some(v) = value.get("schema_version") {
+        return v.as_u64().ok_or_else(|| "schema_version is not a number".to_string());
+    }
+    match value.get("schema").and_then(Value::as_str) {
+        None => Ok(1),
+        Some(name) => name
+            .strip_prefix("snapshot_v")
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| format!("not a snapshot: schema is \"{name}\"")),
+    }
+}
+
+/// Human-readable differences between two snapshot files.
+pub fn diff_files(a: &str, b: &str) -> Result<String, String> {
+    Ok(diff(&Snapshot::load(a)?, &Snapshot::load(b)?))
+}
+
+pub fn diff(a: &Snapshot, b: &Snapshot) -> String {
+    let mut out = Vec::new();
+
+    let gap = b.timestamp.abs_diff(a.timestamp);
+    let gap = if gap < 60 { format!("{gap}s") } else { format_duration(gap) };
+    let elapsed = if b.timestamp >= a.timestamp {
+        format!("{gap} later")
+    } else {
+        format!("{gap} earlier")
+    };
+    out.push(format!("Snapshot A: {}  ({})", a.hostname, a.os));
+    out.push(format!("Snapshot B: {}  ({}), {elapsed}", b.hostname, b.os));
+    if a.hostname != b.hostname {
+        out.push("Note: snapshots come from different hosts".into());
+    }
+
+    out.push(String::new());
+    out.push("System".into());
+    if a.kernel != b.kernel {
+        out.push(row("Kernel", &a.kernel, &b.kernel, String::new()));
+    }
+    out.push(row(
+        "CPU usage",
+        &format!("{:.1}%", a.cpu_usage),
+        &format!("{:.1}%", b.cpu_usage),
+        format!("{:+.1}", b.cpu_usage - a.cpu_usage),
+    ));
+    out.push(row(
+        "Load (1m)",
+        &format!("{:.2}", a.load_average[0]),
+        &format!("{:.2}", b.load_average[0]),
+        format!("{:+.2}", b.load_average[0] - a.load_average[0]),
+    ));
+    out.push(bytes_row("Memory used", a.memory_used, b.memory_used));
+    if a.swap_total > 0 || b.swap_total > 0 {
+        out.push(bytes_row("Swap used", a.swap_used, b.swap_used));
+    }
+    out.push(row(
+        "Processes",
+        &a.processes.len().to_string(),
+        &b.processes.len().to_string(),
+        format!("{:+}", b.processes.len() as i64 - a.processes.len() as i64),
+    ));
+
+    for disk in &b.disks {
+        match a.disks.iter().find(|d| d.mount == disk.mount) {
+            Some(old) if old.used != disk.used => {
+                out.push(bytes_row(&format!("Disk {}", disk.mount), old.used, disk.used));
+            }
+            Some(_) => {}
+            None => out.push(format!("  + Disk {} mounted ({})", disk.mount, format_bytes(disk.total))),
+        }
+    }
+    for disk in a.disks.iter().filter(|d| !b.disks.iter().any(|n| n.mount == d.mount)) {
+        out.push(format!("  - Disk {} unmounted", disk.mount));
+    }
+
+    for (i, gpu) in b.gpus.iter().enumerate() {
+        let Some(old) = a.gpus.get(i).filter(|g| g.name == gpu.name) else {
+            out.push(format!("  + GPU {}", gpu.name));
+            continue;
+        };
+        out.push(row(
+            &format!("GPU{i} util"),
+            &format!("{}%", old.utilization),
+            &format!("{}%", gpu.utilization),
+            format!("{:+}", gpu.utilization as i64 - old.utilization as i64),
+        ));
+        out.push(bytes_row(&format!("GPU{i} VRAM"), old.memory_used, gpu.memory_used));
+        out.push(row(
+            &format!("GPU{i} temp"),
+            &format!("{}°C", old.temperature),
+            &format!("{}°C", gpu.temperature),
+            format!("{:+}", gpu.temperature as i64 - old.temperature as i64),
+        ));
+    }
+
+    // A PID only counts as the same process if the name matches too,
+    // otherwise it was reused in between
+    let before: HashMap<(u32, &str), &SnapshotProcess> =
+        a.processes.iter().map(|p| ((p.pid, p.name.as_str()), p)).collect();
+    let after: HashMap<(u32, &str), &SnapshotProcess> =
+        b.processes.iter().map(|p| ((p.pid, p.name.as_str()), p)).collect();
+
+    let mut started: Vec<&SnapshotProcess> = b
+        .processes
+        .iter()
+        .filter(|p| !before.contains_key(&(p.pid, p.name.as_str())))
+        .collect();
+    let mut exited: Vec<&SnapshotProcess> = a
+        .processes
+        .iter()
+        .filter(|p| !after.contains_key(&(p.pid, p.name.as_str())))
+        .collect();
+    let mut changed: Vec<(&SnapshotProcess, &SnapshotProcess)> = b
+        .processes
+        .iter()
+        .filter_map(|p| before.get(&(p.pid, p.name.as_str())).map(|old| (*old, p)))
+        .filter(|(old, new)| {
+            (new.cpu - old.cpu).abs() >= CPU_THRESHOLD
+                || new.memory.abs_diff(old.memory) >= MEMORY_THRESHOLD
+        })
+        .collect();
+    started.sort_by_key(|p| std::cmp::Reverse(p.memory));
+    exited.sort_by_key(|p| std::cmp::Reverse(p.memory));
+    changed.sort_by_key(|(old, new)| std::cmp::Reverse(new.memory.abs_diff(old.memory)));
+
+    out.push(String::new());
+    out.push(format!(
+        "Processes: {} started, {} exited, {} changed",
+        started.len(),
+        exited.len(),
+        changed.len()
+    ));
+    for p in started.iter().take(MAX_LISTED) {
+        out.push(process_row('+', p));
+    }
+    more(&mut out, started.len());
+    for p in exited.iter().take(MAX_LISTED) {
+        out.push(process_row('-', p));
+    }
+    more(&mut out, exited.len());
+    for (old, new) in changed.iter().take(MAX_LISTED) {
+        out.push(format!(
+            "  ~ {:>7} {:<24} CPU {:.1}% → {:.1}%  MEM {} → {} ({})",
+            new.pid,
+            short_name(&new.name),
+            old.cpu,
+            new.cpu,
+            format_bytes(old.memory),
+            format_bytes(new.memory),
+            signed_bytes(old.memory, new.memory)
+        ));
+    }
+    more(&mut out, changed.len());
+
+    out.join("\n")
+}
+
+fn process_row(marker: char, p: &SnapshotProcess) -> String {
+    format!(
+        "  {marker} {:>7} {:<24} {:>5.1}% {:>10}",
+        p.pid,
+        short_name(&p.name),
+        p.cpu,
+        format_bytes(p.memory)
+    )
+}
+
+/// Keep the process columns aligned for long names like kernel workers.
+fn short_name(name: &str) -> String {
+    if name.chars().count() > 24 {
+        name.chars().take(23).chain(['…']).collect()
+    } else {
+        name.to_string()
+    }
+}
+
+fn row(label: &str, a: &str, b: &str, delta: String) -> String {
+    format!("  {label:<16} {a:>12} → {b:<12} {delta}")
+}
+
+fn bytes_row(label: &str, a: u64, b: u64) -> String {
+    row(label, &format_bytes(a), &format_bytes(b), signed_bytes(a, b))
+}
+
+fn signed_bytes(a: u64, b: u64) -> String {
+    let sign = if b >= a { '+' } else { '-' };
+    format!("{sign}{}", format_bytes(a.abs_diff(b)))
+}
+
+fn more(out: &mut Vec<String>, total: usize) {
+    if total > MAX_LISTED {
+        out.push(format!("    … {} more", total - MAX_LISTED));
+    }
+}
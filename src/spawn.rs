@@ -0,0 +1,143 @@
+//! Processes launched from the `:run` prompt.
+//!
+//! Attached commands have stdout/stderr captured into a shared buffer by
+//! reader threads so the output popup can show them while they run.
+//! Detached commands get null stdio and a session of their own, so
+//! Ctrl+C in the monitor or the terminal closing doesn't take them down
+//! too; they are only tracked to be reaped.
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Keep at most this many lines of captured output per command
+const MAX_OUTPUT_LINES: usize = 1000;
+
+pub struct SpawnedCommand {
+    pub pid: u32,
+    pub command: String,
+    pub detached: bool,
+    pub output: Arc<Mutex<Vec<String>>>,
+    pub exit_status: Option<ExitStatus>,
+    child: Child,
+}
+
+impl SpawnedCommand {
+    pub fn spawn(command: &str, detached: bool) -> Result<Self, String> {
+        let args = split_args(command);
+        let (program, rest) = args.split_first().ok_or("Empty command")?;
+
+        let mut cmd = Command::new(program);
+        cmd.args(rest).stdin(Stdio::null());
+        if detached {
+            cmd.stdout(Stdio::null()).stderr(Stdio::null());
+            detach(&mut cmd);
+        } else {
+            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        }
+
+        let mut child = cmd.spawn().map_err(|e| format!("{program}: {e}"))?;
+        let output = Arc::new(Mutex::new(Vec::new()));
+        if let Some(stdout) = child.stdout.take() {
+            capture(stdout, Arc::clone(&output));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            capture(stderr, Arc::clone(&output));
+        }
+
+        Ok(Self {
+            pid: child.id(),
+            command: command.to_string(),
+            detached,
+            output,
+            exit_status: None,
+            child,
+        })
+    }
+
+    /// Reap the child if it has exited. Returns true once it is finished.
+    pub fn poll(&mut self) -> bool {
+        if self.exit_status.is_none()
+            && let Ok(Some(status)) = self.child.try_wait()
+        {
+            self.exit_status = Some(status);
+        }
+        self.exit_status.is_some()
+    }
+
+    pub fn output_lines(&self) -> Vec<String> {
+        self.output.lock().map(|o| o.clone()).unwrap_or_default()
+    }
+}
+
+/// Start `cmd` in a new session, away from the terminal's signals.
+#[cfg(unix)]
+fn detach(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    // SAFETY: setsid is async-signal-safe, so fine between fork and exec
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Start `cmd` without a console, in a process group of its own so
+/// Ctrl+C isn't delivered to it.
+#[cfg(windows)]
+fn detach(cmd: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    cmd.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+}
+
+fn capture<R: Read + Send + 'static>(stream: R, output: Arc<Mutex<Vec<String>>>) {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if let Ok(mut out) = output.lock() {
+                out.push(line);
+                if out.len() > MAX_OUTPUT_LINES {
+                    out.remove(0);
+                }
+            }
+        }
+    });
+}
+
+/// Split a command line on whitespace, honouring single and double quotes.
+pub fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_arg = false;
+
+    for c in input.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
@@ -0,0 +1,94 @@
+//! `--tmux-status`: one compact line for a tmux status bar.
+//!
+//! tmux runs `#(rustmonitor --tmux-status)` every `status-interval`, so this
+//! samples only CPU, memory and network (no process table, GPUs or
+//! subprocesses) and returns after the one short wait CPU usage needs.
+//! Colors come from the configured theme's thresholds, as in the TUI, as
+//! `#[fg=...]` markup.
+
+use std::time::Instant;
+
+use ratatui::style::Color;
+use sysinfo::{Networks, System};
+
+use crate::app::{format_bytes, Theme};
+use crate::config::Config;
+use crate::theme;
+
+pub fn render(config: &Config) -> String {
+    let mut system = System::new();
+    let mut networks = Networks::new_with_refreshed_list();
+    system.refresh_cpu_usage();
+    let started = Instant::now();
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_cpu_usage();
+    system.refresh_memory();
+    networks.refresh(true);
+    let secs = started.elapsed().as_secs_f64();
+
+    // The configured theme, user theme files included, as the TUI picks it
+    let (user_themes, _) = theme::load_user_themes();
+    let theme = config
+        .theme
+        .as_deref()
+        .and_then(|name| Theme::resolve(name, &user_themes))
+        .unwrap_or(Theme::Default);
+    let mut colors = theme.colors(&user_themes);
+    if config.no_color() {
+        colors = colors.without_color();
+    }
+    let cpu = system.global_cpu_usage() as f64;
+    let mem = if system.total_memory() > 0 {
+        system.used_memory() as f64 / system.total_memory() as f64 * 100.0
+    } else {
+        0.0
+    };
+    let (rx, tx) = networks
+        .values()
+        .fold((0, 0), |(rx, tx), n| (rx + n.received(), tx + n.transmitted()));
+    let per_sec = |bytes: u64| format!("{}/s", format_bytes((bytes as f64 / secs) as u64));
+    let (down, up) = if config.ascii_only() { ("v", "^") } else { ("↓", "↑") };
+
+    [
+        styled(&format!("CPU {cpu:.0}%"), colors.cpu_usage_color(cpu)),
+        styled(&format!("MEM {mem:.0}%"), colors.memory_usage_color(mem)),
+        styled(
+            &format!("{down}{} {up}{}", per_sec(rx), per_sec(tx)),
+            colors.network,
+        ),
+    ]
+    .join(" ")
+}
+
+/// Wrap in tmux color markup, or leave plain when colors are off.
+fn styled(text: &str, color: Color) -> String {
+    match tmux_color(color) {
+        Some(color) => format!("#[fg={color}]{text}#[default]"),
+        None => text.to_string(),
+    }
+}
+
+fn tmux_color(color: Color) -> Option<String> {
+    let name = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some(format!("#{r:02x}{g:02x}{b:02x}")),
+        Color::Indexed(i) => return Some(format!("colour{i}")),
+        Color::Black => "black",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "magenta",
+        Color::Cyan => "cyan",
+        Color::Gray => "white",
+        Color::DarkGray => "brightblack",
+        Color::LightRed => "brightred",
+        Color::LightGreen => "brightgreen",
+        Color::LightYellow => "brightyellow",
+        Color::LightBlue => "brightblue",
+        Color::LightMagenta => "brightmagenta",
+        Color::LightCyan => "brightcyan",
+        Color::White => "brightwhite",
+    };
+    Some(name.to_string())
+}
@@ -0,0 +1,49 @@
+//! Session summary printed to stdout after the terminal is restored, when
+//! `exit_summary` is on or `--summary` is passed.
+
+use crate::app::{format_bytes, format_duration, App};
+use crate::peaks::Peak;
+
+/// Counters kept for the summary; peaks live in `App::session_peaks` so the
+/// System tab's `r` doesn't clear them.
+#[derive(Default)]
+pub struct SessionCounts {
+    pub alerts_fired: usize,
+    pub processes_killed: usize,
+}
+
+/// `abandoned` lists background tasks still running when the shutdown
+/// wait ran out.
+pub fn render(app: &App, abandoned: &[String]) -> String {
+    let peaks = &app.session_peaks;
+    // "12.5% (3m in)": when into the session the peak was reached
+    let peak = |peak: Option<Peak>, show: &dyn Fn(f64) -> String| match peak {
+        Some(p) => format!(
+            "{} ({} in)",
+            show(p.value),
+            format_duration(p.at.duration_since(app.start_time).as_secs())
+        ),
+        None => "–".to_string(),
+    };
+    let mut rows = vec![
+        ("Duration", app.monitor_uptime_str()),
+        ("Peak CPU", peak(peaks.cpu, &|v| format!("{v:.1}%"))),
+        (
+            "Peak memory",
+            peak(peaks.memory, &|v| {
+                format!("{} of {}", format_bytes(v as u64), format_bytes(app.total_memory))
+            }),
+        ),
+        ("Alerts fired", app.session.alerts_fired.to_string()),
+        ("Processes killed", app.session.processes_killed.to_string()),
+    ];
+    if !abandoned.is_empty() {
+        rows.push(("Unfinished", abandoned.join(", ")));
+    }
+
+    let mut out = format!("RustMonitor session on {}", app.hostname);
+    for (label, value) in rows {
+        out.push_str(&format!("\n  {label:<18}{value}"));
+    }
+    out
+}
@@ -0,0 +1,105 @@
+//! Kernel tunables that commonly explain performance surprises.
+//!
+//! Linux reads `/proc/sys` directly (the dots in a name become path
+//! separators); macOS asks `sysctl` once for the whole list. Which keys are
+//! shown comes from the `kernel_params` config key. Well-known keys carry a
+//! recommended value so the System tab can flag ones that look off.
+
+pub struct KernelParam {
+    pub name: String,
+    /// `None` when the key doesn't exist on this kernel or can't be read
+    pub value: Option<String>,
+    /// Set when the value differs from the usual recommendation
+    pub hint: Option<&'static str>,
+}
+
+/// Keys shown when the config doesn't override them.
+pub fn default_names() -> Vec<String> {
+    let names: &[&str] = if cfg!(target_os = "macos") {
+        &["kern.maxfiles", "kern.maxfilesperproc", "kern.ipc.somaxconn"]
+    } else {
+        &[
+            "vm.swappiness",
+            "vm.overcommit_memory",
+            "fs.file-max",
+            "net.core.somaxconn",
+        ]
+    };
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+pub fn read(names: &[String]) -> Vec<KernelParam> {
+    let values = read_values(names);
+    names
+        .iter()
+        .zip(values)
+        .map(|(name, value)| {
+            let hint = value.as_deref().and_then(|v| hint(name, v));
+            KernelParam {
+                name: name.clone(),
+                value,
+                hint,
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn read_values(names: &[String]) -> Vec<Option<String>> {
+    names
+        .iter()
+        .map(|name| {
+            let path = format!("/proc/sys/{}", name.replace('.', "/"));
+            // Multi-value keys (e.g. kernel.printk) are tab-separated
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|v| v.split_whitespace().collect::<Vec<_>>().join(" "))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn read_values(names: &[String]) -> Vec<Option<String>> {
+    // Unknown keys are reported on stderr while the rest still print
+    let text = std::process::Command::new("sysctl")
+        .args(names)
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).to_string())
+        .unwrap_or_default();
+    names
+        .iter()
+        .map(|name| {
+            text.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key.trim() == name).then(|| value.trim().to_string())
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_values(names: &[String]) -> Vec<Option<String>> {
+    vec![None; names.len()]
+}
+
+/// Advice for a known key whose value is outside the usual range.
+fn hint(name: &str, value: &str) -> Option<&'static str> {
+    let number = value.parse::<u64>().ok()?;
+    match name {
+        "vm.swappiness" if number > 60 => {
+            Some("recommended ≤ 60; high values swap out active apps early")
+        }
+        "vm.overcommit_memory" if number == 1 => {
+            Some("recommended 0; 1 never refuses allocations, so the OOM killer steps in")
+        }
+        "vm.overcommit_memory" if number == 2 => {
+            Some("recommended 0; strict mode can fail allocations with RAM free")
+        }
+        "fs.file-max" if number < 65536 => Some("recommended ≥ 65536 for servers"),
+        "net.core.somaxconn" | "kern.ipc.somaxconn" if number < 1024 => {
+            Some("recommended ≥ 1024; small accept queues drop bursts of connections")
+        }
+        "kern.maxfiles" if number < 65536 => Some("recommended ≥ 65536 for servers"),
+        _ => None,
+    }
+}
@@ -0,0 +1,102 @@
+//! Copies of fired alerts for log-based alerting pipelines, with `syslog =
+//! on`.
+//!
+//! - Under systemd, journald's native socket, so the alert key and host
+//!   arrive as their own fields (`RUSTMONITOR_ALERT=disk:/home`) next to
+//!   `MESSAGE` and can be matched with `journalctl RUSTMONITOR_ALERT=...`.
+//! - Otherwise the local syslog socket (`/dev/log`, `/var/run/syslog` on
+//!   macOS) with the same values as `key=value` pairs before the message.
+//! - On Windows, the Application event log through `eventcreate`, source
+//!   `RustMonitor`. Registering a new source needs an administrator once.
+//!
+//! Alerts go out at warning priority under the `user` facility, tagged
+//! `rustmonitor`. Sending is fire-and-forget: a missing logger costs nothing.
+
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+
+#[cfg(target_os = "linux")]
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+#[cfg(target_os = "macos")]
+const SYSLOG_SOCKET: &str = "/var/run/syslog";
+#[cfg(all(unix, not(target_os = "macos")))]
+const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// `LOG_USER | LOG_WARNING`
+#[cfg(unix)]
+const PRIORITY: u8 = 8 + 4;
+
+pub struct Syslog {
+    #[cfg(unix)]
+    socket: Option<UnixDatagram>,
+    /// Talking journald's native protocol rather than plain syslog
+    #[cfg(unix)]
+    journal: bool,
+}
+
+impl Syslog {
+    pub fn open() -> Self {
+        #[cfg(target_os = "linux")]
+        if let Some(socket) = connect(JOURNAL_SOCKET) {
+            return Self {
+                socket: Some(socket),
+                journal: true,
+            };
+        }
+        Self {
+            #[cfg(unix)]
+            socket: connect(SYSLOG_SOCKET),
+            #[cfg(unix)]
+            journal: false,
+        }
+    }
+
+    /// Log a fired alert: its key (`cpu`, `disk:/home`), the host it's
+    /// about and the message shown in the UI.
+    #[cfg(unix)]
+    pub fn alert(&self, key: &str, host: &str, message: &str) {
+        let Some(socket) = &self.socket else {
+            return;
+        };
+        // Both formats are line-based
+        let clean = |s: &str| s.replace(['\n', '\r'], " ");
+        let (key, host, message) = (clean(key), clean(host), clean(message));
+        let datagram = if self.journal {
+            format!(
+                "MESSAGE={message}\nPRIORITY=4\nSYSLOG_FACILITY=1\nSYSLOG_IDENTIFIER=rustmonitor\n\
+                 SYSLOG_PID={}\nRUSTMONITOR_ALERT={key}\nRUSTMONITOR_HOST={host}\n",
+                std::process::id()
+            )
+        } else {
+            // No timestamp: the syslog daemon stamps it on arrival
+            format!(
+                "<{PRIORITY}>rustmonitor[{}]: alert={key} host={host} {message}",
+                std::process::id()
+            )
+        };
+        let _ = socket.send(datagram.as_bytes());
+    }
+
+    #[cfg(target_os = "windows")]
+    pub fn alert(&self, key: &str, host: &str, message: &str) {
+        use std::process::{Command, Stdio};
+
+        let _ = Command::new("eventcreate")
+            .args(["/L", "APPLICATION", "/T", "WARNING", "/SO", "RustMonitor", "/ID", "1"])
+            .arg("/D")
+            .arg(format!("{message} (alert={key} host={host})"))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+
+    #[cfg(not(any(unix, target_os = "windows")))]
+    pub fn alert(&self, _key: &str, _host: &str, _message: &str) {}
+}
+
+#[cfg(unix)]
+fn connect(path: &str) -> Option<UnixDatagram> {
+    let socket = UnixDatagram::unbound().ok()?;
+    socket.connect(path).ok()?;
+    Some(socket)
+}
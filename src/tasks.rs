@@ -0,0 +1,109 @@
+//! Background work that would otherwise block the draw loop.
+//!
+//! Slow actions (external commands, file exports, network requests) run on
+//! their own thread. When the work finishes it hands back a completion
+//! closure, which the main loop applies to `App` on its own thread, so
+//! results land without any locking around application state. Tasks started
+//! with [`TaskManager::spawn`] show a spinner in the footer until then;
+//! periodic refreshes use [`TaskManager::spawn_quiet`] to stay out of sight.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+
+/// Applies a finished task's result to the application state.
+pub type Completion = Box<dyn FnOnce(&mut App) + Send>;
+
+/// How often the footer spinner advances while a visible task runs.
+pub const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
+const SPINNER_UNICODE: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_ASCII: &[&str] = &["|", "/", "-", "\\"];
+
+struct Task {
+    label: String,
+    started: Instant,
+    quiet: bool,
+    rx: Receiver<Completion>,
+}
+
+#[derive(Default)]
+pub struct TaskManager {
+    tasks: Vec<Task>,
+}
+
+impl TaskManager {
+    /// Run `work` on a background thread with a footer spinner labelled `label`.
+    pub fn spawn<F>(&mut self, label: impl Into<String>, work: F)
+    where
+        F: FnOnce() -> Completion + Send + 'static,
+    {
+        self.start(label.into(), false, work);
+    }
+
+    /// Like [`spawn`](Self::spawn) but without a spinner, for routine refreshes.
+    pub fn spawn_quiet<F>(&mut self, label: impl Into<String>, work: F)
+    where
+        F: FnOnce() -> Completion + Send + 'static,
+    {
+        self.start(label.into(), true, work);
+    }
+
+    fn start<F>(&mut self, label: String, quiet: bool, work: F)
+    where
+        F: FnOnce() -> Completion + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(work());
+        });
+        self.tasks.push(Task {
+            label,
+            started: Instant::now(),
+            quiet,
+            rx,
+        });
+    }
+
+    /// Whether a task with this label is still running, to avoid stacking
+    /// duplicate requests.
+    pub fn is_running(&self, label: &str) -> bool {
+        self.tasks.iter().any(|t| t.label == label)
+    }
+
+    /// Remove finished tasks and return their completions. A task whose
+    /// thread panicked is dropped without one.
+    pub fn take_finished(&mut self) -> Vec<Completion> {
+        let mut done = Vec::new();
+        self.tasks.retain(|task| match task.rx.try_recv() {
+            Ok(completion) => {
+                done.push(completion);
+                false
+            }
+            Err(TryRecvError::Empty) => true,
+            Err(TryRecvError::Disconnected) => false,
+        });
+        done
+    }
+
+    pub fn has_visible(&self) -> bool {
+        self.tasks.iter().any(|t| !t.quiet)
+    }
+
+    /// Labels and elapsed time of the tasks that show a spinner.
+    pub fn visible(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.tasks
+            .iter()
+            .filter(|t| !t.quiet)
+            .map(|t| (t.label.as_str(), t.started.elapsed()))
+    }
+}
+
+/// Spinner glyph for the given elapsed time.
+pub fn spinner_frame(elapsed: Duration, ascii_only: bool) -> &'static str {
+    let frames = if ascii_only { SPINNER_ASCII } else { SPINNER_UNICODE };
+    let step = (elapsed.as_millis() / SPINNER_INTERVAL.as_millis()) as usize;
+    frames[step % frames.len()]
+}
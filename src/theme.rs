@@ -1,6 +1,54 @@
-use ratatui::style::Color;
+use std::fs;
+use std::path::Path;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::{bar, Marker};
 use crate::app::Theme;
+use crate::config::{self, ColorDepth};
+
+/// Sparkline levels for terminals without the ▁▂▃… block elements
+const ASCII_BAR_SET: bar::Set<'static> = bar::Set {
+    full: "#",
+    seven_eighths: "#",
+    three_quarters: "=",
+    five_eighths: "=",
+    half: "-",
+    three_eighths: "-",
+    one_quarter: ".",
+    one_eighth: ".",
+    empty: " ",
+};
+
+/// Every color a theme sets, by its theme-file key
+const COLOR_KEYS: [&str; 15] = [
+    "primary", "secondary", "accent", "cpu", "memory", "network", "disk", "warning", "danger",
+    "success", "text", "text_dim", "border", "highlight_bg", "tab_active",
+];
+
+/// The 16 ANSI colors as xterm draws them by default
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel levels of the 6×6×6 cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
 
+#[derive(Clone)]
 pub struct ThemeColors {
     pub primary: Color,
     pub secondary: Color,
@@ -17,12 +65,20 @@ pub struct ThemeColors {
     pub border: Color,
     pub highlight_bg: Color,
     pub tab_active: Color,
+    pub monochrome: bool,
+    pub bar_set: bar::Set<'static>,
+    pub block_full: &'static str,
+    pub block_empty: &'static str,
+    /// Points of line charts
+    pub chart_marker: Marker,
 }
 
 impl ThemeColors {
+    /// A built-in theme's colors; user themes carry their own (see
+    /// `App::theme_colors`) and get the default ones here.
     pub fn from_theme(theme: Theme) -> Self {
         match theme {
-            Theme::Default => Self {
+            Theme::Default | Theme::User(_) => Self {
                 primary: Color::Cyan,
                 secondary: Color::Magenta,
                 accent: Color::Yellow,
@@ -38,6 +94,7 @@ impl ThemeColors {
                 border: Color::DarkGray,
                 highlight_bg: Color::DarkGray,
                 tab_active: Color::Cyan,
+                ..Self::glyph_defaults()
             },
             Theme::Ocean => Self {
                 primary: Color::Rgb(100, 180, 255),
@@ -55,6 +112,7 @@ impl ThemeColors {
                 border: Color::Rgb(60, 80, 120),
                 highlight_bg: Color::Rgb(30, 50, 80),
                 tab_active: Color::Rgb(100, 180, 255),
+                ..Self::glyph_defaults()
             },
             Theme::Forest => Self {
                 primary: Color::Rgb(100, 200, 100),
@@ -72,6 +130,7 @@ impl ThemeColors {
                 border: Color::Rgb(60, 100, 60),
                 highlight_bg: Color::Rgb(30, 60, 30),
                 tab_active: Color::Rgb(100, 200, 100),
+                ..Self::glyph_defaults()
             },
             Theme::Sunset => Self {
                 primary: Color::Rgb(255, 150, 80),
@@ -89,7 +148,228 @@ impl ThemeColors {
                 border: Color::Rgb(120, 80, 60),
                 highlight_bg: Color::Rgb(80, 40, 30),
                 tab_active: Color::Rgb(255, 150, 80),
+                ..Self::glyph_defaults()
+            },
+            Theme::Dracula => Self {
+                primary: Color::Rgb(189, 147, 249),
+                secondary: Color::Rgb(255, 121, 198),
+                accent: Color::Rgb(139, 233, 253),
+                cpu: Color::Rgb(139, 233, 253),
+                memory: Color::Rgb(255, 121, 198),
+                network: Color::Rgb(80, 250, 123),
+                disk: Color::Rgb(241, 250, 140),
+                warning: Color::Rgb(255, 184, 108),
+                danger: Color::Rgb(255, 85, 85),
+                success: Color::Rgb(80, 250, 123),
+                text: Color::Rgb(248, 248, 242),
+                text_dim: Color::Rgb(98, 114, 164),
+                border: Color::Rgb(98, 114, 164),
+                highlight_bg: Color::Rgb(68, 71, 90),
+                tab_active: Color::Rgb(189, 147, 249),
+                ..Self::glyph_defaults()
+            },
+            Theme::Gruvbox => Self {
+                primary: Color::Rgb(254, 128, 25),
+                secondary: Color::Rgb(211, 134, 155),
+                accent: Color::Rgb(250, 189, 47),
+                cpu: Color::Rgb(131, 165, 152),
+                memory: Color::Rgb(211, 134, 155),
+                network: Color::Rgb(142, 192, 124),
+                disk: Color::Rgb(250, 189, 47),
+                warning: Color::Rgb(250, 189, 47),
+                danger: Color::Rgb(251, 73, 52),
+                success: Color::Rgb(184, 187, 38),
+                text: Color::Rgb(235, 219, 178),
+                text_dim: Color::Rgb(146, 131, 116),
+                border: Color::Rgb(80, 73, 69),
+                highlight_bg: Color::Rgb(60, 56, 54),
+                tab_active: Color::Rgb(254, 128, 25),
+                ..Self::glyph_defaults()
+            },
+            Theme::Nord => Self {
+                primary: Color::Rgb(136, 192, 208),
+                secondary: Color::Rgb(180, 142, 173),
+                accent: Color::Rgb(235, 203, 139),
+                cpu: Color::Rgb(136, 192, 208),
+                memory: Color::Rgb(180, 142, 173),
+                network: Color::Rgb(163, 190, 140),
+                disk: Color::Rgb(129, 161, 193),
+                warning: Color::Rgb(235, 203, 139),
+                danger: Color::Rgb(191, 97, 106),
+                success: Color::Rgb(163, 190, 140),
+                text: Color::Rgb(236, 239, 244),
+                text_dim: Color::Rgb(123, 136, 161),
+                border: Color::Rgb(76, 86, 106),
+                highlight_bg: Color::Rgb(59, 66, 82),
+                tab_active: Color::Rgb(136, 192, 208),
+                ..Self::glyph_defaults()
+            },
+            Theme::SolarizedDark => Self {
+                primary: Color::Rgb(38, 139, 210),
+                secondary: Color::Rgb(108, 113, 196),
+                accent: Color::Rgb(181, 137, 0),
+                cpu: Color::Rgb(38, 139, 210),
+                memory: Color::Rgb(211, 54, 130),
+                network: Color::Rgb(42, 161, 152),
+                disk: Color::Rgb(181, 137, 0),
+                warning: Color::Rgb(203, 75, 22),
+                danger: Color::Rgb(220, 50, 47),
+                success: Color::Rgb(133, 153, 0),
+                text: Color::Rgb(147, 161, 161),
+                text_dim: Color::Rgb(88, 110, 117),
+                border: Color::Rgb(88, 110, 117),
+                highlight_bg: Color::Rgb(7, 54, 66),
+                tab_active: Color::Rgb(38, 139, 210),
+                ..Self::glyph_defaults()
+            },
+            Theme::Catppuccin => Self {
+                primary: Color::Rgb(203, 166, 247),
+                secondary: Color::Rgb(245, 194, 231),
+                accent: Color::Rgb(249, 226, 175),
+                cpu: Color::Rgb(137, 180, 250),
+                memory: Color::Rgb(203, 166, 247),
+                network: Color::Rgb(166, 227, 161),
+                disk: Color::Rgb(250, 179, 135),
+                warning: Color::Rgb(249, 226, 175),
+                danger: Color::Rgb(243, 139, 168),
+                success: Color::Rgb(166, 227, 161),
+                text: Color::Rgb(205, 214, 244),
+                text_dim: Color::Rgb(166, 173, 200),
+                border: Color::Rgb(108, 112, 134),
+                highlight_bg: Color::Rgb(49, 50, 68),
+                tab_active: Color::Rgb(203, 166, 247),
+                ..Self::glyph_defaults()
             },
+            // For light terminal backgrounds: dark text and borders, a pale
+            // selection and fills dark enough to read against white
+            Theme::Light => Self {
+                primary: Color::Rgb(9, 105, 218),
+                secondary: Color::Rgb(130, 80, 223),
+                accent: Color::Rgb(154, 103, 0),
+                cpu: Color::Rgb(9, 105, 218),
+                memory: Color::Rgb(130, 80, 223),
+                network: Color::Rgb(26, 127, 55),
+                disk: Color::Rgb(154, 103, 0),
+                warning: Color::Rgb(188, 76, 0),
+                danger: Color::Rgb(207, 34, 46),
+                success: Color::Rgb(26, 127, 55),
+                text: Color::Rgb(31, 35, 40),
+                text_dim: Color::Rgb(101, 109, 118),
+                border: Color::Rgb(140, 149, 159),
+                highlight_bg: Color::Rgb(221, 244, 255),
+                tab_active: Color::Rgb(9, 105, 218),
+                ..Self::glyph_defaults()
+            },
+            Theme::SolarizedLight => Self {
+                primary: Color::Rgb(38, 139, 210),
+                secondary: Color::Rgb(108, 113, 196),
+                accent: Color::Rgb(181, 137, 0),
+                cpu: Color::Rgb(38, 139, 210),
+                memory: Color::Rgb(211, 54, 130),
+                network: Color::Rgb(42, 161, 152),
+                disk: Color::Rgb(181, 137, 0),
+                warning: Color::Rgb(203, 75, 22),
+                danger: Color::Rgb(220, 50, 47),
+                success: Color::Rgb(133, 153, 0),
+                text: Color::Rgb(88, 110, 117),
+                text_dim: Color::Rgb(147, 161, 161),
+                border: Color::Rgb(147, 161, 161),
+                highlight_bg: Color::Rgb(238, 232, 213),
+                tab_active: Color::Rgb(38, 139, 210),
+                ..Self::glyph_defaults()
+            },
+        }
+    }
+
+    /// The color a theme file sets with `key`, for every color field.
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "primary" => &mut self.primary,
+            "secondary" => &mut self.secondary,
+            "accent" => &mut self.accent,
+            "cpu" => &mut self.cpu,
+            "memory" => &mut self.memory,
+            "network" => &mut self.network,
+            "disk" => &mut self.disk,
+            "warning" => &mut self.warning,
+            "danger" => &mut self.danger,
+            "success" => &mut self.success,
+            "text" => &mut self.text,
+            "text_dim" => &mut self.text_dim,
+            "border" => &mut self.border,
+            "highlight_bg" => &mut self.highlight_bg,
+            "tab_active" => &mut self.tab_active,
+            _ => return None,
+        })
+    }
+
+    fn glyph_defaults() -> Self {
+        Self {
+            primary: Color::Reset,
+            secondary: Color::Reset,
+            accent: Color::Reset,
+            cpu: Color::Reset,
+            memory: Color::Reset,
+            network: Color::Reset,
+            disk: Color::Reset,
+            warning: Color::Reset,
+            danger: Color::Reset,
+            success: Color::Reset,
+            text: Color::Reset,
+            text_dim: Color::Reset,
+            border: Color::Reset,
+            highlight_bg: Color::Reset,
+            tab_active: Color::Reset,
+            monochrome: false,
+            bar_set: bar::NINE_LEVELS,
+            block_full: "█",
+            block_empty: "░",
+            chart_marker: Marker::Braille,
+        }
+    }
+
+    /// Drop all colors (NO_COLOR), keeping the glyph settings.
+    pub fn without_color(self) -> Self {
+        Self {
+            monochrome: true,
+            bar_set: self.bar_set,
+            block_full: self.block_full,
+            block_empty: self.block_empty,
+            chart_marker: self.chart_marker,
+            ..Self::glyph_defaults()
+        }
+    }
+
+    /// Map RGB colors to the nearest ones a 256- or 16-color terminal has.
+    pub fn with_depth(mut self, depth: ColorDepth) -> Self {
+        if depth != ColorDepth::TrueColor {
+            for key in COLOR_KEYS {
+                if let Some(color) = self.color_mut(key) {
+                    *color = reduce_color(*color, depth);
+                }
+            }
+        }
+        self
+    }
+
+    /// Swap block elements for plain ASCII on terminals that can't draw them.
+    pub fn with_ascii(self) -> Self {
+        Self {
+            bar_set: ASCII_BAR_SET,
+            block_full: "#",
+            block_empty: ".",
+            // The one chart marker even the Linux console font has
+            chart_marker: Marker::Dot,
+            ..self
+        }
+    }
+
+    /// Style for the selected row; reverse video when there are no colors.
+    pub fn selected_style(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(self.highlight_bg)
         }
     }
 
@@ -103,6 +383,18 @@ impl ThemeColors {
         }
     }
 
+    /// Free RAM goes to cache, so memory only turns amber well past half
+    /// and red as it nears the alert threshold.
+    pub fn memory_usage_color(&self, pct: f64) -> Color {
+        if pct > 90.0 {
+            self.danger
+        } else if pct > 75.0 {
+            self.warning
+        } else {
+            self.success
+        }
+    }
+
     pub fn disk_usage_color(&self, pct: f64) -> Color {
         if pct > 90.0 {
             self.danger
@@ -112,4 +404,176 @@ impl ThemeColors {
             self.success
         }
     }
+
+    /// A volume filling at 100 MB a minute is out of space within hours.
+    pub fn disk_growth_color(&self, bytes_per_min: f64) -> Color {
+        const MB: f64 = 1024.0 * 1024.0;
+        if bytes_per_min >= 100.0 * MB {
+            self.danger
+        } else if bytes_per_min >= 10.0 * MB {
+            self.warning
+        } else {
+            self.text_dim
+        }
+    }
+
+    /// Most NVMe drives start throttling somewhere between 70 and 80°C.
+    pub fn drive_temp_color(&self, celsius: u32) -> Color {
+        if celsius >= 70 {
+            self.danger
+        } else if celsius >= 55 {
+            self.warning
+        } else {
+            self.success
+        }
+    }
+}
+
+fn reduce_color(color: Color, depth: ColorDepth) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        // Only the 16 base colors survive in 16-color mode
+        Color::Indexed(i) if i >= 16 && depth == ColorDepth::Ansi16 => indexed_rgb(i),
+        color => return color,
+    };
+    match depth {
+        ColorDepth::Ansi16 => ANSI_16
+            .iter()
+            .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+            .map_or(color, |(ansi, _)| *ansi),
+        _ => Color::Indexed(nearest_256(rgb)),
+    }
+}
+
+/// The closer of the nearest cube color and the nearest gray ramp step.
+fn nearest_256((r, g, b): (u8, u8, u8)) -> u8 {
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * step;
+    if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube) {
+        232 + step
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+/// RGB of a 256-color palette entry past the 16 base colors.
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    if i >= 232 {
+        let v = 8 + 10 * (i - 232);
+        return (v, v, v);
+    }
+    let i = (i - 16) as usize;
+    (CUBE_LEVELS[i / 36], CUBE_LEVELS[i / 6 % 6], CUBE_LEVELS[i % 6])
+}
+
+/// Squared distance weighted towards green, which the eye is most
+/// sensitive to.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    2 * d(a.0, b.0) + 4 * d(a.1, b.1) + 3 * d(a.2, b.2)
+}
+
+/// A theme from a file in the `themes` config directory, after the
+/// built-in ones in the `t` cycle.
+#[derive(Clone)]
+pub struct UserTheme {
+    pub name: String,
+    pub colors: ThemeColors,
+}
+
+/// Every `*.toml`, `*.yaml` and `*.yml` file in `rustmonitor/themes/`,
+/// sorted by name, plus a message for each file or line that couldn't be
+/// used.
+pub fn load_user_themes() -> (Vec<UserTheme>, Vec<String>) {
+    let Some(dir) = config::config_dir().map(|d| d.join("themes")) else {
+        return (Vec::new(), Vec::new());
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| matches!(e, "toml" | "yaml" | "yml"))
+        })
+        .collect();
+    paths.sort();
+
+    let (mut themes, mut errors) = (Vec::new(), Vec::new());
+    for path in paths {
+        match fs::read_to_string(&path) {
+            Ok(text) => themes.push(parse_theme(&path, &text, &mut errors)),
+            Err(e) => errors.push(format!("{}: {e}", path.display())),
+        }
+    }
+    (themes, errors)
+}
+
+/// A flat TOML or YAML file: `key = "value"` or `key: value` per line,
+/// `#` comments. `name` defaults to the file name and `base` names the
+/// built-in theme whose colors the file overrides (`default` if unset).
+/// Colors are names (`cyan`, `light-red`, `dark-gray`), `#rrggbb` or a
+/// 256-color index.
+fn parse_theme(path: &Path, text: &str, errors: &mut Vec<String>) -> UserTheme {
+    let file = path.file_name().unwrap_or_default().to_string_lossy();
+    let mut name = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let mut colors = ThemeColors::from_theme(Theme::Default);
+    let pairs: Vec<_> = text
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.trim();
+            // TOML tables and YAML document markers carry nothing here
+            if line.is_empty() || line.starts_with(['#', '[']) || line == "---" {
+                return None;
+            }
+            let split = line.find(['=', ':'])?;
+            let key = line[..split].trim().trim_matches('"');
+            Some((i + 1, key, unquote(&line[split + 1..])))
+        })
+        .collect();
+    // The base goes first wherever it's written, so the rest override it
+    for &(line, _, value) in pairs.iter().filter(|(_, key, _)| *key == "base") {
+        match Theme::parse(value) {
+            Some(base) => colors = ThemeColors::from_theme(base),
+            None => errors.push(format!("{file}:{line}: unknown base theme {value}")),
+        }
+    }
+    for (line, key, value) in pairs {
+        match key {
+            "name" => name = value.to_string(),
+            "base" => {}
+            _ => match (colors.color_mut(key), value.parse::<Color>()) {
+                (Some(color), Ok(parsed)) => *color = parsed,
+                (Some(_), Err(_)) => errors.push(format!("{file}:{line}: bad color {value}")),
+                (None, _) => errors.push(format!("{file}:{line}: unknown key {key}")),
+            },
+        }
+    }
+    UserTheme { name, colors }
+}
+
+/// A quoted value up to its closing quote, or a bare one up to a comment.
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(rest) = value.strip_prefix(quote) {
+            return rest.split(quote).next().unwrap_or(rest);
+        }
+    }
+    value.split(" #").next().unwrap_or(value).trim()
 }
@@ -0,0 +1,176 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Cell, Row, Table},
+    Frame,
+};
+
+use super::helpers::{draw_search_bar, large_columns, row_height};
+use crate::app::App;
+use crate::connections::{format_addr, Connection};
+use crate::theme::ThemeColors;
+
+/// Columns of the Connections table, in drawing order; Origin only
+/// appears once a GeoIP database is loaded
+#[derive(Clone, Copy)]
+enum ConnectionColumn {
+    Proto,
+    Local,
+    Remote,
+    State,
+    Pid,
+    Process,
+    Origin,
+}
+
+impl ConnectionColumn {
+    const ALL: [ConnectionColumn; 7] = [
+        ConnectionColumn::Proto,
+        ConnectionColumn::Local,
+        ConnectionColumn::Remote,
+        ConnectionColumn::State,
+        ConnectionColumn::Pid,
+        ConnectionColumn::Process,
+        ConnectionColumn::Origin,
+    ];
+
+    /// Large UI mode drops the protocol and PID columns
+    fn essential(self) -> bool {
+        !matches!(self, ConnectionColumn::Proto | ConnectionColumn::Pid)
+    }
+}
+
+/// The table's cells or widths, less what large UI mode drops
+fn table_columns<T>(app: &App, items: Vec<T>) -> Vec<T> {
+    large_columns(app, &ConnectionColumn::ALL, ConnectionColumn::essential, items)
+}
+
+pub fn draw_connections(frame: &mut Frame, app: &mut App, colors: &ThemeColors, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    draw_search_bar(
+        frame,
+        " Search ",
+        &app.connection_query,
+        app.input_mode,
+        colors,
+        chunks[0],
+    );
+
+    // The Origin column only appears once a GeoIP database is loaded
+    let show_origin = app.geoip.is_some();
+    let mut header_cells = vec![
+        Cell::from("Proto"),
+        Cell::from("Local Address"),
+        Cell::from("Remote Address"),
+        Cell::from("State"),
+        Cell::from("PID"),
+        Cell::from("Process"),
+    ];
+    if show_origin {
+        header_cells.push(Cell::from("Origin"));
+    }
+    let header = Row::new(table_columns(app, header_cells))
+    .style(
+        Style::default()
+            .fg(colors.primary)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let dim = Style::default().fg(colors.text_dim);
+    let row_height = row_height(app);
+    let visible_rows = (chunks[1].height.saturating_sub(3) / row_height) as usize;
+    app.list_rows = visible_rows;
+    let offset = app
+        .connection_scroll
+        .saturating_sub(visible_rows.saturating_sub(1));
+
+    let rows: Vec<Row> = app
+        .filtered_connections
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible_rows)
+        .filter_map(|(i, &idx)| {
+            let conn = app.connections.get(idx)?;
+            let style = if i == app.connection_scroll {
+                colors.selected_style()
+            } else {
+                Style::default()
+            };
+            // Unknown owners belong to other users unless running as root
+            let (pid, process) = match conn.pid {
+                Some(pid) => (
+                    Cell::from(pid.to_string()),
+                    Cell::from(conn.process.clone()),
+                ),
+                None => (Cell::from("—").style(dim), Cell::from("—").style(dim)),
+            };
+            let mut cells = vec![
+                Cell::from(conn.proto_label()),
+                Cell::from(format_addr(Some(&conn.local))),
+                Cell::from(app.remote_label(conn)),
+                Cell::from(conn.state.clone()).style(state_style(conn, colors)),
+                pid,
+                process,
+            ];
+            if show_origin {
+                cells.push(match app.origin_label(conn) {
+                    Some(origin) => Cell::from(origin).style(Style::default().fg(colors.accent)),
+                    None => Cell::from("—").style(dim),
+                });
+            }
+            Some(
+                Row::new(table_columns(app, cells))
+                    .height(row_height)
+                    .style(style),
+            )
+        })
+        .collect();
+
+    let total = app.filtered_connections.len();
+    let listening = app
+        .filtered_connections
+        .iter()
+        .filter_map(|&idx| app.connections.get(idx))
+        .filter(|c| c.is_listening())
+        .count();
+    let position = if total == 0 { 0 } else { app.connection_scroll + 1 };
+
+    let mut widths = vec![
+        Constraint::Length(6),
+        Constraint::Min(22),
+        Constraint::Min(22),
+        Constraint::Length(12),
+        Constraint::Length(8),
+        Constraint::Min(16),
+    ];
+    if show_origin {
+        widths.push(Constraint::Min(20));
+    }
+    let table = Table::new(rows, table_columns(app, widths))
+    .header(header)
+    .block(
+        Block::bordered()
+            .title(format!(
+                " Connections ({total}, {listening} listening) — Sort: {} — Names: {} — [{position}/{total}] ",
+                app.connection_sort.label(),
+                if app.reverse_dns { "on" } else { "off" }
+            ))
+            .border_style(Style::default().fg(colors.primary)),
+    );
+
+    frame.render_widget(table, chunks[1]);
+}
+
+fn state_style(conn: &Connection, colors: &ThemeColors) -> Style {
+    match conn.state.as_str() {
+        "LISTEN" => Style::default().fg(colors.success),
+        "ESTABLISHED" => Style::default().fg(colors.primary),
+        "SYN_SENT" | "SYN_RECV" | "CLOSE_WAIT" => Style::default().fg(colors.warning),
+        _ => Style::default().fg(colors.text_dim),
+    }
+}
@@ -0,0 +1,295 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use super::helpers::{large_columns, row_height};
+use crate::app::{format_bytes, App};
+use crate::smart::SmartHealth;
+use crate::theme::ThemeColors;
+
+/// Processes listed in the top disk I/O panel.
+const TOP_IO_PROCESSES: usize = 5;
+
+/// Columns of the Disks table, in drawing order
+#[derive(Clone, Copy)]
+enum DiskColumn {
+    Mount,
+    Device,
+    FileSystem,
+    Usage,
+    UsedTotal,
+    Read,
+    Write,
+    Iops,
+    Busy,
+    Queue,
+    Temperature,
+    Health,
+}
+
+impl DiskColumn {
+    const ALL: [DiskColumn; 12] = [
+        DiskColumn::Mount,
+        DiskColumn::Device,
+        DiskColumn::FileSystem,
+        DiskColumn::Usage,
+        DiskColumn::UsedTotal,
+        DiskColumn::Read,
+        DiskColumn::Write,
+        DiskColumn::Iops,
+        DiskColumn::Busy,
+        DiskColumn::Queue,
+        DiskColumn::Temperature,
+        DiskColumn::Health,
+    ];
+
+    /// Kept in large UI mode: mount, usage, throughput, temperature and
+    /// health
+    fn essential(self) -> bool {
+        !matches!(
+            self,
+            DiskColumn::Device | DiskColumn::FileSystem | DiskColumn::Iops | DiskColumn::Busy | DiskColumn::Queue
+        )
+    }
+}
+
+/// The table's cells or widths, less what large UI mode drops
+fn table_columns<T>(app: &App, items: Vec<T>) -> Vec<T> {
+    large_columns(app, &DiskColumn::ALL, DiskColumn::essential, items)
+}
+
+pub fn draw_disks(frame: &mut Frame, app: &mut App, colors: &ThemeColors, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(TOP_IO_PROCESSES as u16 + 3),
+            Constraint::Length(1),
+        ])
+        .split(area);
+    let area = chunks[0];
+    draw_top_io(frame, app, colors, chunks[1]);
+    draw_smart_detail(frame, app, colors, chunks[2]);
+
+    let header = Row::new(table_columns(app, vec![
+        Cell::from("Mount"),
+        Cell::from("Device"),
+        Cell::from("FS"),
+        Cell::from("Usage"),
+        Cell::from("Used / Total"),
+        Cell::from("Read/s"),
+        Cell::from("Write/s"),
+        Cell::from("IOPS"),
+        Cell::from("Busy"),
+        Cell::from("Queue"),
+        Cell::from("Temp"),
+        Cell::from("Health"),
+    ]))
+    .style(
+        Style::default()
+            .fg(colors.primary)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let na = || Cell::from("—").style(Style::default().fg(colors.text_dim));
+    let row_height = row_height(app);
+    let visible_rows = (area.height.saturating_sub(3) / row_height) as usize;
+    app.list_rows = visible_rows;
+    let offset = app.disk_scroll.saturating_sub(visible_rows.saturating_sub(1));
+
+    let rows: Vec<Row> = app
+        .disk_devices
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible_rows)
+        .map(|(i, disk)| {
+            let style = if i == app.disk_scroll {
+                colors.selected_style()
+            } else {
+                Style::default()
+            };
+            let smart = app.smart_for(disk);
+            let pct = disk.usage_pct();
+            let bar_width = 10;
+            let filled = ((pct / 100.0) * bar_width as f64) as usize;
+            let bar = colors.block_full.repeat(filled.min(bar_width))
+                + &colors.block_empty.repeat(bar_width - filled.min(bar_width));
+
+            let (health, health_color) = health_label(smart, colors);
+            // A failing drive stands out even when another row is selected
+            let mount_color = if smart.is_some_and(SmartHealth::is_failing) {
+                colors.danger
+            } else {
+                colors.text
+            };
+            Row::new(table_columns(app, vec![
+                Cell::from(disk.mount.clone()).style(Style::default().fg(mount_color)),
+                Cell::from(disk.device.clone()).style(Style::default().fg(colors.text_dim)),
+                Cell::from(disk.file_system.clone()).style(Style::default().fg(colors.text_dim)),
+                Cell::from(format!("{bar} {pct:>3.0}%"))
+                    .style(Style::default().fg(colors.disk_usage_color(pct))),
+                Cell::from(format!("{} / {}", format_bytes(disk.used), format_bytes(disk.total))),
+                Cell::from(format_bytes(disk.read_per_sec))
+                    .style(Style::default().fg(colors.success)),
+                Cell::from(format_bytes(disk.write_per_sec))
+                    .style(Style::default().fg(colors.warning)),
+                disk.iops.map_or_else(na, |v| Cell::from(format!("{v:.0}"))),
+                disk.busy.map_or_else(na, |v| {
+                    Cell::from(format!("{v:.0}%"))
+                        .style(Style::default().fg(colors.cpu_usage_color(v)))
+                }),
+                disk.queue_depth.map_or_else(na, |v| Cell::from(format!("{v:.2}"))),
+                app.disk_temperature(disk).map_or_else(na, |t| {
+                    Cell::from(format!("{t}°C"))
+                        .style(Style::default().fg(colors.drive_temp_color(t)))
+                }),
+                Cell::from(health).style(Style::default().fg(health_color)),
+            ]))
+            .height(row_height)
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        table_columns(app, vec![
+            Constraint::Min(12),
+            Constraint::Length(16),
+            Constraint::Length(6),
+            Constraint::Length(16),
+            Constraint::Length(20),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(6),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(5),
+            Constraint::Length(7),
+        ]),
+    )
+    .header(header)
+    .block(
+        Block::bordered()
+            .title(format!(
+                " Disks ({}) — Sort: {} ",
+                app.disk_devices.len(),
+                app.disk_sort.label()
+            ))
+            .border_style(Style::default().fg(colors.disk)),
+    );
+
+    frame.render_widget(table, area);
+}
+
+/// Processes doing the most disk I/O right now, by bytes per second.
+fn draw_top_io(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    let mut busiest: Vec<_> = app.processes.iter().filter(|p| p.disk_io_rate() > 0).collect();
+    busiest.sort_by_key(|p| std::cmp::Reverse(p.disk_io_rate()));
+
+    let header = Row::new(vec![
+        Cell::from("PID"),
+        Cell::from("Name"),
+        Cell::from("Read/s"),
+        Cell::from("Write/s"),
+    ])
+    .style(
+        Style::default()
+            .fg(colors.primary)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows: Vec<Row> = busiest
+        .iter()
+        .take(TOP_IO_PROCESSES)
+        .map(|p| {
+            Row::new(vec![
+                Cell::from(p.pid.to_string()).style(Style::default().fg(colors.text_dim)),
+                Cell::from(p.name.clone()),
+                Cell::from(format_bytes(p.disk_read_rate))
+                    .style(Style::default().fg(colors.success)),
+                Cell::from(format_bytes(p.disk_write_rate))
+                    .style(Style::default().fg(colors.warning)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Min(16),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::bordered()
+            .title(" Top Disk I/O Processes ")
+            .border_style(Style::default().fg(colors.disk)),
+    );
+    frame.render_widget(table, area);
+}
+
+fn health_label(smart: Option<&SmartHealth>, colors: &ThemeColors) -> (&'static str, Color) {
+    match smart {
+        None => ("—", colors.text_dim),
+        Some(h) if h.is_failing() => ("FAIL", colors.danger),
+        Some(h) if h.passed.is_none() => ("?", colors.text_dim),
+        Some(h) if h.is_degraded() => ("WARN", colors.warning),
+        Some(_) => ("OK", colors.success),
+    }
+}
+
+/// SMART details for the selected disk's physical drive.
+fn draw_smart_detail(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    let Some(disk) = app.disk_devices.get(app.disk_scroll) else {
+        return;
+    };
+    let Some(device) = &disk.parent_disk else {
+        return;
+    };
+    let mut spans = vec![Span::styled(
+        format!(" SMART {device}: "),
+        Style::default()
+            .fg(colors.text_dim)
+            .add_modifier(Modifier::BOLD),
+    )];
+    match app.smart.get(device) {
+        None => spans.push(Span::styled("checking…", Style::default().fg(colors.text_dim))),
+        Some(h) if h.passed.is_none() => spans.push(Span::styled(
+            h.error.clone().unwrap_or_default(),
+            Style::default().fg(colors.text_dim),
+        )),
+        Some(h) => {
+            let (label, color) = if h.is_failing() {
+                ("FAILING", colors.danger)
+            } else {
+                ("PASSED", colors.success)
+            };
+            spans.push(Span::styled(label, Style::default().fg(color)));
+            if let Some(wear) = h.wear_used {
+                let color = if wear >= 90 { colors.warning } else { colors.text };
+                spans.push(Span::styled(
+                    format!("  · {wear}% endurance used"),
+                    Style::default().fg(color),
+                ));
+            }
+            if let Some(n) = h.reallocated {
+                let color = if n > 0 { colors.warning } else { colors.text };
+                spans.push(Span::styled(
+                    format!("  · {n} reallocated/media errors"),
+                    Style::default().fg(color),
+                ));
+            }
+            if let Some(t) = h.temperature {
+                spans.push(Span::styled(format!("  · {t}°C"), Style::default().fg(colors.text)));
+            }
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
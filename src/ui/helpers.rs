@@ -1,11 +1,178 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
+    widgets::{Axis, Block, Chart, Dataset, GraphType, Paragraph, Sparkline},
+    Frame,
 };
 
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::app::{format_interval, App, InputMode};
 use crate::theme::ThemeColors;
 
+/// Table rows are two lines tall in large UI mode.
+pub fn row_height(app: &App) -> u16 {
+    if app.large_ui { 2 } else { 1 }
+}
+
+/// The graph window of a history squeezed into `width` sparkline
+/// columns. Sparklines otherwise draw only the oldest `width` samples.
+pub fn fit_history(app: &App, history: &VecDeque<f64>, width: u16) -> Vec<u64> {
+    app.downsample(history, width as usize)
+        .into_iter()
+        .map(|v| v as u64)
+        .collect()
+}
+
+/// `last 1m · min 2% · avg 35% · max 90%` for a graph's border, over the
+/// samples in the graph window, with each value shown through `format`.
+pub fn graph_caption(app: &App, history: &VecDeque<f64>, format: impl Fn(f64) -> String) -> String {
+    let (mut min, mut max, mut sum) = (f64::MAX, 0.0, 0.0);
+    for &v in app.sampled(history) {
+        min = min.min(v);
+        max = f64::max(max, v);
+        sum += v;
+    }
+    let count = app.sampled(history).len();
+    let (min, avg) = if count == 0 { (0.0, 0.0) } else { (min, sum / count as f64) };
+    format!(
+        " last {} · min {} · avg {} · max {} ",
+        format_interval(app.graph_span()),
+        format(min),
+        format(avg),
+        format(max)
+    )
+}
+
+/// A sparkline of `history` with a y-axis down its left edge: the value
+/// the bars are scaled to at the top and 0 at the bottom, through `format`.
+/// Sparklines scale to their tallest bar, so without it a flat line could
+/// be bytes or gigabytes. Too small an area gets the bare sparkline.
+pub fn draw_scaled_sparkline(
+    frame: &mut Frame,
+    app: &App,
+    history: &VecDeque<f64>,
+    format: impl Fn(f64) -> String,
+    style: Style,
+    colors: &ThemeColors,
+    area: Rect,
+) {
+    let top = format(app.windowed(history).copied().reduce(f64::max).unwrap_or(0.0));
+    let axis_width = top.chars().count() as u16 + 1;
+    let (axis, graph) = if area.height >= 2 && area.width >= axis_width * 3 {
+        let [axis, graph] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(axis_width), Constraint::Min(1)])
+            .areas(area);
+        (Some(axis), graph)
+    } else {
+        (None, area)
+    };
+    let data = fit_history(app, history, graph.width);
+    let sparkline = Sparkline::default()
+        .data(&data)
+        .bar_set(colors.bar_set.clone())
+        .style(style);
+    frame.render_widget(sparkline, graph);
+
+    if let Some(axis) = axis {
+        let dim = Style::default().fg(colors.text_dim);
+        let mut lines = vec![Line::from(Span::styled(top, dim))];
+        lines.resize(axis.height as usize - 1, Line::from(""));
+        lines.push(Line::from(Span::styled("0", dim)));
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Right), axis);
+    }
+}
+
+/// One line of a history chart.
+pub struct ChartSeries<'a> {
+    /// Legend entry, shown when several series share a chart
+    pub name: &'static str,
+    pub history: &'a VecDeque<f64>,
+    pub color: Color,
+}
+
+/// `series` overlaid on a line chart with a time axis running to `now`
+/// over the graph window and a value axis from 0 to `max` (the highest
+/// sample if `None`), labelled through `format`. Long windows are
+/// downsampled to the chart's resolution like sparklines.
+pub fn draw_history_chart(
+    frame: &mut Frame,
+    app: &App,
+    series: &[ChartSeries],
+    max: Option<f64>,
+    format: impl Fn(f64) -> String,
+    colors: &ThemeColors,
+    area: Rect,
+) {
+    // Braille packs two points into a cell's width
+    let resolution = area.width as usize * 2;
+    let span = app.graph_span().as_secs_f64();
+    let points: Vec<Vec<(f64, f64)>> = series
+        .iter()
+        .map(|s| {
+            let values = app.downsample(s.history, resolution);
+            let step = span / values.len().saturating_sub(1).max(1) as f64;
+            let first = -step * values.len().saturating_sub(1) as f64;
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (first + step * i as f64, v))
+                .collect()
+        })
+        .collect();
+    let top = max.unwrap_or_else(|| {
+        let highest = points.iter().flatten().map(|&(_, v)| v).fold(0.0, f64::max);
+        if highest > 0.0 { highest } else { 1.0 }
+    });
+
+    let overlaid = series.len() > 1;
+    let datasets = series
+        .iter()
+        .zip(&points)
+        .map(|(s, data)| {
+            let dataset = Dataset::default()
+                .marker(colors.chart_marker)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(s.color))
+                .data(data);
+            if overlaid { dataset.name(s.name) } else { dataset }
+        })
+        .collect();
+
+    let dim = Style::default().fg(colors.text_dim);
+    let ago = |span: Duration| format!("-{}", format_interval(span));
+    let x_axis = Axis::default()
+        .style(dim)
+        .bounds([-span, 0.0])
+        .labels([ago(app.graph_span()), ago(app.graph_span() / 2), "now".to_string()]);
+    let y_axis = Axis::default()
+        .style(dim)
+        .bounds([0.0, top])
+        .labels(["0".to_string(), format(top / 2.0), format(top)]);
+    frame.render_widget(Chart::new(datasets).x_axis(x_axis).y_axis(y_axis), area);
+}
+
+/// In large UI mode, drop the items whose column in `columns` isn't
+/// `essential` so the rest get the room; otherwise keep them all.
+pub fn large_columns<C: Copy, T>(
+    app: &App,
+    columns: &[C],
+    essential: fn(C) -> bool,
+    items: Vec<T>,
+) -> Vec<T> {
+    if !app.large_ui {
+        return items;
+    }
+    items
+        .into_iter()
+        .zip(columns)
+        .filter_map(|(item, &column)| essential(column).then_some(item))
+        .collect()
+}
+
 pub fn info_line(label: &str, value: &str, colors: &ThemeColors) -> Line<'static> {
     Line::from(vec![
         Span::styled(
@@ -18,6 +185,19 @@ pub fn info_line(label: &str, value: &str, colors: &ThemeColors) -> Line<'static
     ])
 }
 
+/// Like `info_line`, but with the value drawn in a status color.
+pub fn status_line(label: &str, value: &str, color: Color, colors: &ThemeColors) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("  {label:<20} "),
+            Style::default()
+                .fg(colors.text_dim)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(value.to_string(), Style::default().fg(color)),
+    ])
+}
+
 pub fn detail_line(label: &str, value: &str, colors: &ThemeColors) -> Line<'static> {
     Line::from(vec![
         Span::styled(
@@ -58,3 +238,42 @@ pub fn shrink_rect(rect: Rect, horizontal: u16, vertical: u16) -> Rect {
         height: rect.height.saturating_sub(vertical * 2),
     }
 }
+
+/// The bordered filter box above a searchable table.
+pub fn draw_search_bar(
+    frame: &mut Frame,
+    title: &str,
+    query: &str,
+    mode: InputMode,
+    colors: &ThemeColors,
+    area: Rect,
+) {
+    let (label, style) = match mode {
+        InputMode::Search => (
+            format!(" Search: {}{} ", query, colors.block_full),
+            Style::default().fg(colors.accent),
+        ),
+        InputMode::Normal | InputMode::Command => {
+            if query.is_empty() {
+                (
+                    " Press / to search ".to_string(),
+                    Style::default().fg(colors.text_dim),
+                )
+            } else {
+                (
+                    format!(" Filter: {} (Esc to clear) ", query),
+                    Style::default().fg(colors.accent),
+                )
+            }
+        }
+    };
+
+    let search = Paragraph::new(label)
+        .style(style)
+        .block(
+            Block::bordered()
+                .title(title)
+                .border_style(Style::default().fg(colors.border)),
+        );
+    frame.render_widget(search, area);
+}
@@ -1,3 +1,5 @@
+mod connections;
+mod disks;
 mod helpers;
 mod network;
 mod overview;
@@ -7,42 +9,81 @@ mod system;
 
 use ratatui::Frame;
 
-use crate::app::{App, Tab};
+use crate::app::{format_interval, App, InputMode, Tab};
+use crate::keymap::Action;
 use crate::theme::ThemeColors;
+use crate::tasks::spinner_frame;
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
-    let colors = ThemeColors::from_theme(app.theme);
+    let mut colors = app.theme_colors();
+    if app.no_color {
+        colors = colors.without_color();
+    } else {
+        colors = colors.with_depth(app.color_depth);
+    }
+    if app.ascii_only {
+        colors = colors.with_ascii();
+    }
     let size = frame.area();
 
     let main_layout = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
             ratatui::layout::Constraint::Length(3),
+            ratatui::layout::Constraint::Length(1),
             ratatui::layout::Constraint::Min(0),
             ratatui::layout::Constraint::Length(1),
         ])
         .split(size);
 
     draw_tabs(frame, app, &colors, main_layout[0]);
+    draw_core_strip(frame, app, &colors, main_layout[1]);
 
+    // Lists that know their exact height overwrite this while drawing
+    app.list_rows = main_layout[2].height.saturating_sub(3) as usize;
     match app.active_tab {
-        Tab::Overview => overview::draw_overview(frame, app, &colors, main_layout[1]),
-        Tab::Processes => processes::draw_processes(frame, app, &colors, main_layout[1]),
-        Tab::SystemInfo => system::draw_system_info(frame, app, &colors, main_layout[1]),
-        Tab::NetworkDetail => network::draw_network_detail(frame, app, &colors, main_layout[1]),
+        Tab::Overview => overview::draw_overview(frame, app, &colors, main_layout[2]),
+        Tab::Processes => processes::draw_processes(frame, app, &colors, main_layout[2]),
+        Tab::SystemInfo => system::draw_system_info(frame, app, &colors, main_layout[2]),
+        Tab::NetworkDetail => network::draw_network_detail(frame, app, &colors, main_layout[2]),
+        Tab::Disks => disks::draw_disks(frame, app, &colors, main_layout[2]),
+        Tab::Connections => {
+            connections::draw_connections(frame, app, &colors, main_layout[2])
+        }
     }
 
-    draw_footer(frame, app, &colors, main_layout[2]);
+    draw_footer(frame, app, &colors, main_layout[3]);
 
     if app.show_help {
-        popups::draw_help_popup(frame, &colors);
+        popups::draw_help_popup(frame, app, &colors);
+    }
+    if app.column_chooser.is_some() {
+        popups::draw_column_chooser(frame, app, &colors);
     }
     if app.kill_confirm.is_some() {
         popups::draw_kill_confirm(frame, app, &colors);
     }
+    if app.kill_all_confirm.is_some() {
+        popups::draw_kill_all_confirm(frame, app, &colors);
+    }
     if app.show_process_detail {
         popups::draw_process_detail(frame, app, &colors);
     }
+    if app.profile_report.is_some() {
+        popups::draw_profile(frame, app, &colors);
+    }
+    if app.disk_detail.is_some() {
+        popups::draw_disk_detail(frame, app, &colors);
+    }
+    if app.interface_detail.is_some() {
+        popups::draw_interface_detail(frame, app, &colors);
+    }
+    if app.show_command_output {
+        popups::draw_command_output(frame, app, &colors);
+    }
+    if app.show_events {
+        popups::draw_events(frame, app, &colors);
+    }
 }
 
 fn draw_tabs(frame: &mut Frame, app: &App, colors: &ThemeColors, area: ratatui::layout::Rect) {
@@ -52,10 +93,15 @@ fn draw_tabs(frame: &mut Frame, app: &App, colors: &ThemeColors, area: ratatui::
     };
 
     let titles: Vec<&str> = Tab::all().iter().map(|t| t.label()).collect();
+    let title = match &app.remote {
+        Some(remote) if remote.connected => format!(" RustMonitor — {} — {} ", remote.host, app.theme_name()),
+        Some(remote) => format!(" RustMonitor — {} (disconnected) — {} ", remote.host, app.theme_name()),
+        None => format!(" RustMonitor — {} ", app.theme_name()),
+    };
     let tabs = Tabs::new(titles)
         .block(
             Block::bordered()
-                .title(format!(" RustMonitor — {} ", app.theme.label()))
+                .title(title)
                 .border_style(Style::default().fg(colors.border)),
         )
         .select(app.active_tab.index())
@@ -68,75 +114,129 @@ fn draw_tabs(frame: &mut Frame, app: &App, colors: &ThemeColors, area: ratatui::
     frame.render_widget(tabs, area);
 }
 
-fn draw_footer(frame: &mut Frame, app: &App, colors: &ThemeColors, area: ratatui::layout::Rect) {
+/// One eighth-block glyph per core, always visible under the tab bar.
+fn draw_core_strip(frame: &mut Frame, app: &App, colors: &ThemeColors, area: ratatui::layout::Rect) {
     use ratatui::{
-        style::{Modifier, Style},
+        style::Style,
         text::{Line, Span},
         widgets::Paragraph,
     };
 
-    let mut spans = vec![
-        Span::styled(
-            " q",
-            Style::default()
-                .fg(colors.danger)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" Quit  "),
-        Span::styled(
-            "Tab",
-            Style::default()
-                .fg(colors.primary)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" Tab  "),
-        Span::styled(
-            "?",
-            Style::default()
-                .fg(colors.accent)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" Help  "),
-        Span::styled(
-            "t",
-            Style::default()
-                .fg(colors.secondary)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" Theme  "),
+    let set = &colors.bar_set;
+    let levels = [
+        set.empty,
+        set.one_eighth,
+        set.one_quarter,
+        set.three_eighths,
+        set.half,
+        set.five_eighths,
+        set.three_quarters,
+        set.seven_eighths,
+        set.full,
     ];
 
-    if app.active_tab == Tab::Processes {
-        spans.extend([
+    let mut spans = vec![Span::styled(
+        format!(" CPU {:>5.1}% ", app.global_cpu),
+        Style::default().fg(colors.text_dim),
+    )];
+    let room = (area.width as usize).saturating_sub(13);
+    for history in app.cpu_history.iter().take(room) {
+        let usage = history.back().copied().unwrap_or(0.0).clamp(0.0, 100.0);
+        // Any activity shows at least one eighth so busy cores never look idle
+        let level = if usage > 0.0 {
+            ((usage / 100.0 * 8.0).round() as usize).clamp(1, 8)
+        } else {
+            0
+        };
+        spans.push(Span::styled(
+            levels[level],
+            Style::default().fg(colors.cpu_usage_color(usage)),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, colors: &ThemeColors, area: ratatui::layout::Rect) {
+    use ratatui::{
+        style::{Modifier, Style},
+        text::{Line, Span},
+        widgets::Paragraph,
+    };
+
+    if app.input_mode == InputMode::Command {
+        let prompt = Line::from(vec![
             Span::styled(
-                "/",
+                " :",
                 Style::default()
                     .fg(colors.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw(" Search  "),
+            Span::styled(format!("{}{}", app.command_input, colors.block_full), Style::default().fg(colors.text)),
             Span::styled(
-                "s",
-                Style::default()
-                    .fg(colors.warning)
-                    .add_modifier(Modifier::BOLD),
+                "   run <cmd> · run -d <cmd> (detached)",
+                Style::default().fg(colors.text_dim),
             ),
-            Span::raw(" Sort  "),
-            Span::styled(
-                "x",
-                Style::default()
-                    .fg(colors.danger)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" Kill  "),
+        ]);
+        let footer = Paragraph::new(prompt).style(Style::default().bg(colors.highlight_bg));
+        frame.render_widget(footer, area);
+        return;
+    }
+
+    let hint = |action: Action, label: &str, color| {
+        [
             Span::styled(
-                "⏎",
-                Style::default()
-                    .fg(colors.primary)
-                    .add_modifier(Modifier::BOLD),
+                app.keymap.hint(action),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
             ),
-            Span::raw(" Detail  "),
-        ]);
+            Span::raw(format!(" {label}  ")),
+        ]
+    };
+
+    let mut spans = vec![Span::raw(" ")];
+    spans.extend(hint(Action::Quit, "Quit", colors.danger));
+    spans.extend(hint(Action::NextTab, "Tab", colors.primary));
+    spans.extend(hint(Action::Help, "Help", colors.accent));
+    spans.extend(hint(Action::Theme, "Theme", colors.secondary));
+    spans.push(Span::styled(
+        format!("{}{}", app.keymap.hint(Action::Faster), app.keymap.hint(Action::Slower)),
+        Style::default()
+            .fg(colors.primary)
+            .add_modifier(Modifier::BOLD),
+    ));
+    spans.push(Span::raw(format!(" {}  ", format_interval(app.tick_rate))));
+
+    match app.active_tab {
+        Tab::Processes => {
+            spans.extend(hint(Action::Search, "Search", colors.accent));
+            spans.extend(hint(Action::Sort, "Sort", colors.warning));
+            spans.extend(hint(Action::Kill, "Kill", colors.danger));
+            spans.extend(hint(Action::Profile, "Profile", colors.secondary));
+            spans.extend(hint(Action::Export, "Export", colors.secondary));
+            spans.extend(hint(Action::Columns, "Columns", colors.secondary));
+            spans.extend(hint(Action::ProcessDetail, "Detail", colors.primary));
+        }
+        Tab::Disks => {
+            spans.extend(hint(Action::Sort, "Sort", colors.warning));
+            spans.extend(hint(Action::DiskDetail, "Detail", colors.primary));
+        }
+        Tab::NetworkDetail => {
+            spans.extend(hint(Action::InterfaceGraph, "Graph selected", colors.secondary));
+            spans.extend(hint(Action::InterfaceDetail, "Detail", colors.primary));
+        }
+        Tab::Connections => {
+            spans.extend(hint(Action::Search, "Search", colors.accent));
+            spans.extend(hint(Action::Sort, "Sort", colors.warning));
+            spans.extend(hint(Action::ReverseDns, "Names", colors.secondary));
+        }
+        Tab::Overview | Tab::SystemInfo => {}
+    }
+
+    for (label, elapsed) in app.tasks.visible() {
+        spans.push(Span::styled(
+            format!("  │ {} {label}… {}s", spinner_frame(elapsed, app.ascii_only), elapsed.as_secs()),
+            Style::default().fg(colors.warning),
+        ));
     }
 
     if let Some((msg, _)) = &app.status_message {
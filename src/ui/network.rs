@@ -1,57 +1,164 @@
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
-    widgets::{Block, Cell, Row, Sparkline, Table},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Sparkline, Table},
     Frame,
 };
 
-use crate::app::{format_bytes, App};
+use super::helpers::{
+    draw_history_chart, draw_scaled_sparkline, fit_history, graph_caption, ChartSeries,
+};
+use crate::app::{format_bytes, format_duration, App, Graph};
+use crate::ifinfo;
 use crate::theme::ThemeColors;
 
 pub fn draw_network_detail(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    let wifi_height = match app.wifi.len() {
+        0 => 0,
+        n => n as u16 + 2,
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Length(3),
+            Constraint::Length(wifi_height),
+            Constraint::Min(6),
+            Constraint::Percentage(35),
+        ])
         .split(area);
 
+    // As a line chart, download and upload share one block
+    let chart = app.line_charts.contains(&Graph::Network);
+    let mut constraints = if chart {
+        vec![Constraint::Percentage(70)]
+    } else {
+        vec![Constraint::Percentage(35), Constraint::Percentage(35)]
+    };
+    if app.ping.is_some() {
+        constraints.push(Constraint::Percentage(30));
+    } else {
+        let share = 100 / constraints.len() as u16;
+        constraints.fill(Constraint::Percentage(share));
+    }
     let spark_cols = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints(constraints)
         .split(chunks[0]);
 
+    // Either the totals or the selected interface's own history
+    let (source, rx_now, tx_now, rx_history, tx_history) = match app
+        .graphed_interface()
+        .and_then(|i| Some((i, app.interface_history.get(&i.name)?)))
+    {
+        Some((iface, (rx, tx))) => (iface.name.as_str(), iface.received, iface.transmitted, rx, tx),
+        None => ("all", app.net_rx, app.net_tx, &app.net_rx_history, &app.net_tx_history),
+    };
+
+    // History is in KB
+    let rate = |kb: f64| app.format_rate((kb * 1024.0) as u64);
+
+    if chart {
+        let block = Block::bordered()
+            .title(format!(
+                " Traffic ({source}) — ↓{}  ↑{} ",
+                app.format_rate(rx_now),
+                app.format_rate(tx_now)
+            ))
+            .title_bottom(format!(" ↓{}", graph_caption(app, rx_history, rate)))
+            .title_bottom(
+                Line::from(format!(" ↑{}", graph_caption(app, tx_history, rate))).right_aligned(),
+            )
+            .border_style(Style::default().fg(colors.network));
+        let inner = block.inner(spark_cols[0]);
+        frame.render_widget(block, spark_cols[0]);
+        let series = [
+            ChartSeries {
+                name: "↓ RX",
+                history: rx_history,
+                color: colors.success,
+            },
+            ChartSeries {
+                name: "↑ TX",
+                history: tx_history,
+                color: colors.warning,
+            },
+        ];
+        draw_history_chart(frame, app, &series, None, rate, colors, inner);
+    } else {
+        let rx = (rx_now, rx_history);
+        let tx = (tx_now, tx_history);
+        draw_traffic_sparklines(frame, app, colors, &spark_cols, source, rx, tx);
+    }
+
+    if let Some(area) = spark_cols.get(if chart { 1 } else { 2 }) {
+        draw_ping(frame, app, colors, *area);
+    }
+    draw_firewall(frame, app, colors, chunks[1]);
+    draw_wifi(frame, app, colors, chunks[2]);
+    draw_interfaces(frame, app, colors, chunks[3]);
+    draw_listening(frame, app, colors, chunks[4]);
+}
+
+/// Download and upload graphs in blocks of their own.
+fn draw_traffic_sparklines(
+    frame: &mut Frame,
+    app: &App,
+    colors: &ThemeColors,
+    spark_cols: &[Rect],
+    source: &str,
+    (rx_now, rx_history): (u64, &VecDeque<f64>),
+    (tx_now, tx_history): (u64, &VecDeque<f64>),
+) {
+    // History is in KB
+    let rate = |kb: f64| app.format_rate((kb * 1024.0) as u64);
+
     let rx_block = Block::bordered()
-        .title(format!(" ↓ Download — {}/s ", format_bytes(app.net_rx)))
+        .title(format!(" ↓ Download ({source}) — {} ", app.format_rate(rx_now)))
+        .title_bottom(graph_caption(app, rx_history, rate))
         .border_style(Style::default().fg(colors.success));
     let rx_inner = rx_block.inner(spark_cols[0]);
     frame.render_widget(rx_block, spark_cols[0]);
-    let rx_data: Vec<u64> = app.net_rx_history.iter().map(|v| *v as u64).collect();
-    let rx_spark = Sparkline::default()
-        .data(&rx_data)
-        .style(Style::default().fg(colors.success));
-    frame.render_widget(rx_spark, rx_inner);
+    let rx_style = Style::default().fg(colors.success);
+    draw_scaled_sparkline(frame, app, rx_history, rate, rx_style, colors, rx_inner);
 
     let tx_block = Block::bordered()
-        .title(format!(" ↑ Upload — {}/s ", format_bytes(app.net_tx)))
+        .title(format!(" ↑ Upload ({source}) — {} ", app.format_rate(tx_now)))
+        .title_bottom(graph_caption(app, tx_history, rate))
         .border_style(Style::default().fg(colors.warning));
     let tx_inner = tx_block.inner(spark_cols[1]);
     frame.render_widget(tx_block, spark_cols[1]);
-    let tx_data: Vec<u64> = app.net_tx_history.iter().map(|v| *v as u64).collect();
-    let tx_spark = Sparkline::default()
-        .data(&tx_data)
-        .style(Style::default().fg(colors.warning));
-    frame.render_widget(tx_spark, tx_inner);
+    let tx_style = Style::default().fg(colors.warning);
+    draw_scaled_sparkline(frame, app, tx_history, rate, tx_style, colors, tx_inner);
+}
 
-    let header = Row::new(vec![
-        Cell::from("Interface"),
-        Cell::from("MAC"),
+/// Interfaces that are administratively or physically down are greyed
+/// out so a dead link stands out from an idle one.
+fn draw_interfaces(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    // The MAC is in the Enter popup; drop it before squeezing the counters
+    let show_mac = area.width >= 160;
+    let mut header_cells = vec![Cell::from("Interface")];
+    if show_mac {
+        header_cells.push(Cell::from("MAC"));
+    }
+    header_cells.extend([
+        Cell::from("State"),
+        Cell::from("Speed"),
+        Cell::from("MTU"),
         Cell::from("RX"),
         Cell::from("TX"),
+        Cell::from("Session ↓/↑"),
+        Cell::from("Since Boot ↓/↑"),
         Cell::from("Pkts In"),
         Cell::from("Pkts Out"),
-        Cell::from("Err In"),
-        Cell::from("Err Out"),
-    ])
-    .style(
+        Cell::from("Errors ↓/↑"),
+        Cell::from("Drops ↓/↑"),
+    ]);
+    let header = Row::new(header_cells).style(
         Style::default()
             .fg(colors.primary)
             .add_modifier(Modifier::BOLD),
@@ -62,53 +169,376 @@ pub fn draw_network_detail(frame: &mut Frame, app: &App, colors: &ThemeColors, a
         .iter()
         .enumerate()
         .map(|(i, iface)| {
+            let down = iface.is_down();
+            let saturated = app.saturated_interfaces.contains(&iface.name);
             let style = if i == app.network_scroll {
-                Style::default().bg(colors.highlight_bg)
+                colors.selected_style()
+            } else if down {
+                Style::default().fg(colors.text_dim)
             } else {
                 Style::default()
             };
-            Row::new(vec![
-                Cell::from(iface.name.clone()).style(Style::default().fg(colors.text)),
-                Cell::from(iface.mac_address.clone()).style(Style::default().fg(colors.text_dim)),
-                Cell::from(format_bytes(iface.received)).style(Style::default().fg(colors.success)),
-                Cell::from(format_bytes(iface.transmitted))
-                    .style(Style::default().fg(colors.warning)),
-                Cell::from(iface.packets_in.to_string()),
-                Cell::from(iface.packets_out.to_string()),
-                Cell::from(iface.errors_in.to_string()).style(if iface.errors_in > 0 {
-                    Style::default().fg(colors.danger)
+            // Down rows keep the dim row style instead of per-cell colors
+            let fg = |color| {
+                if down {
+                    Style::default()
                 } else {
-                    Style::default().fg(colors.text_dim)
-                }),
-                Cell::from(iface.errors_out.to_string()).style(if iface.errors_out > 0 {
-                    Style::default().fg(colors.danger)
+                    Style::default().fg(color)
+                }
+            };
+            let rate_style = |color| {
+                if saturated {
+                    Style::default().fg(colors.danger).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(colors.text_dim)
+                    fg(color)
+                }
+            };
+            let mut cells = vec![if saturated {
+                Cell::from(format!("⚠ {}", iface.name)).style(rate_style(colors.text))
+            } else {
+                Cell::from(iface.name.clone()).style(fg(colors.text))
+            }];
+            if show_mac {
+                cells.push(Cell::from(iface.mac_address.clone()).style(fg(colors.text_dim)));
+            }
+            let state = iface.link.state.as_deref().unwrap_or("—");
+            cells.extend([
+                Cell::from(state.to_string()).style(match state {
+                    "up" => fg(colors.success),
+                    "down" | "lowerlayerdown" | "notpresent" => Style::default().fg(colors.danger),
+                    _ => fg(colors.text_dim),
                 }),
+                Cell::from(
+                    iface
+                        .link
+                        .speed_mbps
+                        .map(ifinfo::format_speed)
+                        .unwrap_or_else(|| "—".into()),
+                )
+                .style(fg(speed_color(iface.link.speed_mbps, colors))),
+                Cell::from(iface.mtu.to_string()).style(fg(colors.text_dim)),
+                Cell::from(app.format_rate(iface.received)).style(rate_style(colors.success)),
+                Cell::from(app.format_rate(iface.transmitted)).style(rate_style(colors.warning)),
+                Cell::from(format!(
+                    "{} / {}",
+                    format_bytes(iface.session_received),
+                    format_bytes(iface.session_transmitted)
+                )),
+                Cell::from(format!(
+                    "{} / {}",
+                    format_bytes(iface.total_received),
+                    format_bytes(iface.total_transmitted)
+                ))
+                .style(fg(colors.text_dim)),
+                Cell::from(iface.packets_in.to_string()),
+                Cell::from(iface.packets_out.to_string()),
+                Cell::from(format!("{} / {}", iface.errors_in, iface.errors_out)).style(
+                    if iface.errors_in + iface.errors_out > 0 {
+                        Style::default().fg(colors.danger)
+                    } else {
+                        fg(colors.text_dim)
+                    },
+                ),
+                match iface.drops {
+                    Some((rx, tx)) => Cell::from(format!("{rx} / {tx}")).style(if rx + tx > 0 {
+                        Style::default().fg(colors.danger)
+                    } else {
+                        fg(colors.text_dim)
+                    }),
+                    None => Cell::from("—").style(fg(colors.text_dim)),
+                },
+            ]);
+            Row::new(cells).style(style)
+        })
+        .collect();
+
+    let mut widths = vec![Constraint::Min(10)];
+    if show_mac {
+        widths.push(Constraint::Length(18));
+    }
+    widths.extend([
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(21),
+        Constraint::Length(21),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ]);
+
+    let (session_rx, session_tx) = app.session_transfer();
+    let (boot_rx, boot_tx) = app.boot_transfer();
+    let table = Table::new(rows, widths).header(header).block(
+        Block::bordered()
+            .title(format!(
+                " Interfaces ({}) — session ↓{} ↑{} · since boot ↓{} ↑{} ",
+                app.network_interfaces.len(),
+                format_bytes(session_rx),
+                format_bytes(session_tx),
+                format_bytes(boot_rx),
+                format_bytes(boot_tx)
+            ))
+            .border_style(Style::default().fg(colors.network)),
+    );
+    frame.render_widget(table, area);
+}
+
+/// Links that negotiated below gigabit are worth a second look; on a
+/// desktop or server NIC it usually means a bad cable or port.
+fn speed_color(speed_mbps: Option<u32>, colors: &ThemeColors) -> ratatui::style::Color {
+    match speed_mbps {
+        Some(s) if s < 1000 => colors.warning,
+        Some(_) => colors.text,
+        None => colors.text_dim,
+    }
+}
+
+/// Round-trip times with lost replies marked, so lag spikes can be lined
+/// up against the traffic graphs beside it.
+fn draw_ping(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    let Some(monitor) = &app.ping else {
+        return;
+    };
+    let target = match (&monitor.target, monitor.is_gateway) {
+        (Some(host), true) => format!("{host} (gw)"),
+        (Some(host), false) => host.clone(),
+        (None, _) => "no default gateway".to_string(),
+    };
+    let loss = monitor.loss_pct();
+    let (title, color) = match monitor.last() {
+        Some(rtt) => (
+            format!(" Ping {target} — {rtt:.1} ms · {loss:.0}% loss "),
+            if loss > 0.0 { colors.warning } else { colors.primary },
+        ),
+        None if monitor.history.is_empty() => (format!(" Ping {target} "), colors.border),
+        None => (format!(" Ping {target} — timeout · {loss:.0}% loss "), colors.danger),
+    };
+    let block = Block::bordered()
+        .title(title)
+        .border_style(Style::default().fg(color));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Newest samples on the right, like the other sparklines
+    let skip = monitor.history.len().saturating_sub(inner.width as usize);
+    let data: Vec<Option<u64>> = monitor
+        .history
+        .iter()
+        .skip(skip)
+        .map(|rtt| rtt.map(|ms| (ms * 10.0).round().max(1.0) as u64))
+        .collect();
+    let spark = Sparkline::default()
+        .data(data)
+        .bar_set(colors.bar_set.clone())
+        .absent_value_symbol("×")
+        .absent_value_style(Style::default().fg(colors.danger))
+        .style(Style::default().fg(colors.primary));
+    frame.render_widget(spark, inner);
+}
+
+/// One line per wireless interface: network, signal, channel and rate,
+/// with the recent signal quality as a sparkline and its range.
+fn draw_wifi(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    if app.wifi.is_empty() {
+        return;
+    }
+    let block = Block::bordered()
+        .title(" Wi-Fi ")
+        .border_style(Style::default().fg(colors.network));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); app.wifi.len()])
+        .split(inner);
+    let dim = Style::default().fg(colors.text_dim);
+
+    for (wifi, row) in app.wifi.iter().zip(rows.iter()) {
+        let history = app.wifi_signal_history.get(&wifi.interface);
+        let caption = history
+            .map(|h| graph_caption(app, h, |v| format!("{v:.0}%")))
+            .unwrap_or_default();
+        let cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(40),
+                Constraint::Length(caption.chars().count() as u16),
+                Constraint::Length(32),
+            ])
+            .split(*row);
+
+        let mut spans = vec![Span::styled(
+            format!(" {:<10}", wifi.interface),
+            Style::default().fg(colors.text).add_modifier(Modifier::BOLD),
+        )];
+        if !wifi.connected {
+            spans.push(Span::styled("Not connected", dim));
+        } else {
+            spans.push(Span::styled(
+                format!("{:<20}", wifi.ssid.as_deref().unwrap_or("(hidden SSID)")),
+                Style::default().fg(colors.primary),
+            ));
+            if let (Some(dbm), Some(quality)) = (wifi.signal_dbm, wifi.quality()) {
+                let color = match quality {
+                    60.. => colors.success,
+                    30.. => colors.warning,
+                    _ => colors.danger,
+                };
+                spans.push(Span::styled(
+                    format!("  {dbm} dBm ({quality}%)"),
+                    Style::default().fg(color),
+                ));
+            }
+            if let Some(channel) = wifi.channel {
+                let freq = wifi
+                    .frequency_mhz
+                    .map(|f| format!(" · {f} MHz"))
+                    .unwrap_or_default();
+                spans.push(Span::styled(format!("  ch {channel}{freq}"), dim));
+            }
+            if let Some(rate) = wifi.bitrate_mbps {
+                spans.push(Span::styled(
+                    format!("  {rate:.0} Mbit/s"),
+                    Style::default().fg(colors.text),
+                ));
+            }
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), cols[0]);
+        frame.render_widget(Paragraph::new(Span::styled(caption, dim)), cols[1]);
+
+        if let Some(history) = history {
+            let data = fit_history(app, history, cols[2].width);
+            let spark = Sparkline::default()
+                .data(&data)
+                .bar_set(colors.bar_set.clone())
+                .max(100)
+                .style(Style::default().fg(colors.network));
+            frame.render_widget(spark, cols[2]);
+        }
+    }
+}
+
+/// Every listening socket with its owner; ones bound to all interfaces
+/// are what the machine exposes, so they come first and stand out.
+fn draw_listening(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    let ports = app.listening_ports();
+    let exposed = ports.iter().filter(|c| c.is_exposed()).count();
+
+    let header = Row::new(vec![
+        Cell::from("Port"),
+        Cell::from("Proto"),
+        Cell::from("Address"),
+        Cell::from("PID"),
+        Cell::from("Process"),
+    ])
+    .style(
+        Style::default()
+            .fg(colors.primary)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let visible_rows = area.height.saturating_sub(3) as usize;
+    let rows: Vec<Row> = ports
+        .iter()
+        .take(visible_rows)
+        .map(|conn| {
+            let address_style = if conn.is_exposed() {
+                Style::default().fg(colors.warning).add_modifier(Modifier::BOLD)
+            } else if conn.local.ip().is_loopback() {
+                Style::default().fg(colors.text_dim)
+            } else {
+                Style::default().fg(colors.text)
+            };
+            let (pid, process) = match conn.pid {
+                Some(pid) => (pid.to_string(), conn.process.clone()),
+                None => ("—".into(), "—".into()),
+            };
+            Row::new(vec![
+                Cell::from(conn.local.port().to_string()),
+                Cell::from(conn.proto_label()),
+                Cell::from(conn.local.ip().to_string()).style(address_style),
+                Cell::from(pid),
+                Cell::from(process),
             ])
-            .style(style)
         })
         .collect();
 
+    let hidden = ports.len().saturating_sub(visible_rows);
+    let more = if hidden > 0 { format!(", {hidden} more") } else { String::new() };
     let table = Table::new(
         rows,
         [
-            Constraint::Min(14),
-            Constraint::Length(18),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Min(16),
             Constraint::Length(8),
+            Constraint::Min(16),
         ],
     )
     .header(header)
     .block(
         Block::bordered()
-            .title(format!(" Interfaces ({}) ", app.network_interfaces.len()))
-            .border_style(Style::default().fg(colors.network)),
+            .title(format!(
+                " Listening Ports ({}, {exposed} on all interfaces{more}) ",
+                ports.len()
+            ))
+            .border_style(Style::default().fg(if exposed > 0 {
+                colors.warning
+            } else {
+                colors.network
+            })),
     );
+    frame.render_widget(table, area);
+}
 
-    frame.render_widget(table, chunks[1]);
+fn draw_firewall(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    let fw = &app.firewall;
+    let mut spans = Vec::new();
+    let border = match fw.enabled {
+        Some(true) => {
+            spans.push(Span::styled(" ● Enabled", Style::default().fg(colors.success)));
+            spans.push(Span::raw(format!("   Rules: {}", fw.rules)));
+            if let Some(policy) = &fw.default_policy {
+                spans.push(Span::raw(format!("   Default: {policy}")));
+            }
+            colors.border
+        }
+        Some(false) => {
+            spans.push(Span::styled(
+                " ⚠ DISABLED ",
+                Style::default()
+                    .fg(colors.text)
+                    .bg(colors.danger)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            colors.danger
+        }
+        None => {
+            spans.push(Span::styled(
+                " Unavailable (listing rules usually requires root)",
+                Style::default().fg(colors.text_dim),
+            ));
+            colors.border
+        }
+    };
+    if let Some(changed) = fw.last_change
+        && let Ok(ago) = SystemTime::now().duration_since(changed)
+    {
+        spans.push(Span::styled(
+            format!("   Last change: {} ago", format_duration(ago.as_secs())),
+            Style::default().fg(colors.text_dim),
+        ));
+    }
+
+    let summary = Paragraph::new(Line::from(spans)).block(
+        Block::bordered()
+            .title(format!(" Firewall ({}) ", fw.backend))
+            .border_style(Style::default().fg(border)),
+    );
+    frame.render_widget(summary, area);
 }
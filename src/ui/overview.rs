@@ -1,12 +1,20 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::Style,
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Gauge, Paragraph, Sparkline},
+    widgets::{Block, Cell, Gauge, Paragraph, Row, Sparkline, Table},
     Frame,
 };
 
-use crate::app::{format_bytes, App};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use super::helpers::{
+    draw_history_chart, draw_scaled_sparkline, fit_history, graph_caption, row_height,
+    ChartSeries,
+};
+use crate::app::{format_bytes, App, GpuInfo, Graph};
+use crate::meminfo::MemoryBreakdown;
 use crate::theme::ThemeColors;
 
 pub fn draw_overview(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
@@ -54,6 +62,7 @@ fn draw_cpu(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
             " CPU — {:.1}% ({} cores) ",
             app.global_cpu, app.cpu_count
         ))
+        .title_bottom(graph_caption(app, &app.global_cpu_history, |v| format!("{v:.0}%")))
         .border_style(Style::default().fg(colors.cpu));
 
     let inner = block.inner(area);
@@ -63,25 +72,42 @@ fn draw_cpu(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
         return;
     }
 
+    // A chart needs room for its axes; the cores make do with the rest
+    let chart = app.line_charts.contains(&Graph::Cpu);
+    let graph_height = if chart { (inner.height / 2).max(3) } else { 3 };
     let sections = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .constraints([Constraint::Length(graph_height), Constraint::Min(1)])
         .split(inner);
 
-    let global_data: Vec<u64> = app.global_cpu_history.iter().map(|v| *v as u64).collect();
-    let sparkline = Sparkline::default()
-        .data(&global_data)
-        .max(100)
-        .style(Style::default().fg(colors.cpu));
-    frame.render_widget(sparkline, sections[0]);
+    if chart {
+        let series = [ChartSeries {
+            name: "CPU",
+            history: &app.global_cpu_history,
+            color: colors.cpu,
+        }];
+        let percent = |v: f64| format!("{v:.0}%");
+        draw_history_chart(frame, app, &series, Some(100.0), percent, colors, sections[0]);
+    } else {
+        let global_data = fit_history(app, &app.global_cpu_history, sections[0].width);
+        let sparkline = Sparkline::default()
+            .data(&global_data)
+            .bar_set(colors.bar_set.clone())
+            .max(100)
+            .style(Style::default().fg(colors.cpu));
+        frame.render_widget(sparkline, sections[0]);
+    }
 
-    let cores_to_show = app.cpu_count.min(sections[1].height as usize);
+    let gauge_height = row_height(app);
+    let cores_to_show = app
+        .cpu_count
+        .min((sections[1].height / gauge_height) as usize);
     if cores_to_show == 0 {
         return;
     }
 
     let constraints: Vec<Constraint> = (0..cores_to_show)
-        .map(|_| Constraint::Length(1))
+        .map(|_| Constraint::Length(gauge_height))
         .chain(std::iter::once(Constraint::Min(0)))
         .collect();
 
@@ -104,35 +130,42 @@ fn draw_cpu(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
 fn draw_memory(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
     let block = Block::bordered()
         .title(" Memory ")
+        .title_bottom(graph_caption(app, &app.mem_history, |v| format!("{v:.0}%")))
         .border_style(Style::default().fg(colors.memory));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    let gauge_height = row_height(app) + 1;
+    let ram_height = if app.memory_breakdown.is_some() { row_height(app) + 2 } else { gauge_height };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(2),
-            Constraint::Length(2),
+            Constraint::Length(ram_height),
+            Constraint::Length(gauge_height),
             Constraint::Min(1),
         ])
         .split(inner);
 
-    let ram_pct = if app.total_memory > 0 {
-        ((app.used_memory as f64 / app.total_memory as f64) * 100.0) as u16
+    if let Some(breakdown) = &app.memory_breakdown {
+        draw_memory_breakdown(frame, breakdown, colors, chunks[0]);
     } else {
-        0
-    };
-    let ram_label = format!(
-        "RAM: {} / {} ({ram_pct}%)",
-        format_bytes(app.used_memory),
-        format_bytes(app.total_memory)
-    );
-    let ram_gauge = Gauge::default()
-        .gauge_style(Style::default().fg(colors.memory))
-        .percent(ram_pct.min(100))
-        .label(ram_label);
-    frame.render_widget(ram_gauge, chunks[0]);
+        let ram_pct = if app.total_memory > 0 {
+            ((app.used_memory as f64 / app.total_memory as f64) * 100.0) as u16
+        } else {
+            0
+        };
+        let ram_label = format!(
+            "RAM: {} / {} ({ram_pct}%)",
+            format_bytes(app.used_memory),
+            format_bytes(app.total_memory)
+        );
+        let ram_gauge = Gauge::default()
+            .gauge_style(Style::default().fg(colors.memory))
+            .percent(ram_pct.min(100))
+            .label(ram_label);
+        frame.render_widget(ram_gauge, chunks[0]);
+    }
 
     let swap_pct = if app.total_swap > 0 {
         ((app.used_swap as f64 / app.total_swap as f64) * 100.0) as u16
@@ -150,89 +183,212 @@ fn draw_memory(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
         .label(swap_label);
     frame.render_widget(swap_gauge, chunks[1]);
 
-    let data: Vec<u64> = app.mem_history.iter().map(|v| *v as u64).collect();
+    if app.line_charts.contains(&Graph::Memory) {
+        let series = [ChartSeries {
+            name: "RAM",
+            history: &app.mem_history,
+            color: colors.memory,
+        }];
+        let percent = |v: f64| format!("{v:.0}%");
+        draw_history_chart(frame, app, &series, Some(100.0), percent, colors, chunks[2]);
+        return;
+    }
+    let data = fit_history(app, &app.mem_history, chunks[2].width);
     let sparkline = Sparkline::default()
         .data(&data)
+        .bar_set(colors.bar_set.clone())
         .max(100)
         .style(Style::default().fg(colors.memory));
     frame.render_widget(sparkline, chunks[2]);
 }
 
+/// RAM as one bar split into used, buffers, cache and free, with a
+/// legend and the available and committed figures under it.
+fn draw_memory_breakdown(frame: &mut Frame, breakdown: &MemoryBreakdown, colors: &ThemeColors, area: Rect) {
+    let [bar_area, legend_area, commit_area] = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+
+    // Reclaimable memory gets its own glyph so the split survives NO_COLOR
+    let shade = if colors.block_full == "#" { "=" } else { "▒" };
+    let segments = [
+        ("used", breakdown.used(), colors.block_full, colors.memory),
+        ("buffers", breakdown.buffers, shade, colors.secondary),
+        ("cache", breakdown.cached, shade, colors.accent),
+        ("free", breakdown.free, colors.block_empty, colors.text_dim),
+    ];
+
+    // Segment edges from running totals, so rounding never loses a cell
+    let width = bar_area.width as u64;
+    let total = breakdown.total.max(1);
+    let mut bar = Vec::new();
+    let (mut sum, mut drawn) = (0, 0);
+    for &(_, bytes, glyph, color) in &segments {
+        sum += bytes;
+        let edge = (sum.min(total) * width / total).max(drawn);
+        bar.push(Span::styled(glyph.repeat((edge - drawn) as usize), Style::default().fg(color)));
+        drawn = edge;
+    }
+    bar.push(Span::styled(
+        colors.block_empty.repeat((width - drawn) as usize),
+        Style::default().fg(colors.text_dim),
+    ));
+    let bar = Line::from(bar);
+    frame.render_widget(Paragraph::new(vec![bar; bar_area.height as usize]), bar_area);
+
+    let legend: Vec<Span> = segments
+        .iter()
+        .flat_map(|&(name, bytes, glyph, color)| {
+            [
+                Span::styled(glyph, Style::default().fg(color)),
+                Span::styled(format!(" {name} {}  ", format_bytes(bytes)), Style::default().fg(colors.text)),
+            ]
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(Line::from(legend)), legend_area);
+
+    let commit_pct = if breakdown.commit_limit > 0 {
+        breakdown.committed as f64 / breakdown.commit_limit as f64 * 100.0
+    } else {
+        0.0
+    };
+    // Past the limit only matters with strict overcommit, but it means
+    // the promises outrun RAM and swap together
+    let commit_color = if commit_pct > 100.0 { colors.warning } else { colors.text };
+    let commit = Line::from(vec![
+        Span::styled("available ", Style::default().fg(colors.text_dim)),
+        Span::styled(
+            format!("{} of {}", format_bytes(breakdown.available), format_bytes(breakdown.total)),
+            Style::default().fg(colors.text),
+        ),
+        Span::styled(" · committed ", Style::default().fg(colors.text_dim)),
+        Span::styled(
+            format!("{} ({commit_pct:.0}% of limit)", format_bytes(breakdown.committed)),
+            Style::default().fg(commit_color),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(commit), commit_area);
+}
+
 fn draw_network_overview(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
     let block = Block::bordered()
         .title(format!(
-            " Network — ↓{}/s  ↑{}/s ",
-            format_bytes(app.net_rx),
-            format_bytes(app.net_tx)
+            " Network — ↓{}  ↑{} ",
+            app.format_rate(app.net_rx),
+            app.format_rate(app.net_tx)
         ))
         .border_style(Style::default().fg(colors.network));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    // One chart under both captions, or a sparkline under each
+    let chart = app.line_charts.contains(&Graph::Network);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1),
-            Constraint::Min(1),
-            Constraint::Length(1),
-            Constraint::Min(1),
-        ])
+        .constraints(if chart {
+            [
+                Constraint::Length(1),
+                Constraint::Length(0),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ]
+        } else {
+            [
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ]
+        })
         .split(inner);
 
+    // History is in KB
+    let rate = |kb: f64| app.format_rate((kb * 1024.0) as u64);
+    let dim = Style::default().fg(colors.text_dim);
+
     let rx_label = Paragraph::new(Line::from(vec![
         Span::styled("↓ RX ", Style::default().fg(colors.success)),
+        Span::styled(graph_caption(app, &app.net_rx_history, rate), dim),
     ]));
     frame.render_widget(rx_label, chunks[0]);
-
-    let rx_data: Vec<u64> = app.net_rx_history.iter().map(|v| *v as u64).collect();
-    let rx_spark = Sparkline::default()
-        .data(&rx_data)
-        .style(Style::default().fg(colors.success));
-    frame.render_widget(rx_spark, chunks[1]);
-
     let tx_label = Paragraph::new(Line::from(vec![
         Span::styled("↑ TX ", Style::default().fg(colors.warning)),
+        Span::styled(graph_caption(app, &app.net_tx_history, rate), dim),
     ]));
     frame.render_widget(tx_label, chunks[2]);
 
-    let tx_data: Vec<u64> = app.net_tx_history.iter().map(|v| *v as u64).collect();
-    let tx_spark = Sparkline::default()
-        .data(&tx_data)
-        .style(Style::default().fg(colors.warning));
-    frame.render_widget(tx_spark, chunks[3]);
+    if chart {
+        let series = [
+            ChartSeries {
+                name: "RX",
+                history: &app.net_rx_history,
+                color: colors.success,
+            },
+            ChartSeries {
+                name: "TX",
+                history: &app.net_tx_history,
+                color: colors.warning,
+            },
+        ];
+        draw_history_chart(frame, app, &series, None, rate, colors, chunks[3]);
+        return;
+    }
+    let rx_style = Style::default().fg(colors.success);
+    draw_scaled_sparkline(frame, app, &app.net_rx_history, rate, rx_style, colors, chunks[1]);
+    let tx_style = Style::default().fg(colors.warning);
+    draw_scaled_sparkline(frame, app, &app.net_tx_history, rate, tx_style, colors, chunks[3]);
 }
 
 fn draw_disks(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
     let block = Block::bordered()
-        .title(" Disks ")
+        .title(format!(
+            " Disks — R {}/s  W {}/s ",
+            format_bytes(app.disk_read),
+            format_bytes(app.disk_write)
+        ))
         .border_style(Style::default().fg(colors.disk));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(2),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(2),
+        ])
+        .split(inner);
+
     let mut lines: Vec<Line> = Vec::new();
-    for disk in app.disks.iter() {
-        let total = disk.total_space();
-        let available = disk.available_space();
-        let used = total.saturating_sub(available);
-        let pct = if total > 0 {
-            (used as f64 / total as f64) * 100.0
-        } else {
-            0.0
-        };
-        let mount = disk.mount_point().to_string_lossy();
-        let fs = disk.file_system().to_string_lossy();
+    for disk in app.disk_devices.iter() {
+        let (total, used) = (disk.total, disk.used);
+        let pct = disk.usage_pct();
+        let (mount, fs) = (&disk.mount, &disk.file_system);
         let bar_width = 16;
         let filled = ((pct / 100.0) * bar_width as f64) as usize;
-        let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
+        let bar: String =
+            colors.block_full.repeat(filled) + &colors.block_empty.repeat(bar_width - filled);
 
         let color = colors.disk_usage_color(pct);
 
-        lines.push(Line::from(vec![
+        let mut header = vec![
             Span::styled(format!("{:<4}", mount), Style::default().fg(colors.text)),
             Span::styled(format!(" [{fs}] "), Style::default().fg(colors.text_dim)),
-        ]));
+        ];
+        if let Some(t) = app.disk_temperature(disk) {
+            header.push(Span::styled(
+                format!("{t}°C"),
+                Style::default().fg(colors.drive_temp_color(t)),
+            ));
+        }
+        lines.push(Line::from(header));
         lines.push(Line::from(vec![
             Span::styled(format!("  {bar} "), Style::default().fg(color)),
             Span::styled(
@@ -245,10 +401,87 @@ fn draw_disks(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
                 Style::default().fg(colors.text_dim),
             ),
         ]));
+        if let Some(line) = app
+            .disk_used_history
+            .get(mount)
+            .and_then(|history| growth_line(history, bar_width, colors))
+        {
+            lines.push(line);
+        }
     }
 
     let paragraph = Paragraph::new(lines);
-    frame.render_widget(paragraph, inner);
+    frame.render_widget(paragraph, chunks[0]);
+
+    // History is in KB
+    let rate = |kb: f64| format!("{}/s", format_bytes((kb * 1024.0) as u64));
+    let dim = Style::default().fg(colors.text_dim);
+
+    let read_label = Paragraph::new(Line::from(vec![
+        Span::styled("R Read ", Style::default().fg(colors.success)),
+        Span::styled(graph_caption(app, &app.disk_read_history, rate), dim),
+    ]));
+    frame.render_widget(read_label, chunks[1]);
+    let read_style = Style::default().fg(colors.success);
+    draw_scaled_sparkline(frame, app, &app.disk_read_history, rate, read_style, colors, chunks[2]);
+
+    let write_label = Paragraph::new(Line::from(vec![
+        Span::styled("W Write ", Style::default().fg(colors.warning)),
+        Span::styled(graph_caption(app, &app.disk_write_history, rate), dim),
+    ]));
+    frame.render_widget(write_label, chunks[3]);
+    let write_style = Style::default().fg(colors.warning);
+    let write_history = &app.disk_write_history;
+    draw_scaled_sparkline(frame, app, write_history, rate, write_style, colors, chunks[4]);
+}
+
+/// Used-space growth per sample as a sparkline the width of the usage bar,
+/// with the rate over that window; `None` while the volume isn't growing.
+fn growth_line(
+    history: &VecDeque<(Instant, u64)>,
+    width: usize,
+    colors: &ThemeColors,
+) -> Option<Line<'static>> {
+    let history: Vec<&(Instant, u64)> = history
+        .iter()
+        .skip(history.len().saturating_sub(width + 1))
+        .collect();
+    let deltas: Vec<u64> = history
+        .windows(2)
+        .map(|pair| pair[1].1.saturating_sub(pair[0].1))
+        .collect();
+    let peak = deltas.iter().copied().max().filter(|&p| p > 0)?;
+
+    let set = &colors.bar_set;
+    let levels = [
+        set.empty,
+        set.one_eighth,
+        set.one_quarter,
+        set.three_eighths,
+        set.half,
+        set.five_eighths,
+        set.three_quarters,
+        set.seven_eighths,
+        set.full,
+    ];
+    let spark: String = deltas
+        .iter()
+        .map(|&d| match d {
+            0 => levels[0],
+            d => levels[((d as f64 / peak as f64 * 8.0).round() as usize).clamp(1, 8)],
+        })
+        .collect();
+    // Sampled at whatever the refresh rate was at the time
+    let window_secs = history[history.len() - 1].0.duration_since(history[0].0).as_secs_f64();
+    let per_min = deltas.iter().sum::<u64>() as f64 / window_secs * 60.0;
+    let color = colors.disk_growth_color(per_min);
+    Some(Line::from(vec![
+        Span::styled(format!("  {spark:>width$} "), Style::default().fg(color)),
+        Span::styled(
+            format!("+{}/min", format_bytes(per_min as u64)),
+            Style::default().fg(color),
+        ),
+    ]))
 }
 
 fn draw_gpu(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
@@ -263,6 +496,8 @@ fn draw_gpu(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
         .constraints(gpu_cols)
         .split(area);
 
+    // Index of each GPU's first process in the Overview selection order
+    let mut first_process = 0;
     for (i, gpu) in app.gpus.iter().enumerate() {
         let mem_pct = if gpu.memory_total > 0 {
             ((gpu.memory_used as f64 / gpu.memory_total as f64) * 100.0) as u16
@@ -281,21 +516,32 @@ fn draw_gpu(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
             None => String::new(),
         };
 
-        let block = Block::bordered()
+        let clock_str = match gpu.core_clock_mhz {
+            Some(mhz) => format!(" @ {mhz} MHz"),
+            None => String::new(),
+        };
+
+        let history = app.gpu_util_history.get(i);
+        let mut block = Block::bordered()
             .title(format!(
-                " {} — {}°C  {}%{}{} ",
-                gpu.name, gpu.temperature, gpu.utilization, fan_str, power_str
+                " {} — {}°C  {}%{}{}{} ",
+                gpu.name, gpu.temperature, gpu.utilization, clock_str, fan_str, power_str
             ))
             .border_style(Style::default().fg(colors.accent));
+        if let Some(history) = history {
+            block = block.title_bottom(graph_caption(app, history, |v| format!("{v:.0}%")));
+        }
 
         let inner = block.inner(cols[i]);
         frame.render_widget(block, cols[i]);
 
+        let extras = gpu_extras(gpu);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(1),
                 Constraint::Length(1),
+                Constraint::Length(if extras.is_empty() { 0 } else { 1 }),
                 Constraint::Min(1),
             ])
             .split(inner);
@@ -316,13 +562,118 @@ fn draw_gpu(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
             ));
         frame.render_widget(vram_gauge, chunks[1]);
 
-        if let Some(history) = app.gpu_util_history.get(i) {
-            let data: Vec<u64> = history.iter().map(|v| *v as u64).collect();
+        if !extras.is_empty() {
+            let line = Paragraph::new(Line::from(Span::styled(
+                extras,
+                Style::default().fg(colors.text_dim),
+            )));
+            frame.render_widget(line, chunks[2]);
+        }
+
+        let bottom = if gpu.processes.is_empty() {
+            [chunks[3], Rect::default()]
+        } else {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .areas(chunks[3])
+        };
+
+        if let Some(history) = history {
+            let data = fit_history(app, history, bottom[0].width);
             let sparkline = Sparkline::default()
                 .data(&data)
+                .bar_set(colors.bar_set.clone())
                 .max(100)
                 .style(Style::default().fg(colors.accent));
-            frame.render_widget(sparkline, chunks[2]);
+            frame.render_widget(sparkline, bottom[0]);
+        }
+
+        if !gpu.processes.is_empty() {
+            let selected = app.gpu_process_scroll.checked_sub(first_process);
+            draw_gpu_processes(frame, gpu, selected, colors, bottom[1]);
         }
+        first_process += gpu.processes.len();
+    }
+}
+
+/// PID / name / GPU memory table, scrolled so the selected row stays visible.
+fn draw_gpu_processes(
+    frame: &mut Frame,
+    gpu: &GpuInfo,
+    selected: Option<usize>,
+    colors: &ThemeColors,
+    area: Rect,
+) {
+    let visible = area.height.saturating_sub(1).max(1) as usize;
+    let offset = selected
+        .filter(|&s| s < gpu.processes.len())
+        .map_or(0, |s| s.saturating_sub(visible - 1));
+
+    let header = Row::new(vec![
+        Cell::from("PID"),
+        Cell::from("Name"),
+        Cell::from("Type"),
+        Cell::from("GPU Mem"),
+    ])
+    .style(
+        Style::default()
+            .fg(colors.primary)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let rows: Vec<Row> = gpu
+        .processes
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .map(|(i, p)| {
+            let style = if selected == Some(i) {
+                colors.selected_style()
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(p.pid.to_string()).style(Style::default().fg(colors.text_dim)),
+                Cell::from(p.name.clone()).style(Style::default().fg(colors.text)),
+                Cell::from(p.kind()).style(Style::default().fg(colors.text_dim)),
+                Cell::from(p.memory.map(format_bytes).unwrap_or_else(|| "N/A".into()))
+                    .style(Style::default().fg(colors.memory)),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(7),
+            Constraint::Min(8),
+            Constraint::Length(4),
+            Constraint::Length(9),
+        ],
+    )
+    .header(header);
+    frame.render_widget(table, area);
+}
+
+/// One-line summary of the optional sensor readings a GPU backend reported.
+fn gpu_extras(gpu: &GpuInfo) -> String {
+    let mut parts = Vec::new();
+    if let Some(mhz) = gpu.memory_clock_mhz {
+        parts.push(format!("Mem {mhz} MHz"));
+    }
+    if let Some(busy) = gpu.memory_busy {
+        parts.push(format!("Mem Busy {busy}%"));
+    }
+    if let Some(t) = gpu.temp_junction {
+        parts.push(format!("Junction {t}°C"));
+    }
+    if let Some(t) = gpu.temp_memory {
+        parts.push(format!("VRAM {t}°C"));
+    }
+    if let Some(rpm) = gpu.fan_rpm {
+        parts.push(format!("Fan {rpm} RPM"));
     }
+    parts.join("  ")
 }
@@ -1,101 +1,144 @@
+use std::collections::VecDeque;
+
 use ratatui::{
-    style::{Modifier, Style},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Clear, Paragraph, Wrap},
+    widgets::{Block, Clear, Paragraph, Sparkline, Wrap},
     Frame,
 };
 
 use crate::app::{format_bytes, format_duration, App};
+use crate::events::EventLevel;
+use crate::ifinfo::{self, format_speed};
+use crate::keymap::ACTIONS;
 use crate::theme::ThemeColors;
 use super::helpers::{centered_rect, detail_line};
 
-pub fn draw_help_popup(frame: &mut Frame, colors: &ThemeColors) {
-    let area = centered_rect(50, 60, frame.area());
+/// Thread-name groups listed in the process detail popup.
+const MAX_THREAD_GROUPS: usize = 5;
+/// Children listed at once in the process detail popup
+const MAX_CHILDREN: usize = 8;
+/// Rows for the CPU and memory sparklines under the process details
+const PROCESS_GRAPH_HEIGHT: u16 = 5;
+
+/// Every bound action from the keymap, the active tab's section first.
+/// Scrolls when it doesn't fit; `help_scroll` is clamped here since only
+/// drawing knows the height.
+pub fn draw_help_popup(frame: &mut Frame, app: &mut App, colors: &ThemeColors) {
+    let area = centered_rect(50, 80, frame.area());
     frame.render_widget(Clear, area);
 
-    let help_text = vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "  General",
-            Style::default()
-                .fg(colors.primary)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("    q / Esc    ", Style::default().fg(colors.accent)),
-            Span::raw("Quit application"),
-        ]),
-        Line::from(vec![
-            Span::styled("    Tab        ", Style::default().fg(colors.accent)),
-            Span::raw("Next tab"),
-        ]),
-        Line::from(vec![
-            Span::styled("    Shift+Tab  ", Style::default().fg(colors.accent)),
-            Span::raw("Previous tab"),
-        ]),
-        Line::from(vec![
-            Span::styled("    ?          ", Style::default().fg(colors.accent)),
-            Span::raw("Toggle help"),
-        ]),
-        Line::from(vec![
-            Span::styled("    t          ", Style::default().fg(colors.accent)),
-            Span::raw("Cycle theme"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "  Navigation",
-            Style::default()
-                .fg(colors.primary)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("    ↑/k ↓/j    ", Style::default().fg(colors.accent)),
-            Span::raw("Scroll up/down"),
-        ]),
-        Line::from(vec![
-            Span::styled("    PgUp/PgDn  ", Style::default().fg(colors.accent)),
-            Span::raw("Page up/down"),
-        ]),
+    let key_line = |keys: String, description: &str| {
         Line::from(vec![
-            Span::styled("    Home/End   ", Style::default().fg(colors.accent)),
-            Span::raw("Jump to top/bottom"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "  Processes",
+            Span::styled(format!("    {keys:<11}"), Style::default().fg(colors.accent)),
+            Span::raw(format!(" {description}")),
+        ])
+    };
+    // Tab labels double as section names
+    let current = app.active_tab.label().trim();
+    let mut sections: Vec<&str> = Vec::new();
+    for info in ACTIONS {
+        if !sections.contains(&info.section) {
+            sections.push(info.section);
+        }
+    }
+    sections.sort_by_key(|&section| section != current);
+
+    let mut help_text = Vec::new();
+    for section in sections {
+        let heading = if section == current {
+            format!("  {section} (this tab)")
+        } else {
+            format!("  {section}")
+        };
+        help_text.push(Line::from(""));
+        help_text.push(Line::from(vec![Span::styled(
+            heading,
             Style::default()
                 .fg(colors.primary)
                 .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("    /          ", Style::default().fg(colors.accent)),
-            Span::raw("Search processes"),
-        ]),
-        Line::from(vec![
-            Span::styled("    s          ", Style::default().fg(colors.accent)),
-            Span::raw("Cycle sort (CPU → MEM → Name → PID)"),
-        ]),
-        Line::from(vec![
-            Span::styled("    x          ", Style::default().fg(colors.accent)),
-            Span::raw("Kill selected process"),
-        ]),
-        Line::from(vec![
-            Span::styled("    Enter      ", Style::default().fg(colors.accent)),
-            Span::raw("View process details"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "  Press any key to close",
+        )]));
+        if section == "Processes" {
+            help_text.push(key_line("Tab".into(), "While searching: match cmdline / environment"));
+        }
+        // Actions without keys, like half-page scrolling outside the vim
+        // profile, are left out
+        for info in ACTIONS
+            .iter()
+            .filter(|info| info.section == section && !app.keymap.keys(info.action).is_empty())
+        {
+            help_text.push(key_line(app.keymap.label(info.action), info.description));
+        }
+        // Prompt commands go with the other general keys
+        if section == "General" {
+            help_text.push(key_line(":run cmd".into(), "Run a command (-d to detach)"));
+            help_text.push(key_line(":export-history".into(), "Graph histories to CSV"));
+        }
+    }
+
+    let block = Block::bordered()
+        .title(" Help ")
+        .border_style(Style::default().fg(colors.primary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [body, hint] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+    let max_scroll = help_text.len().saturating_sub(body.height as usize);
+    app.help_scroll = app.help_scroll.min(max_scroll);
+    let position = if max_scroll > 0 {
+        format!("  ↑/↓ PgUp/PgDn scroll ({}/{}) · ", app.help_scroll, max_scroll)
+    } else {
+        "  ".to_string()
+    };
+    frame.render_widget(
+        Paragraph::new(help_text).scroll((app.help_scroll as u16, 0)),
+        body,
+    );
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            format!("{position}any other key to close"),
             Style::default().fg(colors.text_dim),
-        )]),
-    ];
+        )),
+        hint,
+    );
+}
+
+/// Every column this build can fill, the shown ones first in table order.
+pub fn draw_column_chooser(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = centered_rect(50, 60, frame.area());
+    frame.render_widget(Clear, area);
 
-    let help = Paragraph::new(help_text).block(
+    let cursor = app.column_chooser.unwrap_or(0);
+    let mut lines = vec![Line::from("")];
+    for (row, column) in app.chooser_columns().into_iter().enumerate() {
+        let shown = app.process_columns.contains(&column);
+        let mark = if shown { "[x]" } else { "[ ]" };
+        let style = if row == cursor {
+            colors.selected_style()
+        } else {
+            Style::default()
+        };
+        lines.push(
+            Line::from(vec![
+                Span::styled(format!("  {mark} "), Style::default().fg(colors.accent)),
+                Span::styled(column.label(), Style::default().fg(colors.text)),
+            ])
+            .style(style),
+        );
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Space toggles, J/K or Shift+↑/↓ move, any other key closes",
+        Style::default().fg(colors.text_dim),
+    )));
+
+    let popup = Paragraph::new(lines).block(
         Block::bordered()
-            .title(" Help ")
+            .title(" Columns ")
             .border_style(Style::default().fg(colors.primary)),
     );
-    frame.render_widget(help, area);
+    frame.render_widget(popup, area);
 }
 
 pub fn draw_kill_confirm(frame: &mut Frame, app: &App, colors: &ThemeColors) {
@@ -103,15 +146,23 @@ pub fn draw_kill_confirm(frame: &mut Frame, app: &App, colors: &ThemeColors) {
     frame.render_widget(Clear, area);
 
     let pid = app.kill_confirm.unwrap_or(0);
+    // Not necessarily the selected row: an escalation arrives seconds later
     let name = app
-        .selected_process()
+        .processes
+        .iter()
+        .find(|p| p.pid == pid)
         .map(|p| p.name.clone())
         .unwrap_or_else(|| "?".into());
+    let (prompt, title) = if app.kill_force {
+        ("  Still running. SIGKILL ", " Force Kill ")
+    } else {
+        ("  Kill process ", " Confirm Kill ")
+    };
 
     let text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("  Kill process ", Style::default().fg(colors.danger)),
+            Span::styled(prompt, Style::default().fg(colors.danger)),
             Span::styled(
                 name,
                 Style::default()
@@ -144,7 +195,79 @@ pub fn draw_kill_confirm(frame: &mut Frame, app: &App, colors: &ThemeColors) {
 
     let popup = Paragraph::new(text).block(
         Block::bordered()
-            .title(" Confirm Kill ")
+            .title(title)
+            .border_style(Style::default().fg(colors.danger)),
+    );
+    frame.render_widget(popup, area);
+}
+
+/// Names of the processes about to be signalled, most common first.
+pub fn draw_kill_all_confirm(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = centered_rect(50, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let targets = app.kill_all_confirm.as_deref().unwrap_or_default();
+    let mut names: Vec<(&str, usize)> = Vec::new();
+    for (_, _, name) in targets {
+        match names.iter_mut().find(|(n, _)| n == name) {
+            Some((_, count)) => *count += 1,
+            None => names.push((name, 1)),
+        }
+    }
+    names.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Send SIGTERM to ", Style::default().fg(colors.danger)),
+            Span::styled(
+                format!("{} processes", targets.len()),
+                Style::default()
+                    .fg(colors.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" matching \"{}\"?", app.search_query),
+                Style::default().fg(colors.danger),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    // Room for the prompt lines above and the answer below
+    let room = (area.height as usize).saturating_sub(8).max(1);
+    for (name, count) in names.iter().take(room) {
+        text.push(Line::from(vec![
+            Span::styled(format!("    {count:>4} × "), Style::default().fg(colors.text_dim)),
+            Span::styled(name.to_string(), Style::default().fg(colors.text)),
+        ]));
+    }
+    if names.len() > room {
+        text.push(Line::from(Span::styled(
+            format!("    … and {} more names", names.len() - room),
+            Style::default().fg(colors.text_dim),
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled(
+            "  y",
+            Style::default()
+                .fg(colors.danger)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" Yes   "),
+        Span::styled(
+            "n",
+            Style::default()
+                .fg(colors.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" No"),
+    ]));
+
+    let popup = Paragraph::new(text).block(
+        Block::bordered()
+            .title(" Kill All Matching ")
             .border_style(Style::default().fg(colors.danger)),
     );
     frame.render_widget(popup, area);
@@ -159,8 +282,15 @@ pub fn draw_process_detail(frame: &mut Frame, app: &App, colors: &ThemeColors) {
         None => return,
     };
 
-    let lines = vec![
-        Line::from(""),
+    let mut lines = vec![
+        if detail.exited {
+            Line::from(Span::styled(
+                "  Process exited — showing its last stats",
+                Style::default().fg(colors.danger).add_modifier(Modifier::BOLD),
+            ))
+        } else {
+            Line::from("")
+        },
         detail_line("PID", &detail.base.pid.to_string(), colors),
         detail_line("Name", &detail.base.name, colors),
         detail_line("Status", &detail.base.status, colors),
@@ -168,6 +298,15 @@ pub fn draw_process_detail(frame: &mut Frame, app: &App, colors: &ThemeColors) {
         detail_line("CPU Usage", &format!("{:.1}%", detail.base.cpu), colors),
         detail_line("Memory", &format_bytes(detail.base.memory), colors),
         detail_line("Virtual Memory", &format_bytes(detail.virtual_memory), colors),
+        detail_line(
+            "Shared / Private",
+            &match (detail.base.shared_memory, detail.base.private_memory()) {
+                (Some(shared), Some(private)) => format!("{} / {}", format_bytes(shared), format_bytes(private)),
+                _ => "N/A".into(),
+            },
+            colors,
+        ),
+        detail_line("Swap", &detail.base.swap.map_or("N/A".to_string(), format_bytes), colors),
         detail_line(
             "Threads",
             &detail
@@ -178,8 +317,16 @@ pub fn draw_process_detail(frame: &mut Frame, app: &App, colors: &ThemeColors) {
         ),
         Line::from(""),
         detail_line("Runtime", &format_duration(detail.base.run_time), colors),
-        detail_line("Disk Read", &format_bytes(detail.base.disk_read), colors),
-        detail_line("Disk Write", &format_bytes(detail.base.disk_write), colors),
+        detail_line(
+            "Disk Read",
+            &format!("{} total · {}/s", format_bytes(detail.base.disk_read), format_bytes(detail.base.disk_read_rate)),
+            colors,
+        ),
+        detail_line(
+            "Disk Write",
+            &format!("{} total · {}/s", format_bytes(detail.base.disk_write), format_bytes(detail.base.disk_write_rate)),
+            colors,
+        ),
         Line::from(""),
         detail_line(
             "Parent PID",
@@ -217,6 +364,182 @@ pub fn draw_process_detail(frame: &mut Frame, app: &App, colors: &ThemeColors) {
             colors,
         ),
         detail_line("Env Variables", &detail.environ_count.to_string(), colors),
+    ];
+
+    // Only worth a section when there's more than one kind of thread
+    if detail.thread_groups.len() > 1 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Busiest threads by name",
+            Style::default()
+                .fg(colors.text_dim)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(detail.thread_groups.iter().take(MAX_THREAD_GROUPS).map(|g| {
+            Line::from(vec![
+                Span::styled(format!("  {:<18} ", g.name), Style::default().fg(colors.text)),
+                Span::styled(
+                    format!("{:>6.1}%", g.cpu),
+                    Style::default().fg(colors.cpu_usage_color(g.cpu as f64)),
+                ),
+                Span::styled(
+                    format!("  {} thread{}", g.threads, if g.threads == 1 { "" } else { "s" }),
+                    Style::default().fg(colors.text_dim),
+                ),
+            ])
+        }));
+    }
+    if !detail.children.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  Children ({})", detail.children.len()),
+            Style::default()
+                .fg(colors.text_dim)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let selected = app
+            .detail_child
+            .and_then(|pid| detail.children.iter().position(|c| c.pid == pid));
+        // Scroll just far enough to keep the highlighted child in view
+        let first = selected.map_or(0, |i| (i + 1).saturating_sub(MAX_CHILDREN));
+        lines.extend(detail.children.iter().enumerate().skip(first).take(MAX_CHILDREN).map(|(i, c)| {
+            let marker = if Some(i) == selected { "▶ " } else { "  " };
+            let mut line = Line::from(vec![
+                Span::styled(format!("  {marker}{:>7} ", c.pid), Style::default().fg(colors.accent)),
+                Span::styled(format!("{:<18.18} ", c.name), Style::default().fg(colors.text)),
+                Span::styled(
+                    format!("{:>6.1}%", c.cpu),
+                    Style::default().fg(colors.cpu_usage_color(c.cpu as f64)),
+                ),
+                Span::styled(format!("  {}", format_bytes(c.memory)), Style::default().fg(colors.text_dim)),
+            ]);
+            if Some(i) == selected {
+                line = line.patch_style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+            line
+        }));
+        let hidden = detail.children.len().saturating_sub(first + MAX_CHILDREN);
+        if hidden > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("      … {hidden} more"),
+                Style::default().fg(colors.text_dim),
+            )));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        if detail.children.is_empty() {
+            "  Press any key to close"
+        } else {
+            "  ↑/↓ pick a child · Enter to select it in the table · any other key to close"
+        },
+        Style::default().fg(colors.text_dim),
+    )));
+
+    let block = Block::bordered()
+        .title(format!(
+            " Process Detail — {} (PID {}){} ",
+            detail.base.name,
+            detail.base.pid,
+            if detail.exited { " — exited" } else { "" }
+        ))
+        .border_style(Style::default().fg(if detail.exited { colors.danger } else { colors.primary }));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let history = app.process_history.as_ref().filter(|h| h.pid == detail.base.pid);
+    let (body, graphs) = match history {
+        Some(history) if inner.height > PROCESS_GRAPH_HEIGHT * 3 => {
+            let [body, graphs] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(PROCESS_GRAPH_HEIGHT)]).areas(inner);
+            (body, Some((history, graphs)))
+        }
+        _ => (inner, None),
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), body);
+
+    if let Some((history, area)) = graphs {
+        let [cpu, memory] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(area);
+        let cpu_now = history.cpu.back().copied().unwrap_or(0.0);
+        let memory_now = history.memory.back().copied().unwrap_or(0.0);
+        draw_process_graph(
+            frame,
+            &history.cpu,
+            format!(" CPU {cpu_now:.1}% "),
+            // Tenths of a percent, so a process idling under 1% still shows
+            |v| (v * 10.0).round() as u64,
+            colors.cpu_usage_color(cpu_now),
+            colors,
+            cpu,
+        );
+        draw_process_graph(
+            frame,
+            &history.memory,
+            format!(" Memory {} ", format_bytes(memory_now as u64)),
+            |v| v as u64,
+            colors.accent,
+            colors,
+            memory,
+        );
+    }
+}
+
+/// One of the detail popup's sparklines, newest samples on the right.
+fn draw_process_graph(
+    frame: &mut Frame,
+    samples: &VecDeque<f64>,
+    title: String,
+    scale: impl Fn(f64) -> u64,
+    color: Color,
+    colors: &ThemeColors,
+    area: Rect,
+) {
+    let block = Block::bordered()
+        .title(title)
+        .border_style(Style::default().fg(colors.text_dim));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let skip = samples.len().saturating_sub(inner.width as usize);
+    let data: Vec<u64> = samples.iter().skip(skip).map(|&v| scale(v)).collect();
+    let spark = Sparkline::default()
+        .data(&data)
+        .bar_set(colors.bar_set.clone())
+        .style(Style::default().fg(color));
+    frame.render_widget(spark, inner);
+}
+
+pub fn draw_disk_detail(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let detail = match &app.disk_detail {
+        Some(d) => d,
+        None => return,
+    };
+    let or_na = |v: &Option<String>| v.clone().unwrap_or_else(|| "N/A".into());
+    let yes_no = |b: bool| if b { "Yes" } else { "No" };
+    let used = detail.total.saturating_sub(detail.available);
+
+    let lines = vec![
+        Line::from(""),
+        detail_line("Mount Point", &detail.mount, colors),
+        detail_line("Device", &detail.device, colors),
+        detail_line("Filesystem", &detail.file_system, colors),
+        detail_line("UUID", &or_na(&detail.uuid), colors),
+        detail_line("Label", &or_na(&detail.label), colors),
+        Line::from(""),
+        detail_line("Total", &format_bytes(detail.total), colors),
+        detail_line("Used", &format_bytes(used), colors),
+        detail_line("Available", &format_bytes(detail.available), colors),
+        Line::from(""),
+        detail_line(
+            "Rotational",
+            detail.rotational.map_or("Unknown", yes_no),
+            colors,
+        ),
+        detail_line("Removable", yes_no(detail.removable), colors),
+        detail_line("Read-only", yes_no(detail.read_only), colors),
+        detail_line("Mount Options", &or_na(&detail.mount_options), colors),
         Line::from(""),
         Line::from(Span::styled(
             "  Press any key to close",
@@ -227,12 +550,213 @@ pub fn draw_process_detail(frame: &mut Frame, app: &App, colors: &ThemeColors) {
     let popup = Paragraph::new(lines)
         .block(
             Block::bordered()
-                .title(format!(
-                    " Process Detail — {} (PID {}) ",
-                    detail.base.name, detail.base.pid
-                ))
-                .border_style(Style::default().fg(colors.primary)),
+                .title(format!(" Filesystem — {} ", detail.mount))
+                .border_style(Style::default().fg(colors.disk)),
         )
         .wrap(Wrap { trim: true });
     frame.render_widget(popup, area);
 }
+
+pub fn draw_interface_detail(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let detail = match &app.interface_detail {
+        Some(d) => d,
+        None => return,
+    };
+    let or_na = |v: &Option<String>| v.clone().unwrap_or_else(|| "N/A".into());
+
+    let mut lines = vec![
+        Line::from(""),
+        detail_line("Interface", &detail.name, colors),
+        detail_line("MAC Address", &detail.mac_address, colors),
+        detail_line("State", &or_na(&detail.link.state), colors),
+        Line::from(""),
+    ];
+    if detail.addresses.is_empty() {
+        lines.push(detail_line("Addresses", "None", colors));
+    }
+    for (i, network) in detail.addresses.iter().enumerate() {
+        let label = if network.addr.is_ipv4() { "IPv4" } else { "IPv6" };
+        let value = match ifinfo::netmask(network) {
+            Some(mask) => format!("{network} (netmask {mask})"),
+            None => network.to_string(),
+        };
+        // Label only the first address of each family
+        let first = !detail.addresses[..i]
+            .iter()
+            .any(|n| n.addr.is_ipv4() == network.addr.is_ipv4());
+        lines.push(detail_line(if first { label } else { "" }, &value, colors));
+    }
+    lines.extend([
+        Line::from(""),
+        detail_line("MTU", &detail.mtu.to_string(), colors),
+        detail_line(
+            "Link Speed",
+            &detail.link.speed_mbps.map_or_else(|| "N/A".into(), format_speed),
+            colors,
+        ),
+        detail_line("Duplex", &or_na(&detail.link.duplex), colors),
+        detail_line("Driver", &or_na(&detail.link.driver), colors),
+        Line::from(""),
+        detail_line("Total Received", &format_bytes(detail.received), colors),
+        detail_line("Total Sent", &format_bytes(detail.transmitted), colors),
+        detail_line(
+            "Dropped (RX / TX)",
+            &detail
+                .drops
+                .map_or_else(|| "N/A".into(), |(rx, tx)| format!("{rx} / {tx}")),
+            colors,
+        ),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Press any key to close",
+            Style::default().fg(colors.text_dim),
+        )),
+    ]);
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .title(format!(" Interface — {} ", detail.name))
+                .border_style(Style::default().fg(colors.network)),
+        )
+        .wrap(Wrap { trim: true });
+    frame.render_widget(popup, area);
+}
+
+pub fn draw_command_output(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let cmd = match app.last_attached_command() {
+        Some(c) => c,
+        None => return,
+    };
+
+    let state = match cmd.exit_status {
+        Some(status) => match status.code() {
+            Some(code) => format!("exited {code}"),
+            None => "killed".to_string(),
+        },
+        None => "running".to_string(),
+    };
+
+    // Show the tail of the output that fits in the popup
+    let output = cmd.output_lines();
+    let visible = area.height.saturating_sub(4) as usize;
+    let mut lines: Vec<Line> = output
+        .iter()
+        .skip(output.len().saturating_sub(visible))
+        .map(|l| Line::from(Span::styled(format!(" {l}"), Style::default().fg(colors.text))))
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " (no output yet)",
+            Style::default().fg(colors.text_dim),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press any key to close",
+        Style::default().fg(colors.text_dim),
+    )));
+
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(format!(" {} — PID {} ({state}) ", cmd.command, cmd.pid))
+            .border_style(Style::default().fg(colors.primary)),
+    );
+    frame.render_widget(popup, area);
+}
+
+/// Newest first, scrolled with j/k.
+pub fn draw_events(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let visible = area.height.saturating_sub(4) as usize;
+    let mut lines: Vec<Line> = app
+        .events
+        .newest_first()
+        .skip(app.events_scroll)
+        .take(visible)
+        .map(|event| {
+            let color = match event.level {
+                EventLevel::Info => colors.text_dim,
+                EventLevel::Warning => colors.warning,
+                EventLevel::Error => colors.danger,
+            };
+            Line::from(vec![
+                Span::styled(format!(" {:>9}  ", event.age()), Style::default().fg(colors.text_dim)),
+                Span::styled(format!("{:<6}", event.level.label()), Style::default().fg(color)),
+                Span::styled(event.message.clone(), Style::default().fg(colors.text)),
+            ])
+        })
+        .collect();
+    if app.events.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No events yet",
+            Style::default().fg(colors.text_dim),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  j/k to scroll, any other key to close",
+        Style::default().fg(colors.text_dim),
+    )));
+
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(format!(" Events ({}) ", app.events.len()))
+            .border_style(Style::default().fg(colors.primary)),
+    );
+    frame.render_widget(popup, area);
+}
+
+pub fn draw_profile(frame: &mut Frame, app: &App, colors: &ThemeColors) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let report = match &app.profile_report {
+        Some(r) => r,
+        None => return,
+    };
+
+    let visible = area.height.saturating_sub(5) as usize;
+    let mut lines = vec![Line::from("")];
+    lines.extend(report.functions.iter().take(visible).map(|(pct, symbol)| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {pct:>6.2}%  "),
+                Style::default().fg(colors.cpu_usage_color(*pct)),
+            ),
+            Span::styled(symbol.clone(), Style::default().fg(colors.text)),
+        ])
+    }));
+    if report.functions.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No samples — the process was idle",
+            Style::default().fg(colors.text_dim),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press any key to close",
+        Style::default().fg(colors.text_dim),
+    )));
+
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(format!(
+                " Profile — {} (PID {}) — {} samples over {}s ",
+                report.name,
+                report.pid,
+                report.samples,
+                report.secs
+            ))
+            .border_style(Style::default().fg(colors.primary)),
+    );
+    frame.render_widget(popup, area);
+}
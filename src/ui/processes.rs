@@ -1,127 +1,257 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
-    widgets::{Block, Cell, Paragraph, Row, Table},
+    text::{Line, Span},
+    widgets::{
+        Block, Cell, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
+        TableState,
+    },
     Frame,
 };
 
-use crate::app::{format_bytes, format_duration, App, InputMode};
+use super::helpers::{draw_search_bar, row_height};
+use crate::app::{format_bytes, format_duration, App, InputMode, ProcessColumn, ProcessInfo};
 use crate::theme::ThemeColors;
 
-pub fn draw_processes(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+/// Most leak suspects listed before the rest are summarised.
+const MAX_INSIGHTS: usize = 4;
+
+pub fn draw_processes(frame: &mut Frame, app: &mut App, colors: &ThemeColors, area: Rect) {
+    let insights_height = match app.leak_suspects.len() {
+        0 => 0,
+        n => n.min(MAX_INSIGHTS + 1) as u16 + 2,
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(insights_height),
+            Constraint::Length(1),
+        ])
         .split(area);
 
-    draw_search_bar(frame, app, colors, chunks[0]);
+    let search_title = match app.input_mode {
+        InputMode::Search => format!(" Search — {} (Tab: scope) ", app.search_scope.label()),
+        _ => format!(" Search — {} ", app.search_scope.label()),
+    };
+    draw_search_bar(
+        frame,
+        &search_title,
+        &app.search_query,
+        app.input_mode,
+        colors,
+        chunks[0],
+    );
+    draw_insights(frame, app, colors, chunks[2]);
+    draw_aggregates(frame, app, colors, chunks[3]);
 
     let sort_label = app.sort_by.label();
     let total = app.filtered_processes.len();
 
-    let header = Row::new(vec![
-        Cell::from("PID"),
-        Cell::from("Name"),
-        Cell::from("CPU%"),
-        Cell::from("Memory"),
-        Cell::from("Runtime"),
-        Cell::from("Disk R/W"),
-        Cell::from("Status"),
-    ])
+    let columns = app.visible_process_columns();
+    let header = Row::new(columns.iter().map(|&c| match c {
+        ProcessColumn::Name if app.show_cmdline => Cell::from("Command"),
+        c => Cell::from(c.label()),
+    }))
     .style(
         Style::default()
             .fg(colors.primary)
             .add_modifier(Modifier::BOLD),
     );
 
-    let visible_rows = chunks[1].height.saturating_sub(4) as usize;
+    let row_height = row_height(app);
+    // Borders and header
+    let visible_rows = ((chunks[1].height.saturating_sub(3) / row_height) as usize).max(1);
+    let selected = app.process_scroll.min(total.saturating_sub(1));
+    // The window only moves when the cursor would leave it
+    let mut offset = app.process_offset.min(total.saturating_sub(visible_rows));
+    if selected < offset {
+        offset = selected;
+    } else if selected >= offset + visible_rows {
+        offset = selected + 1 - visible_rows;
+    }
+    app.process_offset = offset;
+    app.list_rows = visible_rows;
+
     let rows: Vec<Row> = app
         .filtered_processes
         .iter()
-        .skip(app.process_scroll)
+        .skip(offset)
         .take(visible_rows)
-        .enumerate()
-        .filter_map(|(i, &idx)| {
+        .filter_map(|&idx| {
             let p = app.processes.get(idx)?;
-            let is_selected = i == 0;
-            let style = if is_selected {
-                Style::default().bg(colors.highlight_bg)
+            let name_style = if app.leak_suspects.iter().any(|s| s.pid == p.pid) {
+                Style::default().fg(colors.warning)
+            } else if app.pinned_pid == Some(p.pid) {
+                Style::default().fg(colors.accent).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
-            Some(
-                Row::new(vec![
-                    Cell::from(p.pid.to_string()),
-                    Cell::from(p.name.clone()),
-                    Cell::from(format!("{:.1}", p.cpu))
-                        .style(Style::default().fg(colors.cpu_usage_color(p.cpu as f64))),
-                    Cell::from(format_bytes(p.memory)),
-                    Cell::from(format_duration(p.run_time)),
-                    Cell::from(format!(
-                        "{}/{}",
-                        format_bytes(p.disk_read),
-                        format_bytes(p.disk_write)
-                    )),
-                    Cell::from(p.status.clone()),
-                ])
-                .style(style),
-            )
+            let cells = columns.iter().map(|&column| match column {
+                ProcessColumn::Name => {
+                    let name: String = app.process_label(p).chars().skip(app.name_scroll).collect();
+                    let name = if app.name_scroll > 0 { format!("…{name}") } else { name };
+                    Cell::from(name).style(name_style)
+                }
+                column => column_cell(column, p, app.total_memory, colors),
+            });
+            Some(Row::new(cells).height(row_height))
         })
         .collect();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(8),
-            Constraint::Min(16),
-            Constraint::Length(8),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(14),
-            Constraint::Length(10),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::bordered()
-            .title(format!(
-                " Processes ({total}) — Sort: {sort_label} — [{}/{}] ",
-                app.process_scroll + 1,
-                total
-            ))
-            .border_style(Style::default().fg(colors.primary)),
-    );
+    let widths: Vec<Constraint> = columns.iter().map(|&c| column_width(c)).collect();
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(colors.selected_style())
+        .block(
+            Block::bordered()
+                .title(format!(
+                    " Processes ({total}) — Sort: {sort_label} — [{}/{}]{} ",
+                    selected + 1,
+                    total,
+                    match app.name_scroll {
+                        0 => String::new(),
+                        n => format!(" — Name +{n}"),
+                    }
+                ))
+                .border_style(Style::default().fg(colors.primary)),
+        );
+    let mut state = TableState::default().with_selected((total > 0).then(|| selected - offset));
+    frame.render_stateful_widget(table, chunks[1], &mut state);
 
-    frame.render_widget(table, chunks[1]);
+    if total > visible_rows {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_symbol(Some(colors.block_empty))
+            .thumb_symbol(colors.block_full)
+            .style(Style::default().fg(colors.border));
+        let mut scroll = ScrollbarState::new(total.saturating_sub(visible_rows)).position(offset);
+        frame.render_stateful_widget(
+            scrollbar,
+            chunks[1].inner(Margin { vertical: 1, horizontal: 0 }),
+            &mut scroll,
+        );
+    }
 }
 
-fn draw_search_bar(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
-    let (label, style) = match app.input_mode {
-        InputMode::Search => (
-            format!(" Search: {}█ ", app.search_query),
-            Style::default().fg(colors.accent),
-        ),
-        InputMode::Normal => {
-            if app.search_query.is_empty() {
-                (
-                    " Press / to search ".to_string(),
-                    Style::default().fg(colors.text_dim),
-                )
-            } else {
-                (
-                    format!(" Filter: {} (Esc to clear) ", app.search_query),
-                    Style::default().fg(colors.accent),
-                )
-            }
+fn column_width(column: ProcessColumn) -> Constraint {
+    match column {
+        ProcessColumn::Pid => Constraint::Length(8),
+        ProcessColumn::Name => Constraint::Min(16),
+        ProcessColumn::Cpu => Constraint::Length(8),
+        ProcessColumn::Memory => Constraint::Length(10),
+        ProcessColumn::MemoryPercent => Constraint::Length(6),
+        ProcessColumn::Virtual | ProcessColumn::Shared | ProcessColumn::Private | ProcessColumn::Swap => {
+            Constraint::Length(10)
+        }
+        ProcessColumn::Runtime => Constraint::Length(10),
+        ProcessColumn::DiskIo => Constraint::Length(14),
+        ProcessColumn::NetIo => Constraint::Length(16),
+        ProcessColumn::Status => Constraint::Length(10),
+        ProcessColumn::User => Constraint::Length(12),
+        ProcessColumn::Threads => Constraint::Length(8),
+    }
+}
+
+fn column_cell(column: ProcessColumn, p: &ProcessInfo, total_memory: u64, colors: &ThemeColors) -> Cell<'static> {
+    match column {
+        ProcessColumn::Pid => Cell::from(p.pid.to_string()),
+        ProcessColumn::Name => Cell::from(p.name.clone()),
+        ProcessColumn::Cpu => Cell::from(format!("{:.1}", p.cpu))
+            .style(Style::default().fg(colors.cpu_usage_color(p.cpu as f64))),
+        ProcessColumn::Memory => Cell::from(format_bytes(p.memory)),
+        ProcessColumn::MemoryPercent => {
+            let pct = if total_memory > 0 { p.memory as f64 / total_memory as f64 * 100.0 } else { 0.0 };
+            Cell::from(format!("{pct:.1}"))
         }
+        ProcessColumn::Virtual => Cell::from(format_bytes(p.virtual_memory)),
+        ProcessColumn::Shared => Cell::from(p.shared_memory.map_or("-".to_string(), format_bytes)),
+        ProcessColumn::Private => Cell::from(p.private_memory().map_or("-".to_string(), format_bytes)),
+        ProcessColumn::Swap => Cell::from(p.swap.map_or("-".to_string(), format_bytes)),
+        ProcessColumn::Runtime => Cell::from(format_duration(p.run_time)),
+        ProcessColumn::DiskIo => Cell::from(format!(
+            "{}/{}",
+            format_bytes(p.disk_read_rate),
+            format_bytes(p.disk_write_rate)
+        )),
+        ProcessColumn::NetIo => Cell::from(format!(
+            "{}/{}",
+            format_bytes(p.net_rx_rate),
+            format_bytes(p.net_tx_rate)
+        )),
+        ProcessColumn::Status => Cell::from(p.status.clone()),
+        ProcessColumn::User => Cell::from(p.user.clone()),
+        ProcessColumn::Threads => Cell::from(p.threads.map_or("-".to_string(), |t| t.to_string())),
+    }
+}
+
+/// Processes whose memory has only grown over the leak window.
+fn draw_insights(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    if app.leak_suspects.is_empty() {
+        return;
+    }
+    let mut lines: Vec<Line> = app
+        .leak_suspects
+        .iter()
+        .take(MAX_INSIGHTS)
+        .map(|s| {
+            Line::from(vec![
+                Span::styled(" possible leak ", Style::default().fg(colors.warning)),
+                Span::styled(format!("{:<8}", s.pid), Style::default().fg(colors.text_dim)),
+                Span::styled(format!("{:<20} ", s.name), Style::default().fg(colors.text)),
+                Span::styled(format_bytes(s.memory), Style::default().fg(colors.memory)),
+                Span::styled(
+                    format!(
+                        "  +{}/min over {}",
+                        format_bytes(s.growth_per_min as u64),
+                        format_duration(s.window_secs)
+                    ),
+                    Style::default().fg(colors.warning),
+                ),
+            ])
+        })
+        .collect();
+    if app.leak_suspects.len() > MAX_INSIGHTS {
+        lines.push(Line::from(Span::styled(
+            format!(" … and {} more", app.leak_suspects.len() - MAX_INSIGHTS),
+            Style::default().fg(colors.text_dim),
+        )));
+    }
+    let panel = Paragraph::new(lines).block(
+        Block::bordered()
+            .title(" Insights ")
+            .border_style(Style::default().fg(colors.warning)),
+    );
+    frame.render_widget(panel, area);
+}
+
+/// Totals for the processes currently passing the filter.
+fn draw_aggregates(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
+    let (cpu, memory) = app
+        .filtered_processes
+        .iter()
+        .filter_map(|&idx| app.processes.get(idx))
+        .fold((0.0f32, 0u64), |(cpu, mem), p| (cpu + p.cpu, mem + p.memory));
+
+    let count = app.filtered_processes.len();
+    let noun = if app.search_query.is_empty() {
+        "processes"
+    } else if count == 1 {
+        "match"
+    } else {
+        "matches"
     };
 
-    let search = Paragraph::new(label)
-        .style(style)
-        .block(
-            Block::bordered()
-                .title(" Search ")
-                .border_style(Style::default().fg(colors.border)),
-        );
-    frame.render_widget(search, area);
+    let summary = Paragraph::new(Line::from(vec![
+        Span::styled(format!(" {count} {noun}: "), Style::default().fg(colors.text_dim)),
+        Span::styled(
+            format!("{cpu:.0}% CPU"),
+            Style::default().fg(colors.cpu_usage_color(cpu as f64 / app.cpu_count.max(1) as f64)),
+        ),
+        Span::styled(", ", Style::default().fg(colors.text_dim)),
+        Span::styled(format_bytes(memory), Style::default().fg(colors.memory)),
+    ]));
+    frame.render_widget(summary, area);
 }
@@ -6,9 +6,13 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{format_bytes, App};
+use crate::app::{format_bytes, format_duration, App, Graph, ANE_MAX_POWER_MW};
+use crate::peaks::Peak;
 use crate::theme::ThemeColors;
-use super::helpers::{info_line, shrink_rect};
+use super::helpers::{
+    draw_history_chart, fit_history, graph_caption, info_line, shrink_rect, status_line,
+    ChartSeries,
+};
 
 pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area: Rect) {
     let cols = Layout::default()
@@ -16,7 +20,7 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
-    let info_lines = vec![
+    let mut info_lines = vec![
         info_line("Hostname", &app.hostname, colors),
         info_line("OS", &format!("{} {}", app.os_name, app.os_version), colors),
         info_line("Kernel", &app.kernel_version, colors),
@@ -26,6 +30,31 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
         info_line("CPU", &app.cpu_brand, colors),
         info_line("Cores", &app.cpu_count.to_string(), colors),
         info_line("CPU Usage", &format!("{:.1}%", app.global_cpu), colors),
+    ];
+
+    // E/P cluster breakdown (Apple Silicon)
+    for cluster in &app.cpu_clusters {
+        let kind = if cluster.efficiency { "E" } else { "P" };
+        let mut value = format!("{}%", cluster.utilization);
+        if let Some(mhz) = cluster.freq_mhz {
+            value.push_str(&format!(" @ {mhz} MHz"));
+        }
+        if let Some(mw) = cluster.power_mw {
+            value.push_str(&format!("  {:.2} W", mw as f64 / 1000.0));
+        }
+        info_lines.push(info_line(&format!("  {kind}-Cluster {}", cluster.name), &value, colors));
+    }
+    if let Some(mw) = app.ane_power_mw {
+        info_lines.push(info_line("ANE Power", &format!("{:.2} W", mw as f64 / 1000.0), colors));
+    }
+    if let Some(mw) = app.cpu_power_mw {
+        info_lines.push(info_line("CPU Power", &format!("{:.2} W", mw as f64 / 1000.0), colors));
+    }
+    if let Some(mw) = app.package_power_mw {
+        info_lines.push(info_line("Package Power", &format!("{:.2} W", mw as f64 / 1000.0), colors));
+    }
+
+    info_lines.extend([
         Line::from(""),
         info_line("Total RAM", &format_bytes(app.total_memory), colors),
         info_line("Used RAM", &format_bytes(app.used_memory), colors),
@@ -37,7 +66,9 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
         info_line("Processes", &app.processes.len().to_string(), colors),
         info_line("Network Interfaces", &app.network_interfaces.len().to_string(), colors),
         info_line("Disks", &app.disks.iter().count().to_string(), colors),
-    ];
+        info_line("NVML", &app.nvml_status, colors),
+    ]);
+    info_lines.extend(peak_lines(app, colors));
 
     let mut gpu_lines: Vec<Line> = Vec::new();
     if !app.gpus.is_empty() {
@@ -60,9 +91,27 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
                 colors,
             ));
             gpu_lines.push(info_line("  VRAM", &mem_str, colors));
+            if let Some(t) = gpu.temp_junction {
+                gpu_lines.push(info_line("  Junction Temp", &format!("{t}°C"), colors));
+            }
+            if let Some(t) = gpu.temp_memory {
+                gpu_lines.push(info_line("  VRAM Temp", &format!("{t}°C"), colors));
+            }
+            if let Some(mhz) = gpu.core_clock_mhz {
+                gpu_lines.push(info_line("  Core Clock", &format!("{mhz} MHz"), colors));
+            }
+            if let Some(mhz) = gpu.memory_clock_mhz {
+                gpu_lines.push(info_line("  Memory Clock", &format!("{mhz} MHz"), colors));
+            }
+            if let Some(busy) = gpu.memory_busy {
+                gpu_lines.push(info_line("  Memory Busy", &format!("{busy}%"), colors));
+            }
             if let Some(fan) = gpu.fan_speed {
                 gpu_lines.push(info_line("  Fan Speed", &format!("{fan}%"), colors));
             }
+            if let Some(rpm) = gpu.fan_rpm {
+                gpu_lines.push(info_line("  Fan RPM", &rpm.to_string(), colors));
+            }
             if let Some(power) = gpu.power_usage {
                 let limit_str = gpu
                     .power_limit
@@ -82,6 +131,9 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
 
     let mut all_lines = info_lines;
     all_lines.extend(gpu_lines);
+    all_lines.extend(hardware_lines(app, colors));
+    all_lines.extend(vulnerability_lines(app, colors));
+    all_lines.extend(kernel_param_lines(app, colors));
 
     let info = Paragraph::new(all_lines)
         .block(
@@ -101,6 +153,9 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
     for _ in &app.gpus {
         right_constraints.push(Constraint::Length(5));
     }
+    if app.ane_power_mw.is_some() {
+        right_constraints.push(Constraint::Length(3)); // Neural Engine
+    }
     right_constraints.push(Constraint::Min(0)); // CPU History
 
     let right_chunks = Layout::default()
@@ -175,7 +230,10 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
             0
         };
         let gpu_block = Block::bordered()
-            .title(format!(" {} — {}°C ", gpu.name, gpu.temperature))
+            .title(match gpu.core_clock_mhz {
+                Some(mhz) => format!(" {} — {}°C @ {mhz} MHz ", gpu.name, gpu.temperature),
+                None => format!(" {} — {}°C ", gpu.name, gpu.temperature),
+            })
             .border_style(Style::default().fg(colors.accent));
         let gpu_inner = gpu_block.inner(right_chunks[chunk_idx]);
         frame.render_widget(gpu_block, right_chunks[chunk_idx]);
@@ -205,16 +263,189 @@ pub fn draw_system_info(frame: &mut Frame, app: &App, colors: &ThemeColors, area
         chunk_idx += 1;
     }
 
+    // Apple Neural Engine
+    if let Some(mw) = app.ane_power_mw {
+        let pct = (mw as f64 / ANE_MAX_POWER_MW as f64 * 100.0).min(100.0);
+        let ane_block = Block::bordered()
+            .title(" Neural Engine ")
+            .border_style(Style::default().fg(colors.secondary));
+        let ane_inner = ane_block.inner(right_chunks[chunk_idx]);
+        frame.render_widget(ane_block, right_chunks[chunk_idx]);
+        let ane_gauge = Gauge::default()
+            .gauge_style(Style::default().fg(colors.cpu_usage_color(pct)))
+            .percent(pct as u16)
+            .label(format!("ANE: {pct:.0}%  {:.2} W", mw as f64 / 1000.0));
+        frame.render_widget(ane_gauge, shrink_rect(ane_inner, 1, 0));
+        chunk_idx += 1;
+    }
+
     // CPU History
     let history_block = Block::bordered()
-        .title(" CPU History (60s) ")
+        .title(" CPU History ")
+        .title_bottom(graph_caption(app, &app.global_cpu_history, |v| format!("{v:.0}%")))
         .border_style(Style::default().fg(colors.cpu));
     let history_inner = history_block.inner(right_chunks[chunk_idx]);
     frame.render_widget(history_block, right_chunks[chunk_idx]);
-    let data: Vec<u64> = app.global_cpu_history.iter().map(|v| *v as u64).collect();
+    if app.line_charts.contains(&Graph::Cpu) {
+        let series = [ChartSeries {
+            name: "CPU",
+            history: &app.global_cpu_history,
+            color: colors.cpu,
+        }];
+        let percent = |v: f64| format!("{v:.0}%");
+        draw_history_chart(frame, app, &series, Some(100.0), percent, colors, history_inner);
+        return;
+    }
+    let data = fit_history(app, &app.global_cpu_history, history_inner.width);
     let sparkline = Sparkline::default()
         .data(&data)
+        .bar_set(colors.bar_set.clone())
         .max(100)
         .style(Style::default().fg(colors.cpu));
     frame.render_widget(sparkline, history_inner);
 }
+
+/// Session maxima with how long ago each was hit.
+fn peak_lines(app: &App, colors: &ThemeColors) -> Vec<Line<'static>> {
+    let peaks = &app.peaks;
+    let show = |label: &str, peak: Option<Peak>, value: &dyn Fn(f64) -> String| {
+        let text = match peak {
+            Some(p) => format!(
+                "{} ({} ago)",
+                value(p.value),
+                format_duration(p.at.elapsed().as_secs())
+            ),
+            None => "—".to_string(),
+        };
+        info_line(label, &text, colors)
+    };
+    let mut lines = vec![
+        Line::from(""),
+        info_line(
+            "Session Peaks",
+            &format!(
+                "last {} (r resets)",
+                format_duration(peaks.since.elapsed().as_secs())
+            ),
+            colors,
+        ),
+        show("  CPU", peaks.cpu, &|v| format!("{v:.1}%")),
+        show("  Memory", peaks.memory, &|v| format_bytes(v as u64)),
+        show("  Download", peaks.net_rx, &|v| app.format_rate(v as u64)),
+        show("  Upload", peaks.net_tx, &|v| app.format_rate(v as u64)),
+    ];
+    if !app.gpus.is_empty() {
+        lines.push(show("  GPU Temperature", peaks.gpu_temp, &|v| format!("{v:.0}°C")));
+    }
+    lines
+}
+
+fn hardware_lines(app: &App, colors: &ThemeColors) -> Vec<Line<'static>> {
+    let hw = &app.hardware;
+    let join = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => Some(format!("{a} {b}")),
+        (Some(v), None) | (None, Some(v)) => Some(v.clone()),
+        (None, None) => None,
+    };
+
+    let mut lines = vec![Line::from("")];
+    if let Some(system) = join(&hw.system_vendor, &hw.product_name) {
+        lines.push(info_line("System", &system, colors));
+    }
+    if let Some(board) = join(&hw.board_vendor, &hw.board_name) {
+        lines.push(info_line("Motherboard", &board, colors));
+    }
+    if let Some(version) = &hw.bios_version {
+        let mut bios = hw
+            .bios_vendor
+            .as_ref()
+            .map(|v| format!("{v} {version}"))
+            .unwrap_or_else(|| version.clone());
+        if let Some(date) = &hw.bios_date {
+            bios.push_str(&format!(" ({date})"));
+        }
+        lines.push(info_line("BIOS / Firmware", &bios, colors));
+    }
+    if app.show_identifiers {
+        for (label, value) in [
+            ("System Serial", &hw.system_serial),
+            ("Board Serial", &hw.board_serial),
+            ("Chassis Serial", &hw.chassis_serial),
+            ("Asset Tag", &hw.asset_tag),
+        ] {
+            if let Some(value) = value {
+                lines.push(info_line(label, value, colors));
+            }
+        }
+    }
+    for module in &hw.memory_modules {
+        let desc = [&module.size, &module.kind, &module.speed]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(info_line(&format!("RAM {}", module.slot), &desc, colors));
+    }
+
+    if lines.len() == 1 {
+        lines.push(info_line("Hardware", "Not available", colors));
+    }
+    lines
+}
+
+fn vulnerability_lines(app: &App, colors: &ThemeColors) -> Vec<Line<'static>> {
+    let vulns = &app.hardware.cpu_vulnerabilities;
+    if vulns.is_empty() {
+        return Vec::new();
+    }
+
+    let vulnerable = vulns.iter().filter(|v| v.is_vulnerable()).count();
+    let mitigated = vulns.iter().filter(|v| v.is_mitigated()).count();
+    let mut lines = vec![
+        Line::from(""),
+        status_line(
+            "CPU Vulnerabilities",
+            &format!("{vulnerable} vulnerable, {mitigated} mitigated, {} total", vulns.len()),
+            if vulnerable > 0 { colors.danger } else { colors.success },
+            colors,
+        ),
+    ];
+    for vuln in vulns {
+        let color = if vuln.is_vulnerable() {
+            colors.danger
+        } else if vuln.is_mitigated() {
+            colors.warning
+        } else {
+            colors.text_dim
+        };
+        lines.push(status_line(&format!("  {}", vuln.name), &vuln.status, color, colors));
+    }
+    lines
+}
+
+fn kernel_param_lines(app: &App, colors: &ThemeColors) -> Vec<Line<'static>> {
+    if app.kernel_params.is_empty() {
+        return Vec::new();
+    }
+
+    let flagged = app.kernel_params.iter().filter(|p| p.hint.is_some()).count();
+    let mut lines = vec![
+        Line::from(""),
+        status_line(
+            "Kernel Parameters",
+            &format!("{flagged} of {} outside recommendations", app.kernel_params.len()),
+            if flagged > 0 { colors.warning } else { colors.success },
+            colors,
+        ),
+    ];
+    for param in &app.kernel_params {
+        let (value, color) = match (&param.value, param.hint) {
+            (None, _) => ("unavailable".to_string(), colors.text_dim),
+            (Some(v), Some(hint)) => (format!("{v}  ({hint})"), colors.warning),
+            (Some(v), None) => (v.clone(), colors.text),
+        };
+        lines.push(status_line(&format!("  {}", param.name), &value, color, colors));
+    }
+    lines
+}
@@ -0,0 +1,82 @@
+//! Liveness signals for running as a long-lived dashboard under a service
+//! manager.
+//!
+//! Under systemd (`NOTIFY_SOCKET` set, e.g. `Type=notify` with
+//! `WatchdogSec=`) this sends `READY=1` once and `WATCHDOG=1` every tick;
+//! with `watchdog_file` configured it also bumps that file's modification
+//! time every tick for supervisors that watch a file instead. Both are sent
+//! from the UI loop, so a hung UI stops them and gets restarted.
+
+use std::fs::File;
+use std::time::SystemTime;
+
+pub struct Watchdog {
+    #[cfg(unix)]
+    notify: Option<std::os::unix::net::UnixDatagram>,
+    file: Option<String>,
+}
+
+impl Watchdog {
+    pub fn new(file: Option<String>) -> Self {
+        let watchdog = Self {
+            #[cfg(unix)]
+            notify: connect_notify_socket(),
+            file,
+        };
+        watchdog.notify("READY=1");
+        watchdog
+    }
+
+    /// Called once per tick.
+    pub fn ping(&self) {
+        self.notify("WATCHDOG=1");
+        if let Some(path) = &self.file {
+            // Touch: create if missing, otherwise only move the mtime
+            let _ = File::options()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|f| f.set_modified(SystemTime::now()));
+        }
+    }
+
+    #[cfg(unix)]
+    fn notify(&self, state: &str) {
+        if let Some(socket) = &self.notify {
+            let _ = socket.send(state.as_bytes());
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn notify(&self, _state: &str) {}
+}
+
+impl Drop for Watchdog {
+    /// Tell systemd the exit is deliberate rather than a crash.
+    fn drop(&mut self) {
+        self.notify("STOPPING=1");
+    }
+}
+
+/// `NOTIFY_SOCKET` is a filesystem path, or an abstract socket name
+/// written with a leading `@`.
+#[cfg(unix)]
+fn connect_notify_socket() -> Option<std::os::unix::net::UnixDatagram> {
+    use std::os::unix::net::UnixDatagram;
+
+    let path = std::env::var("NOTIFY_SOCKET").ok()?;
+    let socket = UnixDatagram::unbound().ok()?;
+    match path.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name).ok()?;
+            socket.connect_addr(&addr).ok()?;
+        }
+        // Abstract sockets only exist on Linux
+        #[cfg(not(target_os = "linux"))]
+        Some(_) => return None,
+        None => socket.connect(&path).ok()?,
+    }
+    Some(socket)
+}
@@ -0,0 +1,198 @@
+//! Wireless link details for the Network tab: SSID, signal, channel and
+//! link rate of each Wi-Fi interface.
+//!
+//! Linux asks nl80211 through `iw dev <iface> link`, falling back to the
+//! signal level in `/proc/net/wireless` when `iw` isn't installed; macOS
+//! reads the CoreWLAN-backed `system_profiler SPAirPortDataType` report
+//! (recent releases hide the SSID unless the app has location access).
+//! Both are slow enough to run as a background task.
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+
+pub struct WifiInfo {
+    pub interface: String,
+    /// `None` when not associated, or when the OS keeps it private
+    pub ssid: Option<String>,
+    pub signal_dbm: Option<i32>,
+    pub channel: Option<u32>,
+    pub frequency_mhz: Option<u32>,
+    /// Transmit rate in Mbit/s
+    pub bitrate_mbps: Option<f64>,
+    pub connected: bool,
+}
+
+impl WifiInfo {
+    /// 0–100% from dBm, the usual linear scale between -100 and -50 dBm.
+    pub fn quality(&self) -> Option<u8> {
+        self.signal_dbm
+            .map(|dbm| (2 * (dbm + 100)).clamp(0, 100) as u8)
+    }
+}
+
+/// IEEE channel number for a center frequency.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn channel_for(mhz: u32) -> Option<u32> {
+    match mhz {
+        2484 => Some(14),
+        2412..=2472 => Some((mhz - 2407) / 5),
+        5955..=7115 => Some((mhz - 5950) / 5),
+        5000..=5925 => Some((mhz - 5000) / 5),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn query() -> Vec<WifiInfo> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+        return Vec::new();
+    };
+    let mut interfaces: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|e| e.path().join("wireless").exists() || e.path().join("phy80211").exists())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    interfaces.sort();
+    interfaces
+        .into_iter()
+        .map(|name| iw_link(&name).unwrap_or_else(|| proc_wireless(name)))
+        .collect()
+}
+
+/// `iw dev wlan0 link` prints "Not connected." or
+///
+/// ```text
+/// Connected to aa:bb:cc:dd:ee:ff (on wlan0)
+///         SSID: home
+///         freq: 5180
+///         signal: -52 dBm
+///         tx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
+/// ```
+#[cfg(target_os = "linux")]
+fn iw_link(interface: &str) -> Option<WifiInfo> {
+    let out = Command::new("iw").args(["dev", interface, "link"]).output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&out.stdout);
+    let field = |name: &str| {
+        text.lines()
+            .find_map(|l| l.trim().strip_prefix(name))
+            .map(str::trim)
+    };
+    let number = |name: &str| {
+        field(name)
+            .and_then(|v| v.split_whitespace().next())
+            .and_then(|v| v.parse::<f64>().ok())
+    };
+    let frequency_mhz = number("freq:").map(|f| f as u32);
+    Some(WifiInfo {
+        interface: interface.to_string(),
+        connected: text.starts_with("Connected"),
+        ssid: field("SSID:").map(str::to_string),
+        signal_dbm: number("signal:").map(|s| s as i32),
+        channel: frequency_mhz.and_then(channel_for),
+        frequency_mhz,
+        bitrate_mbps: number("tx bitrate:"),
+    })
+}
+
+/// `/proc/net/wireless`: two header lines, then
+/// `wlan0: 0000   60.  -50.  -256  0 0 0 0 0 0` (status, link, level, noise).
+#[cfg(target_os = "linux")]
+fn proc_wireless(interface: String) -> WifiInfo {
+    let level = std::fs::read_to_string("/proc/net/wireless")
+        .ok()
+        .and_then(|text| {
+            text.lines().skip(2).find_map(|line| {
+                let (name, rest) = line.split_once(':')?;
+                if name.trim() != interface {
+                    return None;
+                }
+                let level: f64 = rest.split_whitespace().nth(2)?.trim_end_matches('.').parse().ok()?;
+                Some(level as i32)
+            })
+        });
+    WifiInfo {
+        interface,
+        ssid: None,
+        // Drivers report 0 while not associated
+        signal_dbm: level.filter(|&l| l < 0),
+        channel: None,
+        frequency_mhz: None,
+        bitrate_mbps: None,
+        connected: level.is_some_and(|l| l < 0),
+    }
+}
+
+/// `system_profiler SPAirPortDataType` nests each interface (`en0:`) under
+/// `Interfaces:`, with the associated network as
+///
+/// ```text
+///           Current Network Information:
+///             home:
+///               Channel: 149 (5GHz, 80MHz)
+///               Signal / Noise: -52 dBm / -90 dBm
+///               Transmit Rate: 867
+/// ```
+#[cfg(target_os = "macos")]
+pub fn query() -> Vec<WifiInfo> {
+    let Ok(out) = Command::new("system_profiler").arg("SPAirPortDataType").output() else {
+        return Vec::new();
+    };
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut list: Vec<WifiInfo> = Vec::new();
+    let mut in_current = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let Some(key) = trimmed.strip_suffix(':') else {
+            if let Some(wifi) = list.last_mut()
+                && in_current
+                && let Some((name, value)) = trimmed.split_once(": ")
+            {
+                match name {
+                    "Channel" => {
+                        wifi.channel = value.split_whitespace().next().and_then(|c| c.parse().ok())
+                    }
+                    "Signal / Noise" => {
+                        wifi.signal_dbm = value.split_whitespace().next().and_then(|s| s.parse().ok())
+                    }
+                    "Transmit Rate" => wifi.bitrate_mbps = value.trim().parse().ok(),
+                    _ => {}
+                }
+            }
+            continue;
+        };
+        let is_interface = key
+            .strip_prefix("en")
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if is_interface {
+            in_current = false;
+            list.push(WifiInfo {
+                interface: key.to_string(),
+                ssid: None,
+                signal_dbm: None,
+                channel: None,
+                frequency_mhz: None,
+                bitrate_mbps: None,
+                connected: false,
+            });
+        } else if key == "Current Network Information" {
+            in_current = true;
+        } else if in_current && let Some(wifi) = list.last_mut() {
+            if wifi.connected {
+                // Past the current network, into the list of other networks
+                in_current = false;
+            } else {
+                wifi.connected = true;
+                wifi.ssid = Some(key.to_string()).filter(|s| s != "<redacted>");
+            }
+        }
+    }
+    list
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn query() -> Vec<WifiInfo> {
+    Vec::new()
+}
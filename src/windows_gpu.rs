@@ -0,0 +1,339 @@
+//! Windows GPU monitoring for adapters NVML doesn't cover (AMD, Intel).
+//!
+//! Adapters come from DXGI, which gives each one's name, memory sizes and
+//! LUID. Utilization and memory in use come from the same PDH performance
+//! counters Task Manager uses (`GPU Engine` and `GPU Adapter Memory`),
+//! whose instance names carry the adapter's LUID, so every reading lands
+//! on the right card however many there are. The query stays open and is
+//! collected once per tick; PDH expands the `*` instances on each collect,
+//! so engines of newly started processes are picked up.
+//!
+//! Integrated GPUs have little or no dedicated memory and work out of
+//! shared system memory, so for those the shared figures are reported.
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::ptr;
+
+pub struct WindowsGpuReading {
+    pub name: String,
+    pub utilization: u32,
+    pub memory_used: u64,
+    pub memory_total: u64,
+}
+
+// ─── DXGI ────────────────────────────────────────────────────────────
+
+#[repr(C)]
+struct Guid {
+    data1: u32,
+    data2: u16,
+    data3: u16,
+    data4: [u8; 8],
+}
+
+/// {770aae78-f26f-4dba-a829-253c83d1b387}
+const IID_IDXGI_FACTORY1: Guid = Guid {
+    data1: 0x770a_ae78,
+    data2: 0xf26f,
+    data3: 0x4dba,
+    data4: [0xa8, 0x29, 0x25, 0x3c, 0x83, 0xd1, 0xb3, 0x87],
+};
+
+#[repr(C)]
+struct Luid {
+    low_part: u32,
+    high_part: i32,
+}
+
+#[repr(C)]
+struct AdapterDesc1 {
+    description: [u16; 128],
+    vendor_id: u32,
+    device_id: u32,
+    sub_sys_id: u32,
+    revision: u32,
+    dedicated_video_memory: usize,
+    dedicated_system_memory: usize,
+    shared_system_memory: usize,
+    adapter_luid: Luid,
+    flags: u32,
+}
+
+/// Microsoft Basic Render Driver and other software rasterizers
+const DXGI_ADAPTER_FLAG_SOFTWARE: u32 = 2;
+
+// Vtable slots: IUnknown (0-2), IDXGIObject (3-6), then the interface's own
+const SLOT_RELEASE: usize = 2;
+const SLOT_ENUM_ADAPTERS1: usize = 12;
+const SLOT_GET_DESC1: usize = 10;
+
+type ReleaseFn = unsafe extern "system" fn(this: *mut c_void) -> u32;
+type EnumAdapters1Fn = unsafe extern "system" fn(this: *mut c_void, index: u32, adapter: *mut *mut c_void) -> i32;
+type GetDesc1Fn = unsafe extern "system" fn(this: *mut c_void, desc: *mut AdapterDesc1) -> i32;
+
+#[link(name = "dxgi")]
+unsafe extern "system" {
+    fn CreateDXGIFactory1(riid: *const Guid, factory: *mut *mut c_void) -> i32;
+}
+
+// ─── PDH ─────────────────────────────────────────────────────────────
+
+const PDH_FMT_DOUBLE: u32 = 0x0000_0200;
+const PDH_MORE_DATA: u32 = 0x8000_07D2;
+/// `PDH_CSTATUS_VALID_DATA` and `PDH_CSTATUS_NEW_DATA`
+const PDH_CSTATUS_OK: [u32; 2] = [0, 1];
+
+#[repr(C)]
+struct PdhFmtCounterValue {
+    c_status: u32,
+    // The union's double member; the others are unused with PDH_FMT_DOUBLE
+    value: f64,
+}
+
+#[repr(C)]
+struct PdhFmtCounterValueItem {
+    name: *const u16,
+    value: PdhFmtCounterValue,
+}
+
+#[link(name = "pdh")]
+unsafe extern "system" {
+    fn PdhOpenQueryW(data_source: *const u16, user_data: usize, query: *mut isize) -> u32;
+    fn PdhAddEnglishCounterW(query: isize, path: *const u16, user_data: usize, counter: *mut isize) -> u32;
+    fn PdhCollectQueryData(query: isize) -> u32;
+    fn PdhGetFormattedCounterArrayW(
+        counter: isize,
+        format: u32,
+        buffer_size: *mut u32,
+        item_count: *mut u32,
+        items: *mut PdhFmtCounterValueItem,
+    ) -> u32;
+    fn PdhCloseQuery(query: isize) -> u32;
+}
+
+struct Adapter {
+    /// As it appears in counter instance names, lowercased:
+    /// `0x00000000_0x0000c3f4`
+    luid: String,
+    name: String,
+    dedicated_total: u64,
+    shared_total: u64,
+}
+
+pub struct WindowsGpuSampler {
+    adapters: Vec<Adapter>,
+    query: isize,
+    utilization: isize,
+    dedicated: isize,
+    shared: isize,
+}
+
+impl WindowsGpuSampler {
+    /// `None` without a hardware adapter or the GPU performance counters
+    /// (older than Windows 10 1709).
+    pub fn new() -> Option<Self> {
+        let adapters = enumerate_adapters();
+        if adapters.is_empty() {
+            return None;
+        }
+        let mut query = 0;
+        // SAFETY: a null data source means live data; `query` is written on success
+        if unsafe { PdhOpenQueryW(ptr::null(), 0, &mut query) } != 0 {
+            return None;
+        }
+        let add = |path: &str| {
+            let path: Vec<u16> = path.encode_utf16().chain([0]).collect();
+            let mut counter = 0;
+            // SAFETY: `path` is NUL-terminated and outlives the call
+            (unsafe { PdhAddEnglishCounterW(query, path.as_ptr(), 0, &mut counter) } == 0).then_some(counter)
+        };
+        let counters = (
+            add(r"\GPU Engine(*)\Utilization Percentage"),
+            add(r"\GPU Adapter Memory(*)\Dedicated Usage"),
+            add(r"\GPU Adapter Memory(*)\Shared Usage"),
+        );
+        let (Some(utilization), Some(dedicated), Some(shared)) = counters else {
+            // SAFETY: opened above and not used again
+            unsafe { PdhCloseQuery(query) };
+            return None;
+        };
+        // Utilization is a rate, so it needs a first sample to diff against
+        // SAFETY: `query` is open
+        unsafe { PdhCollectQueryData(query) };
+        Some(Self {
+            adapters,
+            query,
+            utilization,
+            dedicated,
+            shared,
+        })
+    }
+
+    /// Latest readings, one per hardware adapter in DXGI order.
+    pub fn readings(&mut self) -> Vec<WindowsGpuReading> {
+        // SAFETY: `query` stays open until drop
+        if unsafe { PdhCollectQueryData(self.query) } != 0 {
+            return Vec::new();
+        }
+        // luid → engine type → utilization summed over processes
+        let mut engines: HashMap<String, HashMap<String, f64>> = HashMap::new();
+        for (instance, value) in counter_values(self.utilization) {
+            if let (Some(luid), Some(engtype)) = (instance_luid(&instance), instance_field(&instance, "engtype_")) {
+                *engines.entry(luid).or_default().entry(engtype).or_default() += value;
+            }
+        }
+        let per_adapter = |counter| {
+            let mut bytes: HashMap<String, u64> = HashMap::new();
+            for (instance, value) in counter_values(counter) {
+                if let Some(luid) = instance_luid(&instance) {
+                    *bytes.entry(luid).or_default() += value as u64;
+                }
+            }
+            bytes
+        };
+        let dedicated = per_adapter(self.dedicated);
+        let shared = per_adapter(self.shared);
+
+        self.adapters
+            .iter()
+            .map(|adapter| {
+                // Like Task Manager: the busiest engine type is the GPU's load
+                let utilization = engines
+                    .get(&adapter.luid)
+                    .and_then(|e| e.values().copied().reduce(f64::max))
+                    .unwrap_or(0.0)
+                    .min(100.0) as u32;
+                let (memory_used, memory_total) = if adapter.dedicated_total > 0 {
+                    (dedicated.get(&adapter.luid).copied().unwrap_or(0), adapter.dedicated_total)
+                } else {
+                    (shared.get(&adapter.luid).copied().unwrap_or(0), adapter.shared_total)
+                };
+                WindowsGpuReading {
+                    name: adapter.name.clone(),
+                    utilization,
+                    memory_used,
+                    memory_total,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Drop for WindowsGpuSampler {
+    fn drop(&mut self) {
+        // SAFETY: opened in `new` and not used after this
+        unsafe { PdhCloseQuery(self.query) };
+    }
+}
+
+/// Hardware adapters from DXGI, skipping software renderers.
+fn enumerate_adapters() -> Vec<Adapter> {
+    let mut adapters = Vec::new();
+    let mut factory: *mut c_void = ptr::null_mut();
+    // SAFETY: the IID matches the interface `factory` receives
+    if unsafe { CreateDXGIFactory1(&IID_IDXGI_FACTORY1, &mut factory) } < 0 || factory.is_null() {
+        return adapters;
+    }
+    for index in 0.. {
+        let mut adapter: *mut c_void = ptr::null_mut();
+        // SAFETY: `factory` is a live IDXGIFactory1, so slot 12 is EnumAdapters1;
+        // any failure, including DXGI_ERROR_NOT_FOUND past the last adapter, ends the list
+        let found = unsafe {
+            let enum_adapters: EnumAdapters1Fn = std::mem::transmute(vtable_slot(factory, SLOT_ENUM_ADAPTERS1));
+            enum_adapters(factory, index, &mut adapter)
+        };
+        if found < 0 || adapter.is_null() {
+            break;
+        }
+        // SAFETY: `adapter` is a live IDXGIAdapter1, so slot 10 is GetDesc1,
+        // and it's released once described
+        let desc = unsafe {
+            let get_desc: GetDesc1Fn = std::mem::transmute(vtable_slot(adapter, SLOT_GET_DESC1));
+            let mut desc: AdapterDesc1 = std::mem::zeroed();
+            let ok = get_desc(adapter, &mut desc) >= 0;
+            release(adapter);
+            ok.then_some(desc)
+        };
+        let Some(desc) = desc else {
+            continue;
+        };
+        if desc.flags & DXGI_ADAPTER_FLAG_SOFTWARE != 0 {
+            continue;
+        }
+        let name_len = desc.description.iter().position(|&c| c == 0).unwrap_or(desc.description.len());
+        adapters.push(Adapter {
+            luid: format!(
+                "0x{:08x}_0x{:08x}",
+                desc.adapter_luid.high_part as u32, desc.adapter_luid.low_part
+            ),
+            name: String::from_utf16_lossy(&desc.description[..name_len]).trim().to_string(),
+            dedicated_total: desc.dedicated_video_memory as u64,
+            shared_total: desc.shared_system_memory as u64,
+        });
+    }
+    // SAFETY: created above and not used after this
+    unsafe { release(factory) };
+    adapters
+}
+
+/// # Safety
+/// `object` must be a live COM object whose vtable has at least `slot + 1` entries.
+unsafe fn vtable_slot(object: *mut c_void, slot: usize) -> *const c_void {
+    unsafe { *(*(object as *const *const *const c_void)).add(slot) }
+}
+
+/// # Safety
+/// `object` must be a live COM object the caller holds a reference to.
+unsafe fn release(object: *mut c_void) {
+    unsafe {
+        let release: ReleaseFn = std::mem::transmute(vtable_slot(object, SLOT_RELEASE));
+        release(object);
+    }
+}
+
+/// Every instance of a wildcard counter with its value from the last
+/// collect, skipping instances without valid data yet.
+fn counter_values(counter: isize) -> Vec<(String, f64)> {
+    let (mut size, mut count) = (0u32, 0u32);
+    // SAFETY: a zero size asks only for the buffer size needed
+    let status = unsafe { PdhGetFormattedCounterArrayW(counter, PDH_FMT_DOUBLE, &mut size, &mut count, ptr::null_mut()) };
+    if status != PDH_MORE_DATA || size == 0 {
+        return Vec::new();
+    }
+    // u64s so the items, which hold pointers and doubles, are aligned
+    let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+    let items = buffer.as_mut_ptr() as *mut PdhFmtCounterValueItem;
+    // SAFETY: `buffer` holds `size` bytes, as PDH asked for
+    if unsafe { PdhGetFormattedCounterArrayW(counter, PDH_FMT_DOUBLE, &mut size, &mut count, items) } != 0 {
+        return Vec::new();
+    }
+    // SAFETY: PDH wrote `count` items at the start of `buffer`, with the
+    // instance names stored after them in the same buffer
+    let items = unsafe { std::slice::from_raw_parts(items, count as usize) };
+    items
+        .iter()
+        .filter(|item| PDH_CSTATUS_OK.contains(&item.value.c_status) && !item.name.is_null())
+        .map(|item| {
+            // SAFETY: each name is a NUL-terminated string inside `buffer`
+            let name = unsafe {
+                let len = (0..).take_while(|&i| *item.name.add(i) != 0).count();
+                String::from_utf16_lossy(std::slice::from_raw_parts(item.name, len))
+            };
+            (name, item.value.value)
+        })
+        .collect()
+}
+
+/// "pid_1234_luid_0x00000000_0x0000C3F4_phys_0_eng_0_engtype_3D" → "0x00000000_0x0000c3f4"
+fn instance_luid(instance: &str) -> Option<String> {
+    let start = instance.find("luid_")? + "luid_".len();
+    let rest = &instance[start..];
+    let end = rest.find("_phys").unwrap_or(rest.len());
+    Some(rest[..end].to_ascii_lowercase())
+}
+
+fn instance_field(instance: &str, prefix: &str) -> Option<String> {
+    let start = instance.find(prefix)? + prefix.len();
+    Some(instance[start..].to_string())
+}